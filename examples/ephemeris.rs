@@ -0,0 +1,64 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+use astrology::cfg::parse_args_ephemeris;
+use astrology::svg_draw::{
+    ephemeris, ephemeris_csv, ephemeris_json, ChartOptions, DataChartNatal,
+};
+use chrono::{Datelike, NaiveDate};
+use std::error::Error;
+use std::fs::File;
+use std::io::Write;
+
+/// Date at 0h UT
+fn data(date: NaiveDate) -> DataChartNatal {
+    DataChartNatal {
+        year: date.year(),
+        month: date.month(),
+        day: date.day(),
+        hour: 0,
+        min: 0,
+        sec: 0.0,
+        lat: 0.0,
+        lng: 0.0,
+        time_zone: 0.0,
+    }
+}
+
+/// Write an ephemeris table in csv or json
+fn main() -> Result<(), Box<dyn Error>> {
+    let cfg = parse_args_ephemeris()?;
+    let options = ChartOptions {
+        zodiac: cfg.zodiac,
+        bodies: cfg.bodies,
+        ..ChartOptions::default()
+    };
+    let rows = ephemeris(
+        data(cfg.date_start),
+        data(cfg.date_end),
+        cfg.step,
+        cfg.path_ephem_files.as_str(),
+        &options,
+    )?;
+    let export = match cfg.format.as_str() {
+        "json" => ephemeris_json(&rows)?,
+        _ => ephemeris_csv(&rows),
+    };
+    let mut file_export = File::create(&cfg.path_and_file)?;
+    file_export.write_all(export.as_bytes())?;
+    println!("File exported to: {}", cfg.path_and_file);
+    Ok(())
+}
@@ -15,17 +15,20 @@
  * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
  */
 use astrology::cfg::parse_args_natal;
-use astrology::svg_draw::{chart_svg, DataChartNatal};
+use astrology::svg_draw::{
+    chart_svg, ChartOptions, DataChartNatal, FixedStarOptions, LotOptions,
+};
 use chrono::{Datelike, Timelike};
 use libswe_sys::sweconst::{AspectsFilter, Language};
 use num_traits::FromPrimitive;
+use std::error::Error;
 use std::ffi::{CStr, CString};
 use std::fs::File;
 use std::io::Write;
 
 /// Write svg natal chart
-fn main() {
-    let cfg = parse_args_natal();
+fn main() -> Result<(), Box<dyn Error>> {
+    let cfg = parse_args_natal()?;
     let d = DataChartNatal {
         year: cfg.date.year(),
         month: cfg.date.month(),
@@ -37,19 +40,41 @@ fn main() {
         lng: cfg.lng,
         time_zone: cfg.time_zone,
     };
-    let mut file_export = File::create(&cfg.path_and_file).unwrap();
-    let path = CString::new(cfg.path_ephem_files.as_str())
-        .expect("CString::new failled");
+    let mut file_export = File::create(&cfg.path_and_file)?;
+    let path = CString::new(cfg.path_ephem_files.as_str())?;
     let path_c_str = unsafe { CStr::from_ptr(path.as_ptr()) };
-    let path_str: &str = path_c_str.to_str().unwrap();
+    let path_str: &str = path_c_str.to_str()?;
     println!("{}", &path_str);
     let aspect: AspectsFilter = match FromPrimitive::from_u32(cfg.aspect) {
         Some(a) => a,
         None => AspectsFilter::NoAspects,
     };
-
-    let svg: String =
-        chart_svg(cfg.size as f32, d, &path_str, Language::English, aspect);
-    file_export.write_all(svg.as_bytes()).unwrap();
+    let options = ChartOptions {
+        house_system: cfg.house_system,
+        zodiac: cfg.zodiac,
+        bodies: cfg.bodies,
+        stars: FixedStarOptions {
+            ring: cfg.fixed_stars,
+            ..FixedStarOptions::default()
+        },
+        lots: LotOptions {
+            draw: cfg.lots,
+            ..LotOptions::default()
+        },
+        sect_label: cfg.sect,
+        style: cfg.style,
+        aspects: cfg.aspect_config,
+        ..ChartOptions::default()
+    };
+    let svg: String = chart_svg(
+        cfg.size as f32,
+        d,
+        &path_str,
+        Language::English,
+        aspect,
+        &options,
+    )?;
+    file_export.write_all(svg.as_bytes())?;
     println!("File exported to: {}", cfg.path_and_file);
+    Ok(())
 }
@@ -15,17 +15,20 @@
  * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
  */
 use astrology::cfg::parse_args_transit;
-use astrology::svg_draw::{chart_svg_with_transit, DataChartNatal};
+use astrology::svg_draw::{
+    chart_svg_with_transit, ChartOptions, DataChartNatal,
+};
 use chrono::{Datelike, Timelike};
 use libswe_sys::sweconst::{AspectsFilter, Language};
 use num_traits::FromPrimitive;
+use std::error::Error;
 use std::ffi::{CStr, CString};
 use std::fs::File;
 use std::io::Write;
 
 /// Write svg natal + transit chart
-fn main() {
-    let cfg = parse_args_transit();
+fn main() -> Result<(), Box<dyn Error>> {
+    let cfg = parse_args_transit()?;
     let d_n = DataChartNatal {
         year: cfg.date_n.year(),
         month: cfg.date_n.month(),
@@ -48,16 +51,25 @@ fn main() {
         lng: cfg.lng_t,
         time_zone: cfg.time_zone_t,
     };
-    let mut file_export = File::create(&cfg.path_and_file).unwrap();
-    let path = CString::new(cfg.path_ephem_files.as_str())
-        .expect("CString::new failled");
+    let mut file_export = File::create(&cfg.path_and_file)?;
+    let path = CString::new(cfg.path_ephem_files.as_str())?;
     let path_c_str = unsafe { CStr::from_ptr(path.as_ptr()) };
-    let path_str: &str = path_c_str.to_str().unwrap();
+    let path_str: &str = path_c_str.to_str()?;
     println!("{}", &path_str);
     let aspect: AspectsFilter = match FromPrimitive::from_u32(cfg.aspect) {
         Some(a) => a,
         None => AspectsFilter::NoAspects,
     };
+    let options = ChartOptions {
+        house_system: cfg.house_system,
+        zodiac: cfg.zodiac,
+        bodies: cfg.bodies,
+        style: cfg.style,
+        aspects: cfg.aspect_config,
+        transit_houses: cfg.transit_houses,
+        lunation_markers: cfg.lunation_markers,
+        ..ChartOptions::default()
+    };
     let svg: String = chart_svg_with_transit(
         1000.0,
         d_n,
@@ -65,7 +77,9 @@ fn main() {
         &path_str,
         Language::English,
         aspect,
-    );
-    file_export.write_all(svg.as_bytes()).unwrap();
+        &options,
+    )?;
+    file_export.write_all(svg.as_bytes())?;
     println!("File exported to: {}", cfg.path_and_file);
+    Ok(())
 }
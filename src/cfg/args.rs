@@ -18,12 +18,17 @@ use super::parse::{
     parse_date, parse_date_from_str, parse_time, parse_time_from_str,
 };
 use super::validator::{
-    validator_parse_aspect, validator_parse_date, validator_parse_latlng,
-    validator_parse_path, validator_parse_size, validator_parse_time,
-    validator_parse_timezone,
+    validator_parse_aspect, validator_parse_bodies, validator_parse_date,
+    validator_parse_house_system, validator_parse_latlng, validator_parse_path,
+    validator_parse_size, validator_parse_step, validator_parse_time,
+    validator_parse_timezone, validator_parse_zodiac,
+};
+use crate::error::AstrologyError;
+use crate::svg_draw::{
+    AspectConfig, BodySelection, ChartStyle, HouseSystem, Zodiac,
 };
 use chrono::{Datelike, NaiveDate, NaiveTime, Timelike, Utc};
-use clap::{App, AppSettings, Arg};
+use clap::{App, AppSettings, Arg, ArgMatches};
 use std::env;
 use std::format;
 use std::str::FromStr;
@@ -35,10 +40,18 @@ pub struct AstrologyConfig {
     pub lat: f32,
     pub lng: f32,
     pub time_zone: f32,
+    pub fixed_stars: bool,
+    pub lots: bool,
+    pub sect: bool,
     pub path_and_file: String,
     pub path_ephem_files: String,
     pub size: u32,
     pub aspect: u32,
+    pub house_system: HouseSystem,
+    pub zodiac: Zodiac,
+    pub bodies: BodySelection,
+    pub style: ChartStyle,
+    pub aspect_config: AspectConfig,
 }
 
 #[derive(Debug)]
@@ -53,10 +66,31 @@ pub struct AstrologyTransitConfig {
     pub lat_t: f32,
     pub lng_t: f32,
     pub time_zone_t: f32,
+    pub transit_houses: bool,
+    pub lunation_markers: bool,
     pub path_and_file: String,
     pub path_ephem_files: String,
     pub size: u32,
     pub aspect: u32,
+    pub house_system: HouseSystem,
+    pub zodiac: Zodiac,
+    pub bodies: BodySelection,
+    pub style: ChartStyle,
+    pub aspect_config: AspectConfig,
+}
+
+#[derive(Debug)]
+pub struct AstrologyEphemerisConfig {
+    pub date_start: NaiveDate,
+    pub date_end: NaiveDate,
+    /// Step in days
+    pub step: f64,
+    /// "csv" or "json"
+    pub format: String,
+    pub path_and_file: String,
+    pub path_ephem_files: String,
+    pub zodiac: Zodiac,
+    pub bodies: BodySelection,
 }
 
 const AUTHOR: &str =
@@ -71,6 +105,14 @@ const ASPECT: &str = "aspect";
 const PATH: &str = "path_and_file";
 const PATH_EPHEM: &str = "path_ephem";
 const SIZE: &str = "size";
+const HOUSE_SYSTEM: &str = "house_system";
+const ZODIAC: &str = "zodiac";
+const BODIES: &str = "bodies";
+const THEME: &str = "theme";
+const ASPECT_CONFIG: &str = "aspect_config";
+const FIXED_STARS: &str = "fixed_stars";
+const LOTS: &str = "lots";
+const SECT: &str = "sect";
 
 const DATE_N: &str = "date_natal";
 const TIME_N: &str = "time_natal";
@@ -82,11 +124,183 @@ const TIME_T: &str = "time_transt";
 const LAT_T: &str = "lat_transit";
 const LNG_T: &str = "lng_transit";
 const TIME_ZONE_T: &str = "time_zone_transit";
+const TRANSIT_HOUSES: &str = "transit_houses";
+const LUNATION_MARKERS: &str = "lunation_markers";
+
+const DATE_START: &str = "start_date";
+const DATE_END: &str = "end_date";
+const STEP: &str = "step";
+const FORMAT: &str = "format";
+
+/// Value of an argument
+fn value_of<'a>(
+    matches: &'a ArgMatches,
+    name: &str,
+) -> Result<&'a str, AstrologyError> {
+    matches.value_of(name).ok_or_else(|| {
+        AstrologyError::InvalidArgument(format!("{} is missing", name))
+    })
+}
+
+/// Value of an argument in f32
+fn value_of_f32(
+    matches: &ArgMatches,
+    name: &str,
+    error: fn(String) -> AstrologyError,
+) -> Result<f32, AstrologyError> {
+    let value = value_of(matches, name)?;
+    f32::from_str(value)
+        .map_err(|_| error(format!("{}: {} isn't a number", name, value)))
+}
+
+/// Value of an argument in u32
+fn value_of_u32(
+    matches: &ArgMatches,
+    name: &str,
+) -> Result<u32, AstrologyError> {
+    let value = value_of(matches, name)?;
+    value.parse::<u32>().map_err(|_| {
+        AstrologyError::InvalidArgument(format!(
+            "{}: {} isn't a number",
+            name, value
+        ))
+    })
+}
+
+/// Value of the argument house system
+fn value_of_house_system(
+    matches: &ArgMatches,
+) -> Result<HouseSystem, AstrologyError> {
+    HouseSystem::from_str(value_of(matches, HOUSE_SYSTEM)?)
+        .map_err(AstrologyError::InvalidArgument)
+}
+
+/// Value of the argument zodiac
+fn value_of_zodiac(matches: &ArgMatches) -> Result<Zodiac, AstrologyError> {
+    Zodiac::from_str(value_of(matches, ZODIAC)?)
+        .map_err(AstrologyError::InvalidArgument)
+}
+
+/// Value of the argument bodies, CHART_BODIES without the argument
+fn value_of_bodies(
+    matches: &ArgMatches,
+) -> Result<BodySelection, AstrologyError> {
+    match matches.value_of(BODIES) {
+        Some(bodies) => BodySelection::from_str(bodies),
+        None => Ok(BodySelection::default()),
+    }
+}
+
+fn value_of_style(matches: &ArgMatches) -> Result<ChartStyle, AstrologyError> {
+    match value_of(matches, THEME)? {
+        "light" => Ok(ChartStyle::light()),
+        "dark" => Ok(ChartStyle::dark()),
+        t => Err(AstrologyError::InvalidArgument(format!(
+            "unknown theme: {}",
+            t
+        ))),
+    }
+}
+
+fn value_of_aspect_config(
+    matches: &ArgMatches,
+) -> Result<AspectConfig, AstrologyError> {
+    match matches.value_of(ASPECT_CONFIG) {
+        Some(path) => AspectConfig::from_json_file(path),
+        None => Ok(AspectConfig::default()),
+    }
+}
+
+/// Default path for the export
+fn default_path(file: &str) -> String {
+    match env::current_dir() {
+        Ok(dir) => format!("{}/{}", dir.as_path().display(), file),
+        Err(_) => file.to_string(),
+    }
+}
+
+/// Argument house system, shared by the natal and transit parsers
+fn arg_house_system<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name(HOUSE_SYSTEM)
+        .long("house_system")
+        .value_name("HOUSE_SYSTEM_CODE")
+        .default_value("P")
+        .help(
+            "Code of house system :
+    Placidus = P
+    Koch = K
+    Porphyry = O
+    Regiomontanus = R
+    Campanus = C
+    Alcabitius = B
+    Equal = E
+    Whole sign = W",
+        )
+        .multiple(false)
+        .validator(validator_parse_house_system)
+        .required(false)
+}
+
+/// Argument theme, shared by the natal and transit parsers
+fn arg_theme<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name(THEME)
+        .long("theme")
+        .value_name("THEME")
+        .default_value("light")
+        .possible_values(&["light", "dark"])
+        .help("Colors of the chart")
+        .multiple(false)
+        .required(false)
+}
+
+/// Argument aspect config, shared by the natal and transit parsers
+fn arg_aspect_config<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name(ASPECT_CONFIG)
+        .long("aspect_config")
+        .value_name("PATH_JSON")
+        .help("Json file of the aspects and orbs")
+        .multiple(false)
+        .required(false)
+}
+
+/// Argument zodiac, shared by the natal and transit parsers
+fn arg_zodiac<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name(ZODIAC)
+        .long("zodiac")
+        .value_name("ZODIAC")
+        .default_value("tropical")
+        .help(
+            "Zodiac: tropical or the ayanamsa of the sidereal zodiac :
+    fagan_bradley, lahiri, de_luce, raman, ushashashi, krishnamurti,
+    djwhal_khul, yukteshwar, jn_bhasin, babylonian_huber, aldebaran_15tau,
+    hipparchos, sassanian, galactic_center_0sag, true_citra, true_revati,
+    true_pushya",
+        )
+        .multiple(false)
+        .validator(validator_parse_zodiac)
+        .required(false)
+}
+
+/// Argument bodies, shared by the natal and transit parsers
+fn arg_bodies<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name(BODIES)
+        .long("bodies")
+        .value_name("BODIES")
+        .help(
+            "Comma separated bodies of the chart :
+    Sun, Moon, Mercury, ..., Pluto, TrueNode or MeanNode, SouthNode,
+    MeanApog, OscuApog, Chiron, Pholus, Ceres, Pallas, Juno, Vesta,
+    Asteroid*, FortunaPart [default: bodies of CHART_BODIES]",
+        )
+        .multiple(false)
+        .validator(validator_parse_bodies)
+        .required(false)
+}
 
 /// Parse args chart natal (exemple -> svg) for clap
-pub fn parse_args_natal() -> AstrologyConfig {
+pub fn parse_args_natal() -> Result<AstrologyConfig, AstrologyError> {
     let now = Utc::now();
-    let date = parse_date(now.day(), now.month(), now.year()).unwrap();
+    let date = parse_date(now.day(), now.month(), now.year())?;
     let default_value_date = format!(
         "{}.{}.{}",
         date.day().to_string(),
@@ -95,14 +309,11 @@ pub fn parse_args_natal() -> AstrologyConfig {
     )
     .to_string();
     //let time = parse_time(now.hour(), now.minute(), now.second()).unwrap();
-    let time = parse_time(0, 0, 0).unwrap();
+    let time = parse_time(0, 0, 0)?;
     let default_value_time =
         format!("{}:{}", time.hour().to_string(), time.minute().to_string(),)
             .to_string();
-    let default_value_path = format!(
-        "{}/natal_chart.svg",
-        env::current_dir().unwrap().as_path().display()
-    );
+    let default_value_path = default_path("natal_chart.svg");
     let default_value_square = "1000";
     let matches = App::new("Astrology")
         .version(env!("CARGO_PKG_VERSION"))
@@ -212,40 +423,64 @@ commercial license.")
                 .validator(validator_parse_aspect)
                 .required(false),
         )
+        .arg(arg_house_system())
+        .arg(arg_zodiac())
+        .arg(arg_bodies())
+        .arg(arg_theme())
+        .arg(arg_aspect_config())
+        .arg(
+            Arg::with_name(FIXED_STARS)
+                .long("fixed_stars")
+                .help("Draw the fixed stars on an outer ring (sefstars.txt)")
+                .multiple(false)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name(LOTS)
+                .long("lots")
+                .help("Draw the hermetic lots on the wheel")
+                .multiple(false)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name(SECT)
+                .long("sect")
+                .help("Draw the sect and the planetary hour (bottom right corner)")
+                .multiple(false)
+                .required(false),
+        )
         .get_matches();
-    let date_final =
-        parse_date_from_str(matches.value_of(DATE).unwrap()).unwrap();
-    let time_final =
-        parse_time_from_str(matches.value_of(TIME).unwrap()).unwrap();
-    let mut ephem_final = matches.values_of(PATH_EPHEM).unwrap();
-    let mut size_final = matches.values_of(SIZE).unwrap();
-    let size_final_string: String =
-        size_final.next().as_deref().unwrap().to_string();
-    let mut aspect_final = matches.values_of(ASPECT).unwrap();
-    let aspect_final_string: String =
-        aspect_final.next().as_deref().unwrap().to_string();
-    AstrologyConfig {
-        date: date_final,
-        time: time_final,
-        lat: f32::from_str(matches.value_of(LAT).unwrap()).unwrap(),
-        lng: f32::from_str(matches.value_of(LNG).unwrap()).unwrap(),
-        time_zone: f32::from_str(matches.value_of(TIME_ZONE).unwrap()).unwrap(),
+    Ok(AstrologyConfig {
+        date: parse_date_from_str(value_of(&matches, DATE)?)?,
+        time: parse_time_from_str(value_of(&matches, TIME)?)?,
+        lat: value_of_f32(&matches, LAT, AstrologyError::InvalidCoordinates)?,
+        lng: value_of_f32(&matches, LNG, AstrologyError::InvalidCoordinates)?,
+        time_zone: value_of_f32(
+            &matches,
+            TIME_ZONE,
+            AstrologyError::InvalidDateTime,
+        )?,
+        fixed_stars: matches.is_present(FIXED_STARS),
+        lots: matches.is_present(LOTS),
+        sect: matches.is_present(SECT),
         path_and_file: matches
             .value_of(PATH)
             .unwrap_or(&default_value_path)
             .to_string(),
-        path_ephem_files: ephem_final.next().as_deref().unwrap().to_string(),
-        size: size_final_string.parse::<u32>().unwrap(),
-        aspect: aspect_final_string.parse::<u32>().unwrap(),
-    }
+        path_ephem_files: value_of(&matches, PATH_EPHEM)?.to_string(),
+        size: value_of_u32(&matches, SIZE)?,
+        aspect: value_of_u32(&matches, ASPECT)?,
+        house_system: value_of_house_system(&matches)?,
+        zodiac: value_of_zodiac(&matches)?,
+        bodies: value_of_bodies(&matches)?,
+        style: value_of_style(&matches)?,
+        aspect_config: value_of_aspect_config(&matches)?,
+    })
 }
 
 /// Parse args chart transit (exemple -> svg_transit) for clap
-pub fn parse_args_transit() -> AstrologyTransitConfig {
-    let default_value_path = format!(
-        "{}/transit_chart.svg",
-        env::current_dir().unwrap().as_path().display()
-    );
+pub fn parse_args_transit() -> Result<AstrologyTransitConfig, AstrologyError> {
+    let default_value_path = default_path("transit_chart.svg");
     let default_value_square = "1000";
     let matches = App::new("Astrology")
         .version(env!("CARGO_PKG_VERSION"))
@@ -400,41 +635,169 @@ commercial license.")
                 .validator(validator_parse_aspect)
                 .required(false),
         )
+        .arg(arg_house_system())
+        .arg(arg_zodiac())
+        .arg(arg_bodies())
+        .arg(arg_theme())
+        .arg(arg_aspect_config())
+        .arg(
+            Arg::with_name(TRANSIT_HOUSES)
+                .long("transit_houses")
+                .help("Draw the houses and angles of the transit location")
+                .multiple(false)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name(LUNATION_MARKERS)
+                .long("lunation_markers")
+                .help("Draw the prenatal syzygy and the last eclipse")
+                .multiple(false)
+                .required(false),
+        )
         .get_matches();
-    let date_n_final =
-        parse_date_from_str(matches.value_of(DATE_N).unwrap()).unwrap();
-    let date_t_final =
-        parse_date_from_str(matches.value_of(DATE_T).unwrap()).unwrap();
-    let time_n_final =
-        parse_time_from_str(matches.value_of(TIME_N).unwrap()).unwrap();
-    let time_t_final =
-        parse_time_from_str(matches.value_of(TIME_T).unwrap()).unwrap();
-    let mut ephem_final = matches.values_of(PATH_EPHEM).unwrap();
-    let mut size_final = matches.values_of(SIZE).unwrap();
-    let size_final_string: String =
-        size_final.next().as_deref().unwrap().to_string();
-    let mut aspect_final = matches.values_of(ASPECT).unwrap();
-    let aspect_final_string: String =
-        aspect_final.next().as_deref().unwrap().to_string();
-    AstrologyTransitConfig {
-        date_n: date_n_final,
-        time_n: time_n_final,
-        lat_n: f32::from_str(matches.value_of(LAT_N).unwrap()).unwrap(),
-        lng_n: f32::from_str(matches.value_of(LNG_N).unwrap()).unwrap(),
-        time_zone_n: f32::from_str(matches.value_of(TIME_ZONE_N).unwrap())
-            .unwrap(),
-        date_t: date_t_final,
-        time_t: time_t_final,
-        lat_t: f32::from_str(matches.value_of(LAT_T).unwrap()).unwrap(),
-        lng_t: f32::from_str(matches.value_of(LNG_T).unwrap()).unwrap(),
-        time_zone_t: f32::from_str(matches.value_of(TIME_ZONE_T).unwrap())
-            .unwrap(),
+    Ok(AstrologyTransitConfig {
+        date_n: parse_date_from_str(value_of(&matches, DATE_N)?)?,
+        time_n: parse_time_from_str(value_of(&matches, TIME_N)?)?,
+        lat_n: value_of_f32(
+            &matches,
+            LAT_N,
+            AstrologyError::InvalidCoordinates,
+        )?,
+        lng_n: value_of_f32(
+            &matches,
+            LNG_N,
+            AstrologyError::InvalidCoordinates,
+        )?,
+        time_zone_n: value_of_f32(
+            &matches,
+            TIME_ZONE_N,
+            AstrologyError::InvalidDateTime,
+        )?,
+        date_t: parse_date_from_str(value_of(&matches, DATE_T)?)?,
+        time_t: parse_time_from_str(value_of(&matches, TIME_T)?)?,
+        lat_t: value_of_f32(
+            &matches,
+            LAT_T,
+            AstrologyError::InvalidCoordinates,
+        )?,
+        lng_t: value_of_f32(
+            &matches,
+            LNG_T,
+            AstrologyError::InvalidCoordinates,
+        )?,
+        time_zone_t: value_of_f32(
+            &matches,
+            TIME_ZONE_T,
+            AstrologyError::InvalidDateTime,
+        )?,
+        transit_houses: matches.is_present(TRANSIT_HOUSES),
+        lunation_markers: matches.is_present(LUNATION_MARKERS),
         path_and_file: matches
             .value_of(PATH)
             .unwrap_or(&default_value_path)
             .to_string(),
-        path_ephem_files: ephem_final.next().as_deref().unwrap().to_string(),
-        size: size_final_string.parse::<u32>().unwrap(),
-        aspect: aspect_final_string.parse::<u32>().unwrap(),
-    }
+        path_ephem_files: value_of(&matches, PATH_EPHEM)?.to_string(),
+        size: value_of_u32(&matches, SIZE)?,
+        aspect: value_of_u32(&matches, ASPECT)?,
+        house_system: value_of_house_system(&matches)?,
+        zodiac: value_of_zodiac(&matches)?,
+        bodies: value_of_bodies(&matches)?,
+        style: value_of_style(&matches)?,
+        aspect_config: value_of_aspect_config(&matches)?,
+    })
+}
+
+/// Parse args ephemeris (exemple -> ephemeris) for clap
+pub fn parse_args_ephemeris() -> Result<AstrologyEphemerisConfig, AstrologyError>
+{
+    let matches = App::new("Astrology")
+        .version(env!("CARGO_PKG_VERSION"))
+        .author(AUTHOR)
+        .about("Create an ephemeris table in csv or json using swissephem c library by Astrodienst AG by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+
+The source code is released under an CC License, which allows it to be used
+also on commercial projects. This software uses the swiss ephemeris which is
+licensed GPL.
+
+Therefore, if you want to use astro_compute_swisseph in your commercial
+projects, you must adhere to the GPL license or buy a Swiss Ephemeris
+commercial license.")
+        .arg(
+            Arg::with_name(DATE_START)
+                .long("start_date")
+                .value_name("DATE_START")
+                .multiple(false)
+                .help("First date (0h UT) in format: dd.mm.yyyy")
+                .validator(validator_parse_date)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(DATE_END)
+                .long("end_date")
+                .value_name("DATE_END")
+                .multiple(false)
+                .help("Last date (0h UT) in format: dd.mm.yyyy")
+                .validator(validator_parse_date)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(STEP)
+                .long("step")
+                .value_name("STEP_IN_DAYS")
+                .default_value("1")
+                .multiple(false)
+                .help("Step between two rows in days")
+                .validator(validator_parse_step)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name(FORMAT)
+                .long("format")
+                .value_name("FORMAT")
+                .default_value("csv")
+                .possible_values(&["csv", "json"])
+                .help("Format of the export")
+                .multiple(false)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name(PATH)
+                .long("path_export")
+                .value_name("PATH_AND_FILE_EPHEMERIS")
+                .help("Path for the export on the disk (ephemeris.csv or ephemeris.json in the current directory by default)")
+                .multiple(false)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name(PATH_EPHEM)
+                .long("path_ephem")
+                .value_name("PATH_SWISS_EPHEM_FILES")
+                .help("Path of swiss ephem files")
+                .multiple(false)
+                .validator(validator_parse_path)
+                .required(true),
+        )
+        .arg(arg_zodiac())
+        .arg(arg_bodies())
+        .get_matches();
+    let format = value_of(&matches, FORMAT)?.to_string();
+    let step = value_of(&matches, STEP)?;
+    Ok(AstrologyEphemerisConfig {
+        date_start: parse_date_from_str(value_of(&matches, DATE_START)?)?,
+        date_end: parse_date_from_str(value_of(&matches, DATE_END)?)?,
+        step: f64::from_str(step).map_err(|_| {
+            AstrologyError::InvalidArgument(format!(
+                "{}: {} isn't a number",
+                STEP, step
+            ))
+        })?,
+        path_and_file: match matches.value_of(PATH) {
+            Some(path) => path.to_string(),
+            None => default_path(format!("ephemeris.{}", format).as_str()),
+        },
+        format,
+        path_ephem_files: value_of(&matches, PATH_EPHEM)?.to_string(),
+        zodiac: value_of_zodiac(&matches)?,
+        bodies: value_of_bodies(&matches)?,
+    })
 }
@@ -2,6 +2,6 @@ mod args;
 mod parse;
 mod validator;
 pub use self::args::{
-    parse_args_natal, parse_args_transit, AstrologyConfig,
-    AstrologyTransitConfig,
+    parse_args_ephemeris, parse_args_natal, parse_args_transit,
+    AstrologyConfig, AstrologyEphemerisConfig, AstrologyTransitConfig,
 };
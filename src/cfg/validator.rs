@@ -1,4 +1,6 @@
+use crate::svg_draw::{BodySelection, HouseSystem, Zodiac};
 use std::path::Path;
+use std::str::FromStr;
 
 /// Check if the path for the swissephem files exist
 pub fn validator_parse_path(path: String) -> Result<(), String> {
@@ -14,7 +16,10 @@ pub fn validator_parse_path(path: String) -> Result<(), String> {
 
 /// Check if size is ok
 pub fn validator_parse_size(size: String) -> Result<(), String> {
-    let i: u32 = size.parse::<u32>().unwrap();
+    let i: u32 = match size.parse::<u32>() {
+        Ok(i) => i,
+        Err(_) => return Err(format!("{} isn't a number.", size)),
+    };
     if i >= 800 && i <= 2000 {
         Ok(())
     } else {
@@ -22,6 +27,15 @@ pub fn validator_parse_size(size: String) -> Result<(), String> {
     }
 }
 
+/// Check if the step (in days) of an ephemeris is ok
+pub fn validator_parse_step(step: String) -> Result<(), String> {
+    match step.parse::<f64>() {
+        Ok(s) if s > 0.0 => Ok(()),
+        Ok(_) => Err(format!("{} isn't a positive number of days.", step)),
+        Err(_) => Err(format!("{} isn't a number.", step)),
+    }
+}
+
 /// Check if format of date is ok
 /// . (3) and - (0-1) for eventual BC date
 pub fn validator_parse_date(date: String) -> Result<(), String> {
@@ -137,7 +151,15 @@ pub fn validator_parse_timezone(time_zone: String) -> Result<(), String> {
 
 /// Check if aspect is valid
 pub fn validator_parse_aspect(aspect: String) -> Result<(), String> {
-    let i: u32 = aspect.parse::<u32>().unwrap();
+    let i: u32 = match aspect.parse::<u32>() {
+        Ok(i) => i,
+        Err(_) => {
+            return Err(format!(
+                "{} isn't a number (--help for code list)",
+                aspect
+            ))
+        },
+    };
     if i <= 12 {
         Ok(())
     } else {
@@ -147,3 +169,26 @@ pub fn validator_parse_aspect(aspect: String) -> Result<(), String> {
         ))
     }
 }
+
+/// Check if house system is valid
+pub fn validator_parse_house_system(
+    house_system: String,
+) -> Result<(), String> {
+    HouseSystem::from_str(house_system.as_str())
+        .map(|_| ())
+        .map_err(|e| format!("{} (--help for code list)", e))
+}
+
+/// Check if zodiac is tropical or a known ayanamsa
+pub fn validator_parse_zodiac(zodiac: String) -> Result<(), String> {
+    Zodiac::from_str(zodiac.as_str())
+        .map(|_| ())
+        .map_err(|e| format!("{} (--help for the list)", e))
+}
+
+/// Check if bodies is a list of known bodies
+pub fn validator_parse_bodies(bodies: String) -> Result<(), String> {
+    BodySelection::from_str(bodies.as_str())
+        .map(|_| ())
+        .map_err(|e| format!("{} (--help for the list)", e))
+}
@@ -0,0 +1,63 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+use std::error::Error;
+use std::fmt;
+
+/// Error of the library
+#[derive(Debug, Clone, PartialEq)]
+pub enum AstrologyError {
+    /// The path of the swiss ephemeris files don't exist or a file is missing
+    MissingEphemerisFiles(String),
+    /// Date, time or time zone invalid
+    InvalidDateTime(String),
+    /// Latitude or longitude invalid
+    InvalidCoordinates(String),
+    /// Error returned by swiss ephemeris
+    CalculationFailed(String),
+    /// Other invalid argument (size, aspect, house system...)
+    InvalidArgument(String),
+}
+
+impl fmt::Display for AstrologyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AstrologyError::MissingEphemerisFiles(s) => {
+                write!(f, "Missing swiss ephemeris files: {}", s)
+            },
+            AstrologyError::InvalidDateTime(s) => {
+                write!(f, "Invalid date/time: {}", s)
+            },
+            AstrologyError::InvalidCoordinates(s) => {
+                write!(f, "Invalid coordinates: {}", s)
+            },
+            AstrologyError::CalculationFailed(s) => {
+                write!(f, "Calculation failed: {}", s)
+            },
+            AstrologyError::InvalidArgument(s) => {
+                write!(f, "Invalid argument: {}", s)
+            },
+        }
+    }
+}
+
+impl Error for AstrologyError {}
+
+impl From<chrono::ParseError> for AstrologyError {
+    fn from(e: chrono::ParseError) -> Self {
+        AstrologyError::InvalidDateTime(e.to_string())
+    }
+}
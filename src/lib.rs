@@ -15,7 +15,9 @@
  * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
  */
 pub mod cfg;
+pub mod error;
 pub mod svg_draw;
+pub use self::error::AstrologyError;
 
 /// Unit test
 #[cfg(test)]
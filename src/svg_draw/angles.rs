@@ -15,10 +15,11 @@
  * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
  */
 extern crate strum;
+use crate::svg_draw::chart_style::ChartStyle;
 use crate::svg_draw::svg_draw::{
     WorkingStorageDrawPolyMorphNatal, WorkingStorageDrawPolyMorphTransit,
 };
-use libswe_sys::sweconst::{Bodies, Theme};
+use libswe_sys::sweconst::Angle;
 use svg::node::element::path::{Data, Number};
 use svg::node::element::{Group, Path};
 use svg::Document;
@@ -26,41 +27,50 @@ pub const ANGLE_SIZE: Number = 50.0;
 
 impl WorkingStorageDrawPolyMorphNatal {
     pub fn angles_draw_asc(&self) -> Document {
-        draw_asc(self.ws.theme)
+        draw_asc(&self.ws.style)
     }
     pub fn angles_draw_fc(&self) -> Document {
-        draw_fc(self.ws.theme)
+        draw_fc(&self.ws.style)
     }
     pub fn angles_draw_desc(&self) -> Document {
-        draw_desc(self.ws.theme)
+        draw_desc(&self.ws.style)
     }
     pub fn angles_draw_mc(&self) -> Document {
-        draw_mc(self.ws.theme)
+        draw_mc(&self.ws.style)
     }
 }
 
 impl WorkingStorageDrawPolyMorphTransit {
     pub fn angles_draw_asc(&self) -> Document {
-        draw_asc(self.ws.theme)
+        draw_asc(&self.ws.style)
     }
     pub fn angles_draw_fc(&self) -> Document {
-        draw_fc(self.ws.theme)
+        draw_fc(&self.ws.style)
     }
     pub fn angles_draw_desc(&self) -> Document {
-        draw_desc(self.ws.theme)
+        draw_desc(&self.ws.style)
     }
     pub fn angles_draw_mc(&self) -> Document {
-        draw_mc(self.ws.theme)
+        draw_mc(&self.ws.style)
+    }
+}
+
+/// Draw an angle, empty for Angle::Nothing
+pub(crate) fn draw_angle(angle: Angle, style: &ChartStyle) -> Document {
+    match angle {
+        Angle::Asc => draw_asc(style),
+        Angle::Fc => draw_fc(style),
+        Angle::Desc => draw_desc(style),
+        Angle::Mc => draw_mc(style),
+        Angle::Nothing => Document::new(),
     }
 }
 
 /// Draw the Ascendant text (AS in english)
-fn draw_asc(theme: Theme) -> Document {
+fn draw_asc(style: &ChartStyle) -> Document {
     let size: (Number, Number) = (ANGLE_SIZE, ANGLE_SIZE);
     let document: Document;
-    // To do use Primary/Secondary/Etc... color for that
-    let color: String =
-        format!("#{:06X}", Bodies::EclNut.object_color(theme) as i32);
+    let color: String = style.angle.clone();
     let data1 = Data::new()
         .move_to((9.2, 28.6)) // M
         .line_by((-2.5, 7.6)) // l
@@ -111,11 +121,10 @@ fn draw_asc(theme: Theme) -> Document {
 }
 
 /// Draw the Fc text (FC in english)
-fn draw_fc(theme: Theme) -> Document {
+fn draw_fc(style: &ChartStyle) -> Document {
     let size: (Number, Number) = (ANGLE_SIZE, ANGLE_SIZE);
     let document: Document;
-    let color: String =
-        format!("#{:06X}", Bodies::EclNut.object_color(theme) as i32);
+    let color: String = style.angle.clone();
     let data1 = Data::new()
         .move_to((7.2, 11.9)) // M
         .horizontal_line_by(13.1) // h
@@ -160,11 +169,10 @@ fn draw_fc(theme: Theme) -> Document {
 }
 
 /// Draw the Desc text (DC in english)
-fn draw_desc(theme: Theme) -> Document {
+fn draw_desc(style: &ChartStyle) -> Document {
     let size: (Number, Number) = (ANGLE_SIZE, ANGLE_SIZE);
     let document: Document;
-    let color: String =
-        format!("#{:06X}", Bodies::EclNut.object_color(theme) as i32);
+    let color: String = style.angle.clone();
     let data1 = Data::new()
         .move_to((4.0, 12.3)) // M
         .cubic_curve_by((1.9, -0.3, 4.2, -0.5, 6.7, -0.5)) // c
@@ -213,11 +221,10 @@ fn draw_desc(theme: Theme) -> Document {
 }
 
 /// Draw the Mc text (CC in english)
-fn draw_mc(theme: Theme) -> Document {
+fn draw_mc(style: &ChartStyle) -> Document {
     let size: (Number, Number) = (ANGLE_SIZE, ANGLE_SIZE);
     let document: Document;
-    let color: String =
-        format!("#{:06X}", Bodies::EclNut.object_color(theme) as i32);
+    let color: String = style.angle.clone();
     let data1 = Data::new()
         .move_to((22.0, 25.5)) // M
         .cubic_curve_by((-0.2, -3.4, -0.4, -7.5, -0.4, -10.5)) // c
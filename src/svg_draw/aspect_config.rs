@@ -0,0 +1,299 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+use super::chart_data::{separation, ChartPoint};
+use super::serde_sweconst;
+use crate::error::AstrologyError;
+use libswe_sys::sweconst::{Aspects, AspectsFilter, Bodies, Language};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fs;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+/// Aspect of a chart
+/// The aspects of libswe_sys and the quintile, septile and novile families
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, EnumIter)]
+pub enum ChartAspect {
+    Conjunction,
+    Opposition,
+    Trine,
+    Square,
+    Sextile,
+    Inconjunction,
+    Sesquisquare,
+    Semisquare,
+    Semisextile,
+    Quintile,
+    Biquintile,
+    Septile,
+    Biseptile,
+    Triseptile,
+    Novile,
+    Binovile,
+    Quadrinovile,
+}
+
+/// Family of the harmonic aspects (opt-in in AspectConfig)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, EnumIter)]
+pub enum AspectFamily {
+    /// Quintile, Biquintile (360° / 5)
+    Quintile,
+    /// Septile, Biseptile, Triseptile (360° / 7)
+    Septile,
+    /// Novile, Binovile, Quadrinovile (360° / 9)
+    Novile,
+}
+
+impl ChartAspect {
+    /// Aspect of libswe_sys, None for a harmonic aspect
+    pub fn aspects(self) -> Option<Aspects> {
+        match self {
+            ChartAspect::Conjunction => Some(Aspects::Conjunction),
+            ChartAspect::Opposition => Some(Aspects::Opposition),
+            ChartAspect::Trine => Some(Aspects::Trine),
+            ChartAspect::Square => Some(Aspects::Square),
+            ChartAspect::Sextile => Some(Aspects::Sextile),
+            ChartAspect::Inconjunction => Some(Aspects::Inconjunction),
+            ChartAspect::Sesquisquare => Some(Aspects::Sesquisquare),
+            ChartAspect::Semisquare => Some(Aspects::Semisquare),
+            ChartAspect::Semisextile => Some(Aspects::Semisextile),
+            _ => None,
+        }
+    }
+
+    /// Family of a harmonic aspect, None for an aspect of libswe_sys
+    pub fn family(self) -> Option<AspectFamily> {
+        match self {
+            ChartAspect::Quintile | ChartAspect::Biquintile => {
+                Some(AspectFamily::Quintile)
+            },
+            ChartAspect::Septile
+            | ChartAspect::Biseptile
+            | ChartAspect::Triseptile => Some(AspectFamily::Septile),
+            ChartAspect::Novile
+            | ChartAspect::Binovile
+            | ChartAspect::Quadrinovile => Some(AspectFamily::Novile),
+            _ => None,
+        }
+    }
+
+    /// Angle of the aspect in degree
+    pub fn angle(self) -> f64 {
+        match self.aspects() {
+            Some(a) => a.angle().0 as f64,
+            None => match self {
+                ChartAspect::Quintile => 72.0,
+                ChartAspect::Biquintile => 144.0,
+                ChartAspect::Septile => 360.0 / 7.0,
+                ChartAspect::Biseptile => 720.0 / 7.0,
+                ChartAspect::Triseptile => 1080.0 / 7.0,
+                ChartAspect::Novile => 40.0,
+                ChartAspect::Binovile => 80.0,
+                _ => 160.0, // Quadrinovile
+            },
+        }
+    }
+
+    /// Default orb in degree (the orb of libswe_sys for its aspects)
+    pub fn orb(self) -> f64 {
+        match self.aspects() {
+            Some(a) => a.angle().1 as f64,
+            None => match self.family() {
+                Some(AspectFamily::Quintile) => 2.0,
+                _ => 1.0,
+            },
+        }
+    }
+
+    /// Major aspect -> bool
+    pub fn maj(self) -> bool {
+        self.aspects().map(|a| a.maj()).unwrap_or(false)
+    }
+
+    pub fn text(self, lang: Language) -> String {
+        match self.aspects() {
+            Some(a) => a.text(lang),
+            None => format!("{:?}", self),
+        }
+    }
+}
+
+impl From<Aspects> for ChartAspect {
+    fn from(aspect: Aspects) -> Self {
+        match aspect {
+            Aspects::Conjunction => ChartAspect::Conjunction,
+            Aspects::Opposition => ChartAspect::Opposition,
+            Aspects::Trine => ChartAspect::Trine,
+            Aspects::Square => ChartAspect::Square,
+            Aspects::Sextile => ChartAspect::Sextile,
+            Aspects::Inconjunction => ChartAspect::Inconjunction,
+            Aspects::Sesquisquare => ChartAspect::Sesquisquare,
+            Aspects::Semisquare => ChartAspect::Semisquare,
+            Aspects::Semisextile => ChartAspect::Semisextile,
+        }
+    }
+}
+
+/// Vector of aspects in AspectsFilter
+/// The harmonic aspects are minors aspects
+pub fn filter_aspects(filter: AspectsFilter) -> Vec<ChartAspect> {
+    let mut res: Vec<ChartAspect> = filter
+        .vec_aspects()
+        .into_iter()
+        .map(ChartAspect::from)
+        .collect();
+    if filter == AspectsFilter::AllAspects
+        || filter == AspectsFilter::AllMinorsAspect
+    {
+        res.extend(ChartAspect::iter().filter(|a| a.family().is_some()));
+    }
+    res
+}
+
+/// Orb of an aspect
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AspectOrb {
+    pub aspect: ChartAspect,
+    pub orb: f64,
+}
+
+/// Factor of the orbs for a bodie (1.5 = orbs 50% wider)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BodieOrb {
+    #[serde(with = "serde_sweconst::bodies")]
+    pub bodie: Bodies,
+    pub factor: f64,
+}
+
+/// Aspects detected and drawn with their orbs
+/// The orb of an aspect between two points is the orb of the aspect multiplied
+/// by the greatest factor of the two points (1.0 for a bodie not in bodies
+/// and for the angles)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AspectConfig {
+    /// Aspects detected, an aspect not in this vector is never detected
+    pub aspects: Vec<AspectOrb>,
+    pub bodies: Vec<BodieOrb>,
+}
+
+impl Default for AspectConfig {
+    /// Aspects of libswe_sys with their orbs
+    fn default() -> Self {
+        AspectConfig {
+            aspects: Aspects::iter()
+                .map(|a| AspectOrb {
+                    aspect: a.into(),
+                    orb: ChartAspect::from(a).orb(),
+                })
+                .collect(),
+            bodies: Vec::new(),
+        }
+    }
+}
+
+impl AspectConfig {
+    /// Config from json, the missing fields are the default
+    /// {"aspects": [{"aspect": "Conjunction", "orb": 8.0}, ...],
+    ///  "bodies": [{"bodie": "Sun", "factor": 1.5}, ...]}
+    pub fn from_json(json: &str) -> Result<Self, AstrologyError> {
+        serde_json::from_str(json).map_err(|e| {
+            AstrologyError::InvalidArgument(format!("aspect config: {}", e))
+        })
+    }
+
+    /// Config from a json file
+    pub fn from_json_file(path: &str) -> Result<Self, AstrologyError> {
+        let json = fs::read_to_string(path).map_err(|e| {
+            AstrologyError::InvalidArgument(format!("{}: {}", path, e))
+        })?;
+        AspectConfig::from_json(json.as_str())
+    }
+
+    /// Add or replace an aspect
+    pub fn with_aspect(mut self, aspect: ChartAspect, orb: f64) -> Self {
+        self.aspects.retain(|a| a.aspect != aspect);
+        self.aspects.push(AspectOrb { aspect, orb });
+        self
+    }
+
+    /// Remove an aspect
+    pub fn without_aspect(mut self, aspect: ChartAspect) -> Self {
+        self.aspects.retain(|a| a.aspect != aspect);
+        self
+    }
+
+    /// Add the aspects of a family with their default orbs
+    pub fn with_family(self, family: AspectFamily) -> Self {
+        ChartAspect::iter()
+            .filter(|a| a.family() == Some(family))
+            .fold(self, |config, a| config.with_aspect(a, a.orb()))
+    }
+
+    /// Add or replace the factor of a bodie
+    pub fn with_bodie(mut self, bodie: Bodies, factor: f64) -> Self {
+        self.bodies.retain(|b| b.bodie != bodie);
+        self.bodies.push(BodieOrb { bodie, factor });
+        self
+    }
+
+    /// Factor of the orbs of a point
+    pub fn factor(&self, point: ChartPoint) -> f64 {
+        match point {
+            ChartPoint::Bodie(bodie) => self
+                .bodies
+                .iter()
+                .find(|b| b.bodie == bodie)
+                .map(|b| b.factor)
+                .unwrap_or(1.0),
+            ChartPoint::Angle(_) => 1.0,
+        }
+    }
+
+    /// Orb of an aspect between two points, None if the aspect is not in the
+    /// config
+    pub fn orb(
+        &self,
+        aspect: ChartAspect,
+        point_1: ChartPoint,
+        point_2: ChartPoint,
+    ) -> Option<f64> {
+        self.aspects
+            .iter()
+            .find(|a| a.aspect == aspect)
+            .map(|a| a.orb * self.factor(point_1).max(self.factor(point_2)))
+    }
+
+    /// Aspect between two points and its orb (distance to the exact aspect)
+    /// If the orbs overlap, the closest aspect is returned
+    pub fn find_aspect(
+        &self,
+        point_1: ChartPoint,
+        lng_1: f64,
+        point_2: ChartPoint,
+        lng_2: f64,
+    ) -> Option<(ChartAspect, f64)> {
+        let sep = separation(lng_1, lng_2);
+        let factor = self.factor(point_1).max(self.factor(point_2));
+        self.aspects
+            .iter()
+            .map(|a| (a.aspect, (sep - a.aspect.angle()).abs(), a.orb * factor))
+            .filter(|(_, orb, max)| orb <= max)
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))
+            .map(|(aspect, orb, _)| (aspect, orb))
+    }
+}
@@ -0,0 +1,270 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+//! Aspect grid (aspectarian)
+//! Triangular for a natal chart, rectangular for natal (rows) x transit
+//! (columns), each cell has the glyph of the aspect and its orb
+use super::angles::draw_angle;
+use super::aspect_config::ChartAspect;
+use super::aspects::aspects_draw;
+use super::bodies::draw_bodie;
+use super::chart_data::{AspectRecord, BodieData, ChartData, ChartPoint};
+use super::chart_style::ChartStyle;
+use super::svg_draw::draw_background;
+use libswe_sys::sweconst::{Angle, Language};
+use svg::node::element::path::Number;
+use svg::node::element::{Rectangle, Text};
+use svg::node::Text as TextNode;
+use svg::Document;
+
+// Size of the glyph in % of the cell
+const GLYPH_RATIO: Number = 60.0;
+// Size of the text in % of the cell
+const TEXT_RATIO: Number = 22.0;
+
+/// Aspectarian of a natal chart
+/// The points are the bodies and the Asc/Mc, the grid is a triangle under the
+/// diagonal
+pub fn aspectarian(
+    chart: &ChartData,
+    max_size: Number,
+    style: &ChartStyle,
+    lang: Language,
+) -> Document {
+    let points = natal_points(chart);
+    let n = points.len();
+    let cell = max_size / n as Number;
+    let mut document = grid_base(max_size, max_size, style);
+    for (i, p) in points.iter().enumerate().skip(1) {
+        // Row
+        document =
+            document.add(point_glyph(*p, 0.0, i as Number * cell, cell, style));
+        for (j, q) in points.iter().enumerate().take(i) {
+            document = document.add(cell_rect(
+                (j + 1) as Number * cell,
+                i as Number * cell,
+                cell,
+                style,
+            ));
+            if let Some(record) = chart.aspects.iter().find(|r| {
+                (r.body_a == p.0 && r.body_b == q.0)
+                    || (r.body_a == q.0 && r.body_b == p.0)
+            }) {
+                document = draw_cell(
+                    document,
+                    record,
+                    (j + 1) as Number * cell,
+                    i as Number * cell,
+                    cell,
+                    style,
+                    lang,
+                );
+            }
+        }
+    }
+    // Column
+    for (j, p) in points.iter().enumerate().take(n - 1) {
+        document = document.add(point_glyph(
+            *p,
+            (j + 1) as Number * cell,
+            0.0,
+            cell,
+            style,
+        ));
+    }
+    document
+}
+
+/// Aspectarian natal (rows: bodies and Asc/Mc) x transit (columns: bodies)
+/// aspects are the transit aspects (body_a natal, body_b transit)
+pub fn aspectarian_transit(
+    natal: &ChartData,
+    transit: &[BodieData],
+    aspects: &[AspectRecord],
+    max_size: Number,
+    style: &ChartStyle,
+    lang: Language,
+) -> Document {
+    let rows = natal_points(natal);
+    let cols: Vec<(ChartPoint, bool)> = transit
+        .iter()
+        .map(|b| (ChartPoint::Bodie(b.bodie), b.retrograde))
+        .collect();
+    let cell = max_size / (rows.len().max(cols.len()) + 1) as Number;
+    let mut document = grid_base(
+        (cols.len() + 1) as Number * cell,
+        (rows.len() + 1) as Number * cell,
+        style,
+    );
+    for (j, c) in cols.iter().enumerate() {
+        document = document.add(point_glyph(
+            *c,
+            (j + 1) as Number * cell,
+            0.0,
+            cell,
+            style,
+        ));
+    }
+    for (i, r) in rows.iter().enumerate() {
+        document = document.add(point_glyph(
+            *r,
+            0.0,
+            (i + 1) as Number * cell,
+            cell,
+            style,
+        ));
+        for (j, c) in cols.iter().enumerate() {
+            let x = (j + 1) as Number * cell;
+            let y = (i + 1) as Number * cell;
+            document = document.add(cell_rect(x, y, cell, style));
+            if let Some(record) =
+                aspects.iter().find(|a| a.body_a == r.0 && a.body_b == c.0)
+            {
+                document = draw_cell(document, record, x, y, cell, style, lang);
+            }
+        }
+    }
+    document
+}
+
+/// Bodies and Asc/Mc of a natal chart, with retrograde
+fn natal_points(chart: &ChartData) -> Vec<(ChartPoint, bool)> {
+    chart
+        .bodies
+        .iter()
+        .map(|b| (ChartPoint::Bodie(b.bodie), b.retrograde))
+        .chain(
+            chart
+                .angles
+                .iter()
+                .filter(|a| a.angle == Angle::Asc || a.angle == Angle::Mc)
+                .map(|a| (ChartPoint::Angle(a.angle), false)),
+        )
+        .collect()
+}
+
+/// Document with the background
+fn grid_base(width: Number, height: Number, style: &ChartStyle) -> Document {
+    draw_background(Document::new(), style, width, height)
+        .set("viewBox", (0, 0, width, height))
+}
+
+/// Border of a cell
+fn cell_rect(
+    x: Number,
+    y: Number,
+    cell: Number,
+    style: &ChartStyle,
+) -> Rectangle {
+    Rectangle::new()
+        .set("x", x)
+        .set("y", y)
+        .set("width", cell)
+        .set("height", cell)
+        .set("fill", "none")
+        .set("stroke", style.circle.as_str())
+        .set("stroke-width", 1)
+}
+
+/// Glyph of a bodie (with "R" if retrograde) or an angle centered in a cell
+fn point_glyph(
+    (point, retrograde): (ChartPoint, bool),
+    x: Number,
+    y: Number,
+    cell: Number,
+    style: &ChartStyle,
+) -> Document {
+    let glyph = match point {
+        ChartPoint::Bodie(b) => draw_bodie(b, retrograde, style),
+        ChartPoint::Angle(a) => draw_angle(a, style),
+    };
+    let size = cell * GLYPH_RATIO / 100.0;
+    glyph
+        .set("x", x + (cell - size) / 2.0)
+        .set("y", y + (cell - size) / 2.0)
+        .set("width", size)
+        .set("height", size)
+}
+
+/// Glyph of the aspect and orb in a cell
+/// Orb in degree/minute with "a" for applying and "s" for separating
+fn draw_cell(
+    document: Document,
+    record: &AspectRecord,
+    x: Number,
+    y: Number,
+    cell: Number,
+    style: &ChartStyle,
+    lang: Language,
+) -> Document {
+    let color = style.aspect_color(record.aspect);
+    let size = cell * GLYPH_RATIO / 100.0;
+    let font_size = cell * TEXT_RATIO / 100.0;
+    let document = match record.aspect.aspects() {
+        Some(a) => {
+            let mut style_aspect = style.clone();
+            style_aspect.text = color;
+            document.add(
+                aspects_draw(a, &style_aspect, lang)
+                    .set("x", x + (cell - size) / 2.0)
+                    .set("y", y)
+                    .set("width", size)
+                    .set("height", size),
+            )
+        },
+        None => document.add(
+            Text::new()
+                .set("x", x + cell / 2.0)
+                .set("y", y + size * 0.7)
+                .set("font-size", size * 0.6)
+                .set("font-family", "sans-serif")
+                .set("text-anchor", "middle")
+                .set("fill", color.as_str())
+                .add(TextNode::new(harmonic_symbol(record.aspect))),
+        ),
+    };
+    let orb = format!(
+        "{}°{:02}'{}",
+        record.orb as i32,
+        (record.orb.fract() * 60.0) as i32,
+        if record.applying { "a" } else { "s" }
+    );
+    document.add(
+        Text::new()
+            .set("x", x + cell / 2.0)
+            .set("y", y + cell - font_size * 0.4)
+            .set("font-size", font_size)
+            .set("font-family", "sans-serif")
+            .set("text-anchor", "middle")
+            .set("fill", style.text.as_str())
+            .add(TextNode::new(orb)),
+    )
+}
+
+/// Symbol of the harmonic aspects (no glyph)
+fn harmonic_symbol(aspect: ChartAspect) -> &'static str {
+    match aspect {
+        ChartAspect::Quintile => "Q",
+        ChartAspect::Biquintile => "bQ",
+        ChartAspect::Septile => "S",
+        ChartAspect::Biseptile => "bS",
+        ChartAspect::Triseptile => "tS",
+        ChartAspect::Novile => "N",
+        ChartAspect::Binovile => "bN",
+        ChartAspect::Quadrinovile => "qN",
+        _ => "",
+    }
+}
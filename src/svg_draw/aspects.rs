@@ -15,7 +15,8 @@
  * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
  */
 extern crate strum;
-use libswe_sys::sweconst::{Aspects, Language, Theme};
+use crate::svg_draw::chart_style::ChartStyle;
+use libswe_sys::sweconst::{Aspects, Language};
 use svg::node::element::path::{Data, Number};
 use svg::node::element::{Group, Line, Path, Rectangle};
 use svg::Document;
@@ -25,13 +26,12 @@ pub const ASPECT_SIZE: Number = 50.0;
 /// Draw aspects text
 pub fn aspects_draw(
     aspect: Aspects,
-    _theme: Theme,
+    style: &ChartStyle,
     _lang: Language,
 ) -> Document {
     let size: (Number, Number) = (ASPECT_SIZE, ASPECT_SIZE);
     let document: Document;
-    //let color: String =
-    //    format!("#{:06X}", Bodies::EclNut.object_color() as i32);
+    let color: &str = style.text.as_str();
     match aspect {
         Aspects::Conjunction => {
             let data = Data::new()
@@ -43,7 +43,7 @@ pub fn aspects_draw(
                 .line_by((18.1, -18.0));
             let path = Path::new()
                 .set("fill", "none")
-                .set("stroke", "black")
+                .set("stroke", color)
                 .set("stroke-width", 6)
                 .set("d", data);
             document = Document::new()
@@ -65,7 +65,7 @@ pub fn aspects_draw(
                 .line_by((0.0, 0.0));
             let path = Path::new()
                 .set("fill", "none")
-                .set("stroke", "black")
+                .set("stroke", color)
                 .set("stroke-width", 6)
                 .set("d", data);
             document = Document::new()
@@ -81,7 +81,7 @@ pub fn aspects_draw(
                 .close(); // z
             let path = Path::new()
                 .set("fill", "none")
-                .set("stroke", "black")
+                .set("stroke", color)
                 .set("stroke-width", 6)
                 .set("d", data);
             document = Document::new()
@@ -95,7 +95,7 @@ pub fn aspects_draw(
                 .set("width", 40.6)
                 .set("height", 41.0)
                 .set("fill", "none")
-                .set("stroke", "black")
+                .set("stroke", color)
                 .set("stroke-width", 6);
             document = Document::new()
                 .set("viewBox", (0, 0, size.0, size.1))
@@ -111,7 +111,7 @@ pub fn aspects_draw(
                 .line_to((13.3, 44.7)); // L
             let path = Path::new()
                 .set("fill", "none")
-                .set("stroke", "black")
+                .set("stroke", color)
                 .set("stroke-width", 6)
                 .set("d", data);
             document = Document::new()
@@ -125,7 +125,7 @@ pub fn aspects_draw(
                 .set("x2", 2.0)
                 .set("y2", 23.0)
                 .set("fill", "none")
-                .set("stroke", "black")
+                .set("stroke", color)
                 .set("stroke-width", 6);
             let line2 = Line::new()
                 .set("x1", 25.0)
@@ -133,7 +133,7 @@ pub fn aspects_draw(
                 .set("x2", 46.0)
                 .set("y2", 36.0)
                 .set("fill", "none")
-                .set("stroke", "black")
+                .set("stroke", color)
                 .set("stroke-width", 6);
             let line3 = Line::new()
                 .set("x1", 25.0)
@@ -141,7 +141,7 @@ pub fn aspects_draw(
                 .set("x2", 12.0)
                 .set("y2", 33.0)
                 .set("fill", "none")
-                .set("stroke", "black")
+                .set("stroke", color)
                 .set("stroke-width", 6);
             document = Document::new()
                 .set("viewBox", (0, 0, size.0, size.1))
@@ -156,7 +156,7 @@ pub fn aspects_draw(
                 .set("x2", 47.0)
                 .set("y2", 26.0)
                 .set("fill", "none")
-                .set("stroke", "black")
+                .set("stroke", color)
                 .set("stroke-width", 6);
             let line2 = Line::new()
                 .set("x1", 19.1)
@@ -164,7 +164,7 @@ pub fn aspects_draw(
                 .set("x2", 4.0)
                 .set("y2", 12.0)
                 .set("fill", "none")
-                .set("stroke", "black")
+                .set("stroke", color)
                 .set("stroke-width", 6);
             document = Document::new()
                 .set("viewBox", (0, 0, size.0, size.1))
@@ -178,7 +178,7 @@ pub fn aspects_draw(
                 .set("x2", 48.0)
                 .set("y2", 26.0)
                 .set("fill", "none")
-                .set("stroke", "black")
+                .set("stroke", color)
                 .set("stroke-width", 6);
             let line2 = Line::new()
                 .set("x1", 28.0)
@@ -186,7 +186,7 @@ pub fn aspects_draw(
                 .set("x2", 4.2)
                 .set("y2", 25.3)
                 .set("fill", "none")
-                .set("stroke", "black")
+                .set("stroke", color)
                 .set("stroke-width", 6);
             document = Document::new()
                 .set("viewBox", (0, 0, size.0, size.1))
@@ -200,7 +200,7 @@ pub fn aspects_draw(
                 .set("x2", 48.0)
                 .set("y2", 26.0)
                 .set("fill", "none")
-                .set("stroke", "black")
+                .set("stroke", color)
                 .set("stroke-width", 6);
             let line2 = Line::new()
                 .set("x1", 24.2)
@@ -208,7 +208,7 @@ pub fn aspects_draw(
                 .set("x2", 2.8)
                 .set("y2", 13.3)
                 .set("fill", "none")
-                .set("stroke", "black")
+                .set("stroke", color)
                 .set("stroke-width", 6);
             let line3 = Line::new()
                 .set("x1", 27.0)
@@ -216,7 +216,7 @@ pub fn aspects_draw(
                 .set("x2", 47.0)
                 .set("y2", 14.0)
                 .set("fill", "none")
-                .set("stroke", "black")
+                .set("stroke", color)
                 .set("stroke-width", 6);
             document = Document::new()
                 .set("viewBox", (0, 0, size.0, size.1))
@@ -229,18 +229,17 @@ pub fn aspects_draw(
 }
 
 /// Draw text for no aspect
-pub fn aspects_no_aspect(_theme: Theme, lang: Language) -> Document {
+pub fn aspects_no_aspect(style: &ChartStyle, lang: Language) -> Document {
     let size: (Number, Number) = (ASPECT_SIZE, ASPECT_SIZE);
     let document: Document;
-    //let color: String =
-    //    format!("#{:06X}", Bodies::EclNut.object_color() as i32);
+    let color: &str = style.text.as_str();
     /*let line = Line::new()
         .set("x1", 2.4)
         .set("y1", 47.6)
         .set("x2", 47.6)
         .set("y2", 2.4)
         .set("fill", "none")
-        .set("stroke", "black")
+        .set("stroke", color)
         .set("stroke-width", 6);
     let ellipse = Ellipse::new()
         .set("cx", 24.5)
@@ -248,7 +247,7 @@ pub fn aspects_no_aspect(_theme: Theme, lang: Language) -> Document {
         .set("rx", 20.8)
         .set("ry", 21.3)
         .set("fill", "none")
-        .set("stroke", "black")
+        .set("stroke", color)
         .set("stroke-width", 6);
     document = Document::new()
         .set("viewBox", (0, 0, size.0, size.1))
@@ -275,7 +274,7 @@ pub fn aspects_no_aspect(_theme: Theme, lang: Language) -> Document {
                 .vertical_line_by(8.6) // v
                 .horizontal_line_to(8.9) // H
                 .close(); // z
-            let path1 = Path::new().set("d", data1);
+            let path1 = Path::new().set("fill", color).set("d", data1);
             let data2 = Data::new()
                 .move_to((41.6, 28.8)) // M
                 .cubic_curve_by((0.0, 5.4, -3.7, 7.7, -7.2, 7.7)) // c
@@ -289,7 +288,7 @@ pub fn aspects_no_aspect(_theme: Theme, lang: Language) -> Document {
                 .cubic_curve_by((0.0, -2.5, -1.2, -5.6, -4.4, -5.6)) // c
                 .cubic_curve_to((31.5, 23.3, 30.1, 26.2, 30.1, 29.0)) // C
                 .close(); // z
-            let path2 = Path::new().set("d", data2);
+            let path2 = Path::new().set("fill", color).set("d", data2);
             document = Document::new()
                 .set("viewBox", (0, 0, size.0, size.1))
                 .add(path1)
@@ -315,7 +314,7 @@ pub fn aspects_no_aspect(_theme: Theme, lang: Language) -> Document {
                 .line_to((4.0, 26.2)) // L
                 .horizontal_line_to(7.7) // H
                 .close(); // z
-            let path1 = Path::new().set("d", data1);
+            let path1 = Path::new().set("fill", color).set("d", data1);
             let data2 = Data::new()
                 .move_to((19.7, 28.8)) // M
                 .cubic_curve_by((0.0, 0.9, 0.0, 1.7, 0.1, 2.4)) // c
@@ -334,7 +333,7 @@ pub fn aspects_no_aspect(_theme: Theme, lang: Language) -> Document {
                 .horizontal_line_by(1.6) // h
                 .vertical_line_to(28.8) // V
                 .close(); // z
-            let path2 = Path::new().set("d", data2);
+            let path2 = Path::new().set("fill", color).set("d", data2);
             let data3 = Data::new()
                 .move_to((28.6, 30.9)) // M
                 .cubic_curve_by((-0.4, 0.2, -1.3, 0.5, -2.5, 0.5)) // c
@@ -348,7 +347,7 @@ pub fn aspects_no_aspect(_theme: Theme, lang: Language) -> Document {
                 .cubic_curve_by((0.9, 0.0, 1.5, -0.2, 1.9, -0.4)) // c
                 .line_to((28.6, 30.9)) // L
                 .close(); // z
-            let path3 = Path::new().set("d", data3);
+            let path3 = Path::new().set("fill", color).set("d", data3);
             let data4 = Data::new()
                 .move_to((37.7, 28.8)) // M
                 .cubic_curve_by((0.0, 0.9, 0.0, 1.7, 0.1, 2.4)) // c
@@ -367,7 +366,7 @@ pub fn aspects_no_aspect(_theme: Theme, lang: Language) -> Document {
                 .horizontal_line_by(1.6) // h
                 .vertical_line_to(28.8) // V
                 .close(); // z
-            let path4 = Path::new().set("d", data4);
+            let path4 = Path::new().set("fill", color).set("d", data4);
             let data5 = Data::new()
                 .move_to((40.4, 24.8)) // M
                 .cubic_curve_by((0.0, -0.9, 0.0, -1.6, -0.1, -2.4)) // c
@@ -386,7 +385,7 @@ pub fn aspects_no_aspect(_theme: Theme, lang: Language) -> Document {
                 .horizontal_line_by(-1.6) // h
                 .vertical_line_by(24.8) // v
                 .close(); // z
-            let path5 = Path::new().set("d", data5);
+            let path5 = Path::new().set("fill", color).set("d", data5);
             document = Document::new()
                 .set("viewBox", (0, 0, size.0, size.1))
                 .add(path1)
@@ -400,11 +399,10 @@ pub fn aspects_no_aspect(_theme: Theme, lang: Language) -> Document {
 }
 
 /// Draw text fort majors aspects
-pub fn aspects_maj_aspects(_theme: Theme, _lang: Language) -> Document {
+pub fn aspects_maj_aspects(style: &ChartStyle, _lang: Language) -> Document {
     let size: (Number, Number) = (ASPECT_SIZE, ASPECT_SIZE);
     let document: Document;
-    //let color: String =
-    //    format!("#{:06X}", Bodies::EclNut.object_color() as i32);
+    let color: &str = style.text.as_str();
     let data1 = Data::new()
         .move_to((21.0, 27.3)) // M
         .cubic_curve_by((-0.1, -2.8, -0.3, -6.2, -0.3, -8.7)) // c
@@ -430,7 +428,7 @@ pub fn aspects_maj_aspects(_theme: Theme, _lang: Language) -> Document {
         .horizontal_line_by(-2.5) // h
         .line_to((21.0, 27.3)) // L
         .close(); // z
-    let path1 = Path::new().set("d", data1);
+    let path1 = Path::new().set("fill", color).set("d", data1);
     let data2 = Data::new()
         .move_to((36.2, 36.2)) // M
         .line_to((36.0, 34.4)) // L
@@ -455,7 +453,7 @@ pub fn aspects_maj_aspects(_theme: Theme, _lang: Language) -> Document {
         .cubic_curve_by((0.1, -0.3, 0.2, -0.6, 0.2, -0.8)) // c
         .vertical_line_to(28.8)
         .close(); // z
-    let path2 = Path::new().set("d", data2);
+    let path2 = Path::new().set("fill", color).set("d", data2);
     let data3 = Data::new()
         .move_to((39.1, 40.4)) // M
         .cubic_curve_by((1.2, -0.1, 2.2, -0.4, 2.9, -1.1)) // c
@@ -473,7 +471,7 @@ pub fn aspects_maj_aspects(_theme: Theme, _lang: Language) -> Document {
         .cubic_curve_by((0.0, -0.9, 0.7, -1.6, 1.7, -1.6)) // c
         .cubic_curve_to((45.3, 15.9, 45.9, 16.7, 45.9, 17.6)) // C
         .close(); // z
-    let path3 = Path::new().set("d", data3);
+    let path3 = Path::new().set("fill", color).set("d", data3);
     let group = Group::new().add(path1).add(path2).add(path3);
     document = Document::new()
         .set("viewBox", (0, 0, size.0, size.1))
@@ -482,11 +480,10 @@ pub fn aspects_maj_aspects(_theme: Theme, _lang: Language) -> Document {
 }
 
 /// Draw text for minors aspects
-pub fn aspects_min_aspects(_theme: Theme, _lang: Language) -> Document {
+pub fn aspects_min_aspects(style: &ChartStyle, _lang: Language) -> Document {
     let size: (Number, Number) = (ASPECT_SIZE, ASPECT_SIZE);
     let document: Document;
-    //let color: String =
-    //    format!("#{:06X}", Bodies::EclNut.object_color() as i32);
+    let color: &str = style.text.as_str();
     let data1 = Data::new()
         .move_to((19.9, 27.3)) // M
         .cubic_curve_by((-0.1, -2.8, -0.3, -6.2, -0.3, -8.7)) // c
@@ -512,7 +509,7 @@ pub fn aspects_min_aspects(_theme: Theme, _lang: Language) -> Document {
         .horizontal_line_by(-2.5) // h
         .line_to((19.9, 27.3)) // L
         .close(); // z
-    let path1 = Path::new().set("d", data1);
+    let path1 = Path::new().set("fill", color).set("d", data1);
     let data2 = Data::new()
         .move_to((30.2, 17.6)) // M
         .cubic_curve_by((0.0, 0.9, -0.6, 1.6, -1.7, 1.6)) // c
@@ -526,7 +523,7 @@ pub fn aspects_min_aspects(_theme: Theme, _lang: Language) -> Document {
         .vertical_line_by(14.5) // v
         .horizontal_line_to(27.3) // H
         .close(); // z
-    let path2 = Path::new().set("d", data2);
+    let path2 = Path::new().set("fill", color).set("d", data2);
     let data3 = Data::new()
         .move_to((34.3, 25.6)) // M
         .cubic_curve_by((0.0, -1.5, 0.0, -2.7, -0.1, -3.9)) // c
@@ -545,7 +542,7 @@ pub fn aspects_min_aspects(_theme: Theme, _lang: Language) -> Document {
         .horizontal_line_by(-2.6) // h
         .vertical_line_to(25.6) // V
         .close(); // z
-    let path3 = Path::new().set("d", data3);
+    let path3 = Path::new().set("fill", color).set("d", data3);
     let group = Group::new().add(path1).add(path2).add(path3);
     document = Document::new()
         .set("viewBox", (0, 0, size.0, size.1))
@@ -554,11 +551,10 @@ pub fn aspects_min_aspects(_theme: Theme, _lang: Language) -> Document {
 }
 
 /// Draw text for all aspects
-pub fn aspects_all_aspects(_theme: Theme, lang: Language) -> Document {
+pub fn aspects_all_aspects(style: &ChartStyle, lang: Language) -> Document {
     let size: (Number, Number) = (ASPECT_SIZE, ASPECT_SIZE);
     let document: Document;
-    //let color: String =
-    //    format!("#{:06X}", Bodies::EclNut.object_color() as i32);
+    let color: &str = style.text.as_str();
     match lang {
         Language::English => {
             let data1 = Data::new()
@@ -580,7 +576,7 @@ pub fn aspects_all_aspects(_theme: Theme, lang: Language) -> Document {
                 .line_by((-2.0, 5.9)) // l
                 .horizontal_line_to(20.7) // H
                 .close(); // z
-            let path1 = Path::new().set("d", data1);
+            let path1 = Path::new().set("fill", color).set("d", data1);
             let data2 = Data::new()
                 .move_to((28.9, 14.9)) // M
                 .horizontal_line_by(2.6) // h
@@ -588,7 +584,7 @@ pub fn aspects_all_aspects(_theme: Theme, lang: Language) -> Document {
                 .horizontal_line_by(-2.6) // h
                 .vertical_line_to(14.9) // V
                 .close(); // z
-            let path2 = Path::new().set("d", data2);
+            let path2 = Path::new().set("fill", color).set("d", data2);
             let data3 = Data::new()
                 .move_to((36.0, 14.9)) // M
                 .horizontal_line_by(2.6) // h
@@ -596,7 +592,7 @@ pub fn aspects_all_aspects(_theme: Theme, lang: Language) -> Document {
                 .horizontal_line_to(36.0) // H
                 .vertical_line_to(14.9) // V
                 .close(); // z
-            let path3 = Path::new().set("d", data3);
+            let path3 = Path::new().set("fill", color).set("d", data3);
             let group = Group::new().add(path1).add(path2).add(path3);
             document = Document::new()
                 .set("viewBox", (0, 0, size.0, size.1))
@@ -614,7 +610,7 @@ pub fn aspects_all_aspects(_theme: Theme, lang: Language) -> Document {
                 .horizontal_line_to(6.5) // H
                 .vertical_line_to(18.8) // V
                 .close(); // z
-            let path1 = Path::new().set("d", data1);
+            let path1 = Path::new().set("fill", color).set("d", data1);
             let data2 = Data::new()
                 .move_to((24.1, 27.3)) // M
                 .cubic_curve_by((0.0, 4.3, -3.0, 6.2, -5.8, 6.2)) // c
@@ -628,7 +624,7 @@ pub fn aspects_all_aspects(_theme: Theme, lang: Language) -> Document {
                 .cubic_curve_by((0.0, -2.0, -1.0, -4.5, -3.5, -4.5)) // c
                 .smooth_cubic_curve_to((14.9, 25.2, 14.9, 27.4)) // S
                 .close(); // z
-            let path2 = Path::new().set("d", data2);
+            let path2 = Path::new().set("fill", color).set("d", data2);
             let data3 = Data::new()
                 .move_to((36.5, 30.0)) // M
                 .cubic_curve_by((0.0, 1.2, 0.0, 2.3, 0.1, 3.2)) // c
@@ -647,7 +643,7 @@ pub fn aspects_all_aspects(_theme: Theme, lang: Language) -> Document {
                 .horizontal_line_by(2.1) // h
                 .vertical_line_to(30.0) // V
                 .close(); // z
-            let path3 = Path::new().set("d", data3);
+            let path3 = Path::new().set("fill", color).set("d", data3);
             let data4 = Data::new()
                 .move_to((39.7, 31.0)) // M
                 .cubic_curve_by((0.6, 0.4, 1.7, 0.8, 2.8, 0.8)) // c
@@ -665,7 +661,7 @@ pub fn aspects_all_aspects(_theme: Theme, lang: Language) -> Document {
                 .cubic_curve_by((-1.3, 0.0, -2.4, -0.3, -3.3, -0.8)) // c
                 .line_to((39.7, 31.0)) // L
                 .close(); // z
-            let path4 = Path::new().set("d", data4);
+            let path4 = Path::new().set("fill", color).set("d", data4);
             let group =
                 Group::new().add(path1).add(path2).add(path3).add(path4);
             document = Document::new()
@@ -15,25 +15,27 @@
  * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
  */
 extern crate strum;
-//use strum::AsStaticRef;
+use crate::svg_draw::chart_style::ChartStyle;
 use crate::svg_draw::svg_draw::{
     WorkingStorageDrawPolyMorphNatal, WorkingStorageDrawPolyMorphTransit,
 };
-use libswe_sys::sweconst::{Bodies, Theme};
+use libswe_sys::sweconst::Bodies;
+use strum::AsStaticRef;
 use svg::node::element::path::{Data, Number};
-use svg::node::element::{Circle, Group, Line, Path};
+use svg::node::element::{Circle, Group, Line, Path, Text};
+use svg::node::Text as TextNode;
 use svg::Document;
 pub const BODIE_SIZE: Number = 50.0;
 
 impl WorkingStorageDrawPolyMorphNatal {
     pub fn bodies_draw(&self, bodie: Bodies, sw_retrograde: bool) -> Document {
-        draw_bodie(bodie, sw_retrograde, self.ws.theme)
+        draw_bodie(bodie, sw_retrograde, &self.ws.style)
     }
 }
 
 impl WorkingStorageDrawPolyMorphTransit {
     pub fn bodies_draw(&self, bodie: Bodies, sw_retrograde: bool) -> Document {
-        draw_bodie(bodie, sw_retrograde, self.ws.theme)
+        draw_bodie(bodie, sw_retrograde, &self.ws.style)
     }
 }
 
@@ -76,11 +78,15 @@ fn is_retrograde(sw: bool, color: String) -> Path {
 }
 
 /// Draw bodie (Planet/Fiction/Asteroid) svg
-fn draw_bodie(bodie: Bodies, sw_retrograde: bool, theme: Theme) -> Document {
+pub(crate) fn draw_bodie(
+    bodie: Bodies,
+    sw_retrograde: bool,
+    style: &ChartStyle,
+) -> Document {
     let size: (Number, Number) = (BODIE_SIZE, BODIE_SIZE);
     let path: Path;
     let document: Document;
-    let color: String = format!("#{:06X}", bodie.object_color(theme));
+    let color: String = style.bodie_color(bodie);
     match bodie {
         Bodies::Sun => {
             let data = Data::new()
@@ -508,7 +514,7 @@ fn draw_bodie(bodie: Bodies, sw_retrograde: bool, theme: Theme) -> Document {
                 .add(group)
                 .add(is_retrograde(sw_retrograde, color));
         },
-        Bodies::TrueNode => {
+        Bodies::TrueNode | Bodies::MeanNode => {
             // Nord Node
             let data = Data::new()
                 .move_to((22.0, 6.3)) // M
@@ -839,8 +845,94 @@ fn draw_bodie(bodie: Bodies, sw_retrograde: bool, theme: Theme) -> Document {
                 .add(line2)
                 .add(is_retrograde(sw_retrograde, color));
         },
+        Bodies::Pallas => {
+            // Lozenge on a cross
+            let data = Data::new()
+                .move_to((25.0, 5.0)) // M
+                .line_to((34.0, 15.0)) // L
+                .line_to((25.0, 25.0)) // L
+                .line_to((16.0, 15.0)) // L
+                .close() // z
+                .move_to((25.0, 25.0)) // M
+                .vertical_line_to(45.0) // V
+                .move_to((17.0, 36.0)) // M
+                .horizontal_line_to(33.0); // H
+            let path = Path::new()
+                .set("fill", "none")
+                .set("stroke", color.clone())
+                .set("stroke-width", 3)
+                .set("d", data);
+            document = Document::new()
+                .set("viewBox", (0, 0, size.0, size.1))
+                .add(path)
+                .add(is_retrograde(sw_retrograde, color));
+        },
+        Bodies::Juno => {
+            // Star on a cross
+            let data = Data::new()
+                .move_to((25.0, 5.0)) // M
+                .vertical_line_to(23.0) // V
+                .move_to((17.2, 9.5)) // M
+                .line_to((32.8, 18.5)) // L
+                .move_to((32.8, 9.5)) // M
+                .line_to((17.2, 18.5)) // L
+                .move_to((25.0, 23.0)) // M
+                .vertical_line_to(45.0) // V
+                .move_to((17.0, 36.0)) // M
+                .horizontal_line_to(33.0); // H
+            let path = Path::new()
+                .set("fill", "none")
+                .set("stroke", color.clone())
+                .set("stroke-width", 3)
+                .set("d", data);
+            document = Document::new()
+                .set("viewBox", (0, 0, size.0, size.1))
+                .add(path)
+                .add(is_retrograde(sw_retrograde, color));
+        },
+        Bodies::Vesta => {
+            // Flame on the hearth
+            let data1 = Data::new()
+                .move_to((25.0, 5.0)) // M
+                .cubic_curve_to((31.0, 12.0, 31.0, 19.0, 25.0, 24.0)) // C
+                .cubic_curve_to((19.0, 19.0, 19.0, 12.0, 25.0, 5.0)) // C
+                .close(); // z
+            let path1 = Path::new()
+                .set("fill", color.clone())
+                .set("stroke", color.clone())
+                .set("stroke-width", 1)
+                .set("d", data1);
+            let data2 = Data::new()
+                .move_to((13.0, 26.0)) // M
+                .line_to((25.0, 44.0)) // L
+                .line_to((37.0, 26.0)); // L
+            let path2 = Path::new()
+                .set("fill", "none")
+                .set("stroke", color.clone())
+                .set("stroke-width", 3)
+                .set("d", data2);
+            document = Document::new()
+                .set("viewBox", (0, 0, size.0, size.1))
+                .add(path1)
+                .add(path2)
+                .add(is_retrograde(sw_retrograde, color));
+        },
         _ => {
-            document = Document::new().set("viewBox", (-1, 0, 50, 50));
+            // No glyph: abbreviation of the name
+            let name = bodie.as_static();
+            let name = name.strip_prefix("Asteroid").unwrap_or(name);
+            let text = Text::new()
+                .set("x", 25.0)
+                .set("y", 31.0)
+                .set("font-size", 16.0)
+                .set("font-family", "sans-serif")
+                .set("text-anchor", "middle")
+                .set("fill", color.clone())
+                .add(TextNode::new(name.chars().take(4).collect::<String>()));
+            document = Document::new()
+                .set("viewBox", (0, 0, size.0, size.1))
+                .add(text)
+                .add(is_retrograde(sw_retrograde, color));
         },
     }
     document
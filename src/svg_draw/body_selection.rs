@@ -0,0 +1,171 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+//! Bodies computed and drawn on the chart: planets, nodes, Liliths, asteroids
+//! and Part of Fortune
+use super::chart_data::CHART_BODIES;
+use super::serde_sweconst;
+use crate::error::AstrologyError;
+use libswe_sys::sweconst::Bodies;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use strum::{AsStaticRef, IntoEnumIterator};
+
+/// Lunar nodes of the chart
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum NodeKind {
+    /// No node
+    None,
+    /// Mean node (MeanNode)
+    Mean,
+    /// True node (TrueNode)
+    #[default]
+    True,
+}
+
+/// Selection of the bodies of a chart, by default the bodies of CHART_BODIES
+/// The aspects, the positions of the glyphs on the wheel, the ephemeris, the
+/// transit searches, the ingresses and stations and the parans of the fixed
+/// stars only use the selected bodies, the lots, the lunations, the returns
+/// and the solar arc compute their bodies without being selected
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BodySelection {
+    /// Planets (Sun to Pluto by default)
+    #[serde(with = "serde_sweconst::bodies_vec")]
+    pub planets: Vec<Bodies>,
+    /// North Node
+    pub nodes: NodeKind,
+    /// South Node (opposite of the North Node of nodes)
+    pub south_node: bool,
+    /// Mean Black Moon Lilith (MeanApog)
+    pub mean_lilith: bool,
+    /// True (osculating) Black Moon Lilith (OscuApog)
+    pub true_lilith: bool,
+    /// Asteroids and fictitious bodies: Chiron, Pholus, Ceres, Pallas, Juno,
+    /// Vesta, numbered asteroids (Asteroid*, they need their seas_*.se1 or
+    /// se*.se1 files) and Uranian planets, Chiron and Ceres by default
+    #[serde(with = "serde_sweconst::bodies_vec")]
+    pub asteroids: Vec<Bodies>,
+    /// Part of Fortune
+    pub fortune: bool,
+}
+
+impl Default for BodySelection {
+    fn default() -> Self {
+        Self {
+            planets: CHART_BODIES[..10].to_vec(),
+            nodes: NodeKind::True,
+            south_node: true,
+            mean_lilith: false,
+            true_lilith: true,
+            asteroids: vec![Bodies::Chiron, Bodies::Ceres],
+            fortune: true,
+        }
+    }
+}
+
+impl BodySelection {
+    /// Bodies in the order of the chart without duplicates: planets, North
+    /// Node, Liliths, asteroids, South Node and Part of Fortune
+    pub fn list(&self) -> Vec<Bodies> {
+        let mut list: Vec<Bodies> = Vec::new();
+        let mut push = |bodie: Bodies| {
+            if !list.contains(&bodie) {
+                list.push(bodie);
+            }
+        };
+        self.planets.iter().for_each(|b| push(*b));
+        match self.nodes {
+            NodeKind::None => {},
+            NodeKind::Mean => push(Bodies::MeanNode),
+            NodeKind::True => push(Bodies::TrueNode),
+        }
+        if self.mean_lilith {
+            push(Bodies::MeanApog);
+        }
+        if self.true_lilith {
+            push(Bodies::OscuApog);
+        }
+        self.asteroids.iter().for_each(|b| push(*b));
+        if self.south_node && self.nodes != NodeKind::None {
+            push(Bodies::SouthNode);
+        }
+        if self.fortune {
+            push(Bodies::FortunaPart);
+        }
+        list
+    }
+
+    /// Is the bodie selected
+    pub fn contains(&self, bodie: Bodies) -> bool {
+        self.list().contains(&bodie)
+    }
+}
+
+/// Comma separated list of the names of the bodies (AsStaticRef: "Sun",
+/// "Moon", "MeanNode", "Pallas", "AsteroidEros", ...)
+/// TrueNode/MeanNode, MeanApog/OscuApog, SouthNode and FortunaPart select the
+/// nodes, the Liliths and the Part of Fortune, the other bodies up to Pluto
+/// are planets and the others asteroids, a South Node without North Node
+/// selects the true nodes
+impl FromStr for BodySelection {
+    type Err = AstrologyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut selection = BodySelection {
+            planets: Vec::new(),
+            nodes: NodeKind::None,
+            south_node: false,
+            mean_lilith: false,
+            true_lilith: false,
+            asteroids: Vec::new(),
+            fortune: false,
+        };
+        for name in s.split(',').map(|n| n.trim()).filter(|n| !n.is_empty()) {
+            let bodie = Bodies::iter()
+                .find(|b| b.as_static().eq_ignore_ascii_case(name))
+                .ok_or_else(|| {
+                    AstrologyError::InvalidArgument(format!(
+                        "unknown bodie: {}",
+                        name
+                    ))
+                })?;
+            match bodie {
+                Bodies::EclNut | Bodies::Earth | Bodies::NPlanets => {
+                    return Err(AstrologyError::InvalidArgument(format!(
+                        "{} isn't a bodie of a chart",
+                        name
+                    )));
+                },
+                Bodies::TrueNode => selection.nodes = NodeKind::True,
+                Bodies::MeanNode => selection.nodes = NodeKind::Mean,
+                Bodies::SouthNode => selection.south_node = true,
+                Bodies::MeanApog => selection.mean_lilith = true,
+                Bodies::OscuApog => selection.true_lilith = true,
+                Bodies::FortunaPart => selection.fortune = true,
+                b if (b as i32) <= Bodies::Pluto as i32 => {
+                    selection.planets.push(b)
+                },
+                b => selection.asteroids.push(b),
+            }
+        }
+        if selection.south_node && selection.nodes == NodeKind::None {
+            selection.nodes = NodeKind::True;
+        }
+        Ok(selection)
+    }
+}
@@ -0,0 +1,83 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+//! Options of the computation of the bodies: center (geocentric, topocentric,
+//! heliocentric), true or apparent positions and equatorial coordinates
+use super::swe_topo;
+use libswe_sys::sweconst::OptionalFlag;
+use serde::{Deserialize, Serialize};
+
+/// Center of the positions of the bodies
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum Center {
+    /// Center of the earth
+    #[default]
+    Geocentric,
+    /// Location of the chart at CalcOptions::altitude (parallax of the Moon
+    /// up to ~1°)
+    Topocentric,
+    /// Center of the Sun, the Sun of the chart is at 0° (the Earth isn't a
+    /// bodie of the chart)
+    Heliocentric,
+}
+
+/// Options of the computation of the bodies
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub struct CalcOptions {
+    #[serde(default)]
+    pub center: Center,
+    /// Altitude in meter above sea level of a topocentric chart
+    #[serde(default)]
+    pub altitude: f64,
+    /// True (geometric) positions without light-time, aberration and
+    /// deflection, apparent positions by default
+    #[serde(default)]
+    pub true_position: bool,
+    /// Compute the right ascension and the declination of the bodies
+    /// (BodieData::right_ascension / declination)
+    #[serde(default)]
+    pub equatorial: bool,
+}
+
+impl CalcOptions {
+    /// Flag of calc_ut (with the speed)
+    pub fn flag(&self) -> i32 {
+        let mut flag = OptionalFlag::Speed as i32;
+        match self.center {
+            Center::Geocentric => {},
+            Center::Topocentric => {
+                flag |= OptionalFlag::TopocentricPosition as i32
+            },
+            Center::Heliocentric => flag |= OptionalFlag::Heliocentric as i32,
+        }
+        if self.true_position {
+            flag |= OptionalFlag::TruePosition as i32;
+        }
+        flag
+    }
+
+    /// Flag of calc_ut for the right ascension and the declination
+    pub fn flag_equatorial(&self) -> i32 {
+        self.flag() | OptionalFlag::EquatorialPosition as i32
+    }
+
+    /// Set the observer of a topocentric chart at lat/lng
+    pub(crate) fn set_topo(&self, lat: f64, lng: f64) {
+        if self.center == Center::Topocentric {
+            swe_topo::set_topo(lng, lat, self.altitude);
+        }
+    }
+}
@@ -0,0 +1,1079 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+extern crate libswe_sys;
+use super::aspect_config::{AspectConfig, ChartAspect};
+use super::body_selection::NodeKind;
+use super::calc_options::{CalcOptions, Center};
+use super::compute_chart::{ChartOptions, DataChartNatal};
+use super::house_system::HouseSystem;
+use super::lots::{compute_lots, lot_data, LotData};
+use super::lunation::{eclipse_before, syzygy_before, Eclipse, Lunation};
+use super::sect::{is_diurnal, planetary_hour, PlanetaryHour};
+use super::serde_sweconst;
+use super::sidereal::{zodiac_houses, Zodiac};
+use crate::error::AstrologyError;
+use chrono::{Datelike, NaiveDate, NaiveTime};
+use libswe_sys::sweconst::{
+    Angle, Bodies, Calandar, House, Object, OptionalFlag, Signs,
+};
+use libswe_sys::swerust;
+use libswe_sys::swerust::handler_swe14::HousesResult;
+use libswe_sys::swerust::handler_swe17;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+use strum::AsStaticRef;
+
+// Tolerance in degree for tag a cusp with an angle
+const ANGLE_ON_CUSP: f64 = 0.000_001;
+
+// Step in day for the speed of the angles (1 minute)
+const ANGLE_SPEED_STEP: f64 = 1.0 / 1440.0;
+
+// Julian day of 01.01.0001 00:00 UTC (proleptic gregorian calendar)
+const JULIAN_DAY_CE: f64 = 1_721_425.5;
+
+/// Bodies computed and drawn on the chart by default (BodySelection), also
+/// the bodies of the ephemeris and of the ingresses
+pub const CHART_BODIES: [Bodies; 16] = [
+    Bodies::Sun,
+    Bodies::Moon,
+    Bodies::Mercury,
+    Bodies::Venus,
+    Bodies::Mars,
+    Bodies::Jupiter,
+    Bodies::Saturn,
+    Bodies::Uranus,
+    Bodies::Neptune,
+    Bodies::Pluto,
+    Bodies::TrueNode, // North Node true
+    Bodies::OscuApog, // AsteroidLilith != Dark moon true
+    Bodies::Chiron,
+    Bodies::Ceres,
+    Bodies::SouthNode,
+    Bodies::FortunaPart,
+];
+
+/// Point of a chart used in an aspect
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ChartPoint {
+    Bodie(#[serde(with = "serde_sweconst::bodies")] Bodies),
+    Angle(#[serde(with = "serde_sweconst::angle")] Angle),
+}
+
+/// Position of a bodie
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BodieData {
+    #[serde(with = "serde_sweconst::bodies")]
+    pub bodie: Bodies,
+    pub longitude: f64,
+    pub latitude: f64,
+    pub speed: f64,
+    #[serde(with = "serde_sweconst::signs")]
+    pub sign: Signs,
+    pub deg: i32,
+    pub min: i32,
+    pub sec: i32,
+    pub house: i32,
+    pub retrograde: bool,
+    /// Right ascension in degree (CalcOptions::equatorial)
+    #[serde(default)]
+    pub right_ascension: Option<f64>,
+    /// Declination in degree (CalcOptions::equatorial)
+    #[serde(default)]
+    pub declination: Option<f64>,
+}
+
+/// Cusp of a house, angle is Angle::Nothing if no angle is on this cusp
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CuspData {
+    pub house: i32,
+    pub longitude: f64,
+    #[serde(with = "serde_sweconst::signs")]
+    pub sign: Signs,
+    pub deg: i32,
+    pub min: i32,
+    pub sec: i32,
+    #[serde(with = "serde_sweconst::angle")]
+    pub angle: Angle,
+}
+
+/// Position of an angle (Asc, Fc, Desc, Mc)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AngleData {
+    #[serde(with = "serde_sweconst::angle")]
+    pub angle: Angle,
+    pub longitude: f64,
+    #[serde(with = "serde_sweconst::signs")]
+    pub sign: Signs,
+    pub deg: i32,
+    pub min: i32,
+    pub sec: i32,
+    /// Degree by day
+    pub speed: f64,
+}
+
+/// Aspect between two points, orb is the distance in degree to the exact
+/// aspect
+/// For a transit, body_a is the natal point and body_b the transit bodie
+/// exact_in_days is computed with the speeds of the moment (linear), negative
+/// if the aspect is separating (days since exact), None if the two points
+/// have the same speed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AspectRecord {
+    pub body_a: ChartPoint,
+    pub body_b: ChartPoint,
+    pub aspect: ChartAspect,
+    pub orb: f64,
+    pub applying: bool,
+    pub exact_in_days: Option<f64>,
+}
+
+/// Chart computed without svg
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChartData {
+    pub julian_day: f64,
+    /// House system of the cusps, Porphyry for Placidus/Koch inside the
+    /// polar circles
+    pub house_system: HouseSystem,
+    /// Zodiac of the longitudes (tropical by default)
+    #[serde(default)]
+    pub zodiac: Zodiac,
+    /// Ayanamsa in degree at the julian day, 0 for the tropical zodiac
+    #[serde(default)]
+    pub ayanamsa: f64,
+    /// Options of the computation of the bodies
+    #[serde(default)]
+    pub calc: CalcOptions,
+    pub bodies: Vec<BodieData>,
+    pub cusps: Vec<CuspData>,
+    pub angles: Vec<AngleData>,
+    pub aspects: Vec<AspectRecord>,
+    /// Arabic lots of ChartOptions::lots, always computed with the geocentric
+    /// bodies
+    #[serde(default)]
+    pub lots: Vec<LotData>,
+    /// Day chart (geocentric Sun above the horizon)
+    #[serde(default)]
+    pub diurnal: bool,
+    /// Planetary day and hour at the julian day and the location of the
+    /// chart, None without sunrise or sunset (polar day or night) and for a
+    /// composite chart
+    #[serde(default)]
+    pub planetary_hour: Option<PlanetaryHour>,
+}
+
+impl ChartData {
+    /// Longitude of a bodie
+    pub fn bodie_longitude(&self, bodie: Bodies) -> Option<f64> {
+        self.bodies
+            .iter()
+            .find(|b| b.bodie == bodie)
+            .map(|b| b.longitude)
+    }
+
+    /// Longitude of an angle
+    pub fn angle_longitude(&self, angle: Angle) -> Option<f64> {
+        self.angles
+            .iter()
+            .find(|a| a.angle == angle)
+            .map(|a| a.longitude)
+    }
+
+    /// Longitude of a bodie or an angle
+    pub fn point_longitude(&self, point: ChartPoint) -> Option<f64> {
+        match point {
+            ChartPoint::Bodie(b) => self.bodie_longitude(b),
+            ChartPoint::Angle(a) => self.angle_longitude(a),
+        }
+    }
+
+    /// Bodies and Asc/Mc with their longitude and speed
+    pub fn points(&self) -> Vec<(ChartPoint, f64, f64)> {
+        self.bodies
+            .iter()
+            .map(|b| (ChartPoint::Bodie(b.bodie), b.longitude, b.speed))
+            .chain(
+                self.angles
+                    .iter()
+                    .filter(|a| a.angle == Angle::Asc || a.angle == Angle::Mc)
+                    .map(|a| {
+                        (ChartPoint::Angle(a.angle), a.longitude, a.speed)
+                    }),
+            )
+            .collect()
+    }
+
+    /// Objects for the working storage of the svg
+    pub fn objects(&self) -> Vec<Object> {
+        objects(&self.bodies)
+    }
+
+    /// Houses for the working storage of the svg
+    pub fn houses(&self) -> Vec<House> {
+        self.cusps
+            .iter()
+            .map(|c| House::new(c.house, c.longitude, c.angle))
+            .collect()
+    }
+
+    /// Angles for the working storage of the svg
+    pub fn angles_house(&self) -> Vec<House> {
+        self.angles
+            .iter()
+            .map(|a| {
+                let id = match a.angle {
+                    Angle::Asc => 1,
+                    Angle::Fc => 4,
+                    Angle::Desc => 7,
+                    Angle::Mc => 10,
+                    Angle::Nothing => 0,
+                };
+                House::new(id, a.longitude, a.angle)
+            })
+            .collect()
+    }
+}
+
+/// Transit chart computed without svg
+/// transit is an independent chart at the date, time zone and location of the
+/// transit: its cusps and angles are the relocated transit houses and the
+/// house of its bodies is a transit house (the natal house of a transit bodie
+/// is house_position(longitude, &natal.cusps))
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitData {
+    pub natal: ChartData,
+    pub transit: ChartData,
+    /// Aspects of the transit bodies to the natal chart
+    pub aspects: Vec<AspectRecord>,
+    /// Prenatal syzygy (with ChartOptions::lunation_markers)
+    pub prenatal_syzygy: Option<Lunation>,
+    /// Last eclipse before the transit (with ChartOptions::lunation_markers)
+    pub last_eclipse: Option<Eclipse>,
+}
+
+/// Synastry computed without svg, two independent natal charts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SynastryData {
+    pub chart_a: ChartData,
+    pub chart_b: ChartData,
+    /// Aspects between the two charts, body_a is a point of chart_a
+    pub aspects: Vec<AspectRecord>,
+}
+
+/// Date and time UTC (proleptic gregorian calendar), rounded to the second
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DateTimeUtc {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub min: u32,
+    pub sec: u32,
+}
+
+impl DateTimeUtc {
+    /// Date and time of a julian day (UT)
+    pub fn from_julian_day(julian_day: f64) -> Option<Self> {
+        let secs = ((julian_day - JULIAN_DAY_CE) * 86400.0).round() as i64;
+        let date = NaiveDate::from_num_days_from_ce_opt(
+            secs.div_euclid(86400) as i32 + 1,
+        )?;
+        let secs = secs.rem_euclid(86400) as u32;
+        Some(DateTimeUtc {
+            year: date.year(),
+            month: date.month(),
+            day: date.day(),
+            hour: secs / 3600,
+            min: secs % 3600 / 60,
+            sec: secs % 60,
+        })
+    }
+}
+
+impl fmt::Display for DateTimeUtc {
+    /// Format dd.mm.yyyy hh:mm:ss like the cli
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:02}.{:02}.{:04} {:02}:{:02}:{:02}",
+            self.day, self.month, self.year, self.hour, self.min, self.sec
+        )
+    }
+}
+
+/// Julian day in UT of a date with time zone
+pub fn julian_day(data: &DataChartNatal) -> Result<f64, AstrologyError> {
+    if NaiveDate::from_ymd_opt(data.year, data.month, data.day).is_none() {
+        return Err(AstrologyError::InvalidDateTime(format!(
+            "{}.{}.{} isn't a valid date",
+            data.day, data.month, data.year
+        )));
+    }
+    if NaiveTime::from_hms_opt(data.hour, data.min, data.sec as u32).is_none()
+        || !(0.0..60.0).contains(&data.sec)
+    {
+        return Err(AstrologyError::InvalidDateTime(format!(
+            "{}:{}:{} isn't a valid time",
+            data.hour, data.min, data.sec
+        )));
+    }
+    if !(-14.0..=14.0).contains(&data.time_zone) {
+        return Err(AstrologyError::InvalidDateTime(format!(
+            "{} isn't a valid time zone",
+            data.time_zone
+        )));
+    }
+    let utc_time_zone: swerust::handler_swe08::UtcTimeZoneResult =
+        swerust::handler_swe08::utc_time_zone(
+            data.year,
+            data.month as i32,
+            data.day as i32,
+            data.hour as i32,
+            data.min as i32,
+            data.sec.into(),
+            data.time_zone.into(),
+        );
+    let utc_to_jd: swerust::handler_swe08::UtcToJdResult =
+        swerust::handler_swe08::utc_to_jd(
+            utc_time_zone.year[0],
+            utc_time_zone.month[0],
+            utc_time_zone.day[0],
+            utc_time_zone.hour[0],
+            utc_time_zone.min[0],
+            utc_time_zone.sec[0],
+            Calandar::Gregorian,
+        );
+    if utc_to_jd.result < 0 {
+        return Err(AstrologyError::InvalidDateTime(utc_to_jd.err));
+    }
+    Ok(utc_to_jd.julian_day_ut)
+}
+
+/// Check the latitude (-90..90) and the longitude (-180..180)
+pub fn check_coordinates(lat: f32, lng: f32) -> Result<(), AstrologyError> {
+    if !(-90.0..=90.0).contains(&lat) {
+        Err(AstrologyError::InvalidCoordinates(format!(
+            "latitude {} isn't between -90 and 90",
+            lat
+        )))
+    } else if !(-180.0..=180.0).contains(&lng) {
+        Err(AstrologyError::InvalidCoordinates(format!(
+            "longitude {} isn't between -180 and 180",
+            lng
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+/// Set the path of the swiss ephemeris files
+pub fn set_ephe_path(path: &str) -> Result<(), AstrologyError> {
+    if !Path::new(path).exists() {
+        return Err(AstrologyError::MissingEphemerisFiles(format!(
+            "the path {} don't exist",
+            path
+        )));
+    }
+    swerust::handler_swe02::set_ephe_path(path);
+    Ok(())
+}
+
+/// Compute a natal chart without svg
+pub fn compute_natal(
+    data: DataChartNatal,
+    path: &str,
+    options: &ChartOptions,
+) -> Result<ChartData, AstrologyError> {
+    set_ephe_path(path)?;
+    check_coordinates(data.lat, data.lng)?;
+    let julian_day = julian_day(&data)?;
+    compute_chart(julian_day, data.lat as f64, data.lng as f64, options)
+}
+
+/// Chart at a julian day (UT) and a location
+pub(crate) fn compute_chart(
+    julian_day: f64,
+    lat: f64,
+    lng: f64,
+    options: &ChartOptions,
+) -> Result<ChartData, AstrologyError> {
+    compute_chart_houses(julian_day, julian_day, lat, lng, options)
+}
+
+/// Chart with the bodies at julian_day and the houses at julian_day_houses
+/// (progressed angles)
+pub(crate) fn compute_chart_houses(
+    julian_day: f64,
+    julian_day_houses: f64,
+    lat: f64,
+    lng: f64,
+    options: &ChartOptions,
+) -> Result<ChartData, AstrologyError> {
+    let ayanamsa = options.zodiac.ayanamsa(julian_day)?;
+    let ayanamsa_houses = options.zodiac.ayanamsa(julian_day_houses)?;
+    let houses = swerust::handler_swe14::houses(
+        julian_day_houses,
+        lat,
+        lng,
+        options.house_system.code(),
+    );
+    // For Placidus/Koch inside the polar circles swiss ephemeris return an
+    // error and compute the houses in Porphyry, the chart keep the system
+    // really used
+    let house_system = if houses.result < 0 {
+        HouseSystem::Porphyry
+    } else {
+        options.house_system
+    };
+    let house_result = zodiac_houses(houses, ayanamsa_houses, house_system);
+    let house_next = zodiac_houses(
+        swerust::handler_swe14::houses(
+            julian_day_houses + ANGLE_SPEED_STEP,
+            lat,
+            lng,
+            house_system.code(),
+        ),
+        ayanamsa_houses,
+        house_system,
+    );
+    let (cusps, angles) = cusps_and_angles(&house_result, &house_next);
+    let bodies =
+        compute_bodies(julian_day, lat, lng, ayanamsa, options, &cusps)?;
+    let aspects = natal_aspects(&bodies, &angles, &options.aspects);
+    // The sect and the lots are geocentric like the Asc (the Sun of a
+    // heliocentric chart is at 0°), the Sun and the bodies of the lots aren't
+    // always selected
+    let heliocentric = options.calc.center == Center::Heliocentric;
+    let geocentric = ChartOptions {
+        calc: CalcOptions {
+            center: Center::Geocentric,
+            ..options.calc
+        },
+        ..options.clone()
+    };
+    let position = |bodie| match bodies.iter().find(|b| b.bodie == bodie) {
+        Some(b) if !heliocentric => Ok(b.longitude),
+        _ => compute_bodie(
+            bodie,
+            julian_day,
+            lat,
+            lng,
+            ayanamsa,
+            &geocentric,
+            &cusps,
+        )
+        .map(|b| b.longitude),
+    };
+    // The Asc is the first angle of cusps_and_angles
+    let diurnal = is_diurnal(position(Bodies::Sun)?, angles[0].longitude);
+    let lots = compute_lots(
+        &options.lots.formulas,
+        diurnal,
+        &angles,
+        &cusps,
+        position,
+    )?;
+    let planetary_hour = planetary_hour(julian_day, lat, lng)?;
+    Ok(ChartData {
+        julian_day,
+        house_system,
+        zodiac: options.zodiac,
+        ayanamsa,
+        calc: options.calc,
+        bodies,
+        cusps,
+        angles,
+        aspects,
+        lots,
+        diurnal,
+        planetary_hour,
+    })
+}
+
+/// Compute a natal chart and a transit chart, each with its own date, time
+/// zone and location
+pub fn compute_transit(
+    data: DataChartNatal,
+    data_transit: DataChartNatal,
+    path: &str,
+    options: &ChartOptions,
+) -> Result<TransitData, AstrologyError> {
+    let location = (data.lat as f64, data.lng as f64);
+    let location_transit = (data_transit.lat as f64, data_transit.lng as f64);
+    let transit = compute_natal(data_transit, path, options)?;
+    let natal = compute_natal(data, path, options)?;
+    let aspects = transit_aspects(&natal, &transit.bodies, &options.aspects);
+    // The prenatal syzygy is seen from the birth place and the last eclipse
+    // from the place of the transit (topocentric chart)
+    let (prenatal_syzygy, last_eclipse) = if options.lunation_markers {
+        (
+            Some(syzygy_before(natal.julian_day, location, options)?),
+            Some(eclipse_before(
+                transit.julian_day,
+                location_transit,
+                options,
+            )?),
+        )
+    } else {
+        (None, None)
+    };
+    Ok(TransitData {
+        natal,
+        transit,
+        aspects,
+        prenatal_syzygy,
+        last_eclipse,
+    })
+}
+
+/// Compute the natal charts of two persons and the aspects between them
+pub fn compute_synastry(
+    data_a: DataChartNatal,
+    data_b: DataChartNatal,
+    path: &str,
+    options: &ChartOptions,
+) -> Result<SynastryData, AstrologyError> {
+    let chart_a = compute_natal(data_a, path, options)?;
+    let chart_b = compute_natal(data_b, path, options)?;
+    let aspects = synastry_aspects(&chart_a, &chart_b, &options.aspects);
+    Ok(SynastryData {
+        chart_a,
+        chart_b,
+        aspects,
+    })
+}
+
+/// Composite chart: midpoint of each pair of bodies, cusps and angles of two
+/// natal charts
+/// The cusps 7 to 12 and the Desc/Fc are opposite to the cusps 1 to 6 and the
+/// Asc/Mc, the julian day is the mean of the two julian days
+pub fn compute_composite(
+    data_a: DataChartNatal,
+    data_b: DataChartNatal,
+    path: &str,
+    options: &ChartOptions,
+) -> Result<ChartData, AstrologyError> {
+    let chart_a = compute_natal(data_a, path, options)?;
+    let chart_b = compute_natal(data_b, path, options)?;
+    let asc = composite_angle(&chart_a, &chart_b, Angle::Asc);
+    let mc = composite_angle(&chart_a, &chart_b, Angle::Mc);
+    let angles: Vec<AngleData> = vec![
+        angle_data(Angle::Asc, asc.0, asc.1),
+        angle_data(Angle::Fc, handler_swe17::degnorm(mc.0 + 180.0), mc.1),
+        angle_data(Angle::Desc, handler_swe17::degnorm(asc.0 + 180.0), asc.1),
+        angle_data(Angle::Mc, mc.0, mc.1),
+    ];
+    let half: Vec<f64> = chart_a
+        .cusps
+        .iter()
+        .zip(chart_b.cusps.iter())
+        .take(6)
+        .map(|(a, b)| midpoint(a.longitude, b.longitude))
+        .collect();
+    let cusps: Vec<CuspData> = half
+        .iter()
+        .copied()
+        .chain(half.iter().map(|l| handler_swe17::degnorm(l + 180.0)))
+        .enumerate()
+        .map(|(i, longitude)| cusp_data(i as i32 + 1, longitude, &angles))
+        .collect();
+    let bodies: Vec<BodieData> = chart_a
+        .bodies
+        .iter()
+        .zip(chart_b.bodies.iter())
+        .map(|(a, b)| {
+            bodie_data(
+                a.bodie,
+                midpoint(a.longitude, b.longitude),
+                (a.latitude + b.latitude) / 2.0,
+                (a.speed + b.speed) / 2.0,
+                &cusps,
+            )
+        })
+        .collect();
+    let aspects = natal_aspects(&bodies, &angles, &options.aspects);
+    // Midpoints of the lots, reversed only if the two lots are reversed
+    let lots: Vec<LotData> = chart_a
+        .lots
+        .iter()
+        .zip(chart_b.lots.iter())
+        .map(|(a, b)| {
+            lot_data(
+                &a.name,
+                midpoint(a.longitude, b.longitude),
+                a.reversed && b.reversed,
+                &cusps,
+            )
+        })
+        .collect();
+    let diurnal = match bodies.iter().find(|b| b.bodie == Bodies::Sun) {
+        Some(sun) => is_diurnal(sun.longitude, asc.0),
+        None => chart_a.diurnal,
+    };
+    Ok(ChartData {
+        julian_day: (chart_a.julian_day + chart_b.julian_day) / 2.0,
+        // Differ from chart_b only inside the polar circles (Porphyry)
+        house_system: chart_a.house_system,
+        zodiac: options.zodiac,
+        ayanamsa: (chart_a.ayanamsa + chart_b.ayanamsa) / 2.0,
+        calc: options.calc,
+        bodies,
+        cusps,
+        angles,
+        aspects,
+        lots,
+        diurnal,
+        planetary_hour: None,
+    })
+}
+
+/// Midpoint and mean speed of an angle of two charts
+fn composite_angle(
+    chart_a: &ChartData,
+    chart_b: &ChartData,
+    angle: Angle,
+) -> (f64, f64) {
+    let a = chart_a.angles.iter().find(|a| a.angle == angle);
+    let b = chart_b.angles.iter().find(|a| a.angle == angle);
+    match (a, b) {
+        (Some(a), Some(b)) => (
+            midpoint(a.longitude, b.longitude),
+            (a.speed + b.speed) / 2.0,
+        ),
+        _ => (0.0, 0.0),
+    }
+}
+
+/// Davison chart: chart at the midpoint in time (julian day) and in space
+/// (mean latitude, midpoint of the longitudes) of two natal charts
+pub fn compute_davison(
+    data_a: DataChartNatal,
+    data_b: DataChartNatal,
+    path: &str,
+    options: &ChartOptions,
+) -> Result<ChartData, AstrologyError> {
+    set_ephe_path(path)?;
+    check_coordinates(data_a.lat, data_a.lng)?;
+    check_coordinates(data_b.lat, data_b.lng)?;
+    let julian_day = (julian_day(&data_a)? + julian_day(&data_b)?) / 2.0;
+    let lat = (data_a.lat as f64 + data_b.lat as f64) / 2.0;
+    // Longitude of the midpoint -180..180
+    let mut lng = midpoint(data_a.lng as f64, data_b.lng as f64);
+    if lng > 180.0 {
+        lng -= 360.0;
+    }
+    compute_chart(julian_day, lat, lng, options)
+}
+
+/// Position of the bodies of options.bodies with options.calc
+/// The house is the house in cusps, the longitudes are the tropical longitudes
+/// minus the ayanamsa, lat/lng are the observer of a topocentric chart
+pub(crate) fn compute_bodies(
+    julian_day: f64,
+    lat: f64,
+    lng: f64,
+    ayanamsa: f64,
+    options: &ChartOptions,
+    cusps: &[CuspData],
+) -> Result<Vec<BodieData>, AstrologyError> {
+    options.calc.set_topo(lat, lng);
+    options
+        .bodies
+        .list()
+        .iter()
+        .map(|bodie| {
+            compute_bodie(
+                *bodie, julian_day, lat, lng, ayanamsa, options, cusps,
+            )
+        })
+        .collect()
+}
+
+/// Objects for the working storage of the svg
+pub(crate) fn objects(bodies: &[BodieData]) -> Vec<Object> {
+    bodies
+        .iter()
+        .map(|b| {
+            Object::new(
+                b.bodie,
+                b.bodie.as_static(),
+                b.bodie.object_type(),
+                b.longitude,
+                b.latitude,
+                b.speed,
+            )
+        })
+        .collect()
+}
+
+/// Error of calc_ut, a missing file (asteroids without seas_*.se1) is
+/// reported as MissingEphemerisFiles
+/// The status of calc_ut_fp is the one of swe_calc_ut with the number of the
+/// Part of Fortune (always "illegal planet number"), so it is not checked
+pub fn calc_error(
+    bodie: Bodies,
+    calc: &swerust::handler_swe03::CalcUtResult,
+) -> Result<(), AstrologyError> {
+    if calc.status >= 0 || bodie == Bodies::FortunaPart {
+        Ok(())
+    } else if calc.serr.contains("not found") {
+        Err(AstrologyError::MissingEphemerisFiles(calc.serr.clone()))
+    } else {
+        Err(AstrologyError::CalculationFailed(format!(
+            "{}: {}",
+            bodie.as_static(),
+            calc.serr
+        )))
+    }
+}
+
+/// Position of a bodie
+/// The lat/lng are only used for the Part of Fortune (computed in the tropical
+/// zodiac, its sidereal longitude is the tropical one minus the ayanamsa), it
+/// is always geocentric like the Asc and without equatorial coordinates
+fn compute_bodie(
+    bodie: Bodies,
+    julian_day: f64,
+    lat: f64,
+    lng: f64,
+    ayanamsa: f64,
+    options: &ChartOptions,
+    cusps: &[CuspData],
+) -> Result<BodieData, AstrologyError> {
+    let calc = if bodie == Bodies::FortunaPart {
+        swerust::handler_swe03::calc_ut_fp(
+            julian_day,
+            lat,
+            lng,
+            options.house_system.code_fortuna(),
+            OptionalFlag::Speed as i32,
+        )
+    } else {
+        calc_bodie(julian_day, bodie, options.calc.flag(), options.bodies.nodes)
+    };
+    calc_error(bodie, &calc)?;
+    let mut res = bodie_data(
+        bodie,
+        handler_swe17::degnorm(calc.longitude - ayanamsa),
+        calc.latitude,
+        calc.speed_longitude,
+        cusps,
+    );
+    if options.calc.equatorial && bodie != Bodies::FortunaPart {
+        let equatorial = calc_bodie(
+            julian_day,
+            bodie,
+            options.calc.flag_equatorial(),
+            options.bodies.nodes,
+        );
+        calc_error(bodie, &equatorial)?;
+        res.right_ascension = Some(equatorial.longitude);
+        // The South Node is computed from the North Node
+        res.declination = Some(if bodie == Bodies::SouthNode {
+            -equatorial.latitude
+        } else {
+            equatorial.latitude
+        });
+    }
+    Ok(res)
+}
+
+/// calc_ut of a bodie, the South Node is opposite to the North Node of nodes
+/// (calc_ut computes it from the true node, the latitude isn't negated)
+pub(crate) fn calc_bodie(
+    julian_day: f64,
+    bodie: Bodies,
+    flag: i32,
+    nodes: NodeKind,
+) -> swerust::handler_swe03::CalcUtResult {
+    if bodie == Bodies::SouthNode && nodes == NodeKind::Mean {
+        let mut calc =
+            swerust::handler_swe03::calc_ut(julian_day, Bodies::MeanNode, flag);
+        calc.longitude = handler_swe17::degnorm(calc.longitude + 180.0);
+        calc
+    } else {
+        swerust::handler_swe03::calc_ut(julian_day, bodie, flag)
+    }
+}
+
+/// Bodie with its sign and house
+pub(crate) fn bodie_data(
+    bodie: Bodies,
+    longitude: f64,
+    latitude: f64,
+    speed: f64,
+    cusps: &[CuspData],
+) -> BodieData {
+    let split = handler_swe17::split_deg(longitude, 0);
+    BodieData {
+        bodie,
+        longitude,
+        latitude,
+        speed,
+        sign: split.sign,
+        deg: split.deg.abs(),
+        min: split.min,
+        sec: split.sec,
+        house: house_position(longitude, cusps),
+        retrograde: speed < 0.0,
+        right_ascension: None,
+        declination: None,
+    }
+}
+
+/// Angle with its sign
+pub(crate) fn angle_data(
+    angle: Angle,
+    longitude: f64,
+    speed: f64,
+) -> AngleData {
+    let split = handler_swe17::split_deg(longitude, 0);
+    AngleData {
+        angle,
+        longitude,
+        sign: split.sign,
+        deg: split.deg.abs(),
+        min: split.min,
+        sec: split.sec,
+        speed,
+    }
+}
+
+/// Cusp with its sign, tagged with the angle on the cusp
+pub(crate) fn cusp_data(
+    house: i32,
+    longitude: f64,
+    angles: &[AngleData],
+) -> CuspData {
+    let mut angle = Angle::Nothing;
+    for a in angles.iter() {
+        if separation(longitude, a.longitude) < ANGLE_ON_CUSP {
+            angle = a.angle;
+        }
+    }
+    let split = handler_swe17::split_deg(longitude, 0);
+    CuspData {
+        house,
+        longitude,
+        sign: split.sign,
+        deg: split.deg.abs(),
+        min: split.min,
+        sec: split.sec,
+        angle,
+    }
+}
+
+/// Split the house result in cusps and angles
+/// A cusp is tagged with an angle only if the angle is on this cusp (the
+/// four angles for a quadrant system, Asc/Desc for Equal, none for Whole sign)
+/// The speed of the angles is computed with house_next (ANGLE_SPEED_STEP
+/// later)
+fn cusps_and_angles(
+    house: &HousesResult,
+    house_next: &HousesResult,
+) -> (Vec<CuspData>, Vec<AngleData>) {
+    let asc = house.ascmc[0];
+    let mc = house.ascmc[1];
+    let asc_speed =
+        signed_distance(asc, house_next.ascmc[0]) / ANGLE_SPEED_STEP;
+    let mc_speed = signed_distance(mc, house_next.ascmc[1]) / ANGLE_SPEED_STEP;
+    let angles: Vec<AngleData> = vec![
+        (Angle::Asc, asc, asc_speed),
+        (Angle::Fc, handler_swe17::degnorm(mc + 180.0), mc_speed),
+        (Angle::Desc, handler_swe17::degnorm(asc + 180.0), asc_speed),
+        (Angle::Mc, mc, mc_speed),
+    ]
+    .into_iter()
+    .map(|(angle, longitude, speed)| angle_data(angle, longitude, speed))
+    .collect();
+    let cusps: Vec<CuspData> = house
+        .cusps
+        .iter()
+        .enumerate()
+        .take(13)
+        .skip(1)
+        .map(|(i, longitude)| cusp_data(i as i32, *longitude, &angles))
+        .collect();
+    (cusps, angles)
+}
+
+/// House (1-12) of a longitude
+pub fn house_position(longitude: f64, cusps: &[CuspData]) -> i32 {
+    for (i, cusp) in cusps.iter().enumerate() {
+        let next = &cusps[(i + 1) % cusps.len()];
+        let size = handler_swe17::degnorm(next.longitude - cusp.longitude);
+        if handler_swe17::degnorm(longitude - cusp.longitude) < size {
+            return cusp.house;
+        }
+    }
+    1
+}
+
+/// Shortest angular distance between two longitudes (0-180)
+pub fn separation(lng_1: f64, lng_2: f64) -> f64 {
+    let d = handler_swe17::degnorm(lng_2 - lng_1);
+    if d > 180.0 {
+        360.0 - d
+    } else {
+        d
+    }
+}
+
+/// Midpoint of two longitudes on the shortest arc (0-360)
+pub fn midpoint(lng_1: f64, lng_2: f64) -> f64 {
+    handler_swe17::degnorm(lng_1 + signed_distance(lng_1, lng_2) / 2.0)
+}
+
+/// Distance from lng_1 to lng_2 in the direction of the zodiac (-180-180)
+pub(crate) fn signed_distance(lng_1: f64, lng_2: f64) -> f64 {
+    let d = handler_swe17::degnorm(lng_2 - lng_1);
+    if d > 180.0 {
+        d - 360.0
+    } else {
+        d
+    }
+}
+
+/// Aspect between two points with their longitude and speed (degree by day)
+pub fn aspect_record(
+    config: &AspectConfig,
+    point_a: ChartPoint,
+    lng_a: f64,
+    speed_a: f64,
+    point_b: ChartPoint,
+    lng_b: f64,
+    speed_b: f64,
+) -> Option<AspectRecord> {
+    let (aspect, orb) = config.find_aspect(point_a, lng_a, point_b, lng_b)?;
+    // Speed of the separation (0-180)
+    let speed_separation = if signed_distance(lng_a, lng_b) >= 0.0 {
+        speed_b - speed_a
+    } else {
+        speed_a - speed_b
+    };
+    // Speed of the orb
+    let speed_orb = if separation(lng_a, lng_b) >= aspect.angle() {
+        speed_separation
+    } else {
+        -speed_separation
+    };
+    Some(AspectRecord {
+        body_a: point_a,
+        body_b: point_b,
+        aspect,
+        orb,
+        applying: speed_orb < 0.0,
+        exact_in_days: if speed_orb == 0.0 {
+            None
+        } else {
+            Some(-orb / speed_orb)
+        },
+    })
+}
+
+/// Aspects of the transit bodies to the natal bodies and Asc/Mc
+/// The natal points are fixed, body_a is the natal point
+pub fn transit_aspects(
+    natal: &ChartData,
+    transit: &[BodieData],
+    config: &AspectConfig,
+) -> Vec<AspectRecord> {
+    let mut res: Vec<AspectRecord> = Vec::new();
+    let natal_points = natal.points();
+    for t in transit.iter() {
+        for (point, longitude, _) in natal_points.iter().copied() {
+            if let Some(record) = aspect_record(
+                config,
+                point,
+                longitude,
+                0.0,
+                ChartPoint::Bodie(t.bodie),
+                t.longitude,
+                t.speed,
+            ) {
+                res.push(record);
+            }
+        }
+    }
+    res
+}
+
+/// Aspects between the points (bodies and Asc/Mc) of two natal charts,
+/// body_a is the point of chart_a
+pub fn synastry_aspects(
+    chart_a: &ChartData,
+    chart_b: &ChartData,
+    config: &AspectConfig,
+) -> Vec<AspectRecord> {
+    let points_b = chart_b.points();
+    chart_a
+        .points()
+        .into_iter()
+        .flat_map(|(point_a, lng_a, speed_a)| {
+            points_b
+                .iter()
+                .filter_map(move |(point_b, lng_b, speed_b)| {
+                    aspect_record(
+                        config, point_a, lng_a, speed_a, *point_b, *lng_b,
+                        *speed_b,
+                    )
+                })
+        })
+        .collect()
+}
+
+/// Aspects between the bodies, and between the bodies and the Asc/Mc
+pub(crate) fn natal_aspects(
+    bodies: &[BodieData],
+    angles: &[AngleData],
+    config: &AspectConfig,
+) -> Vec<AspectRecord> {
+    let mut res: Vec<AspectRecord> = Vec::new();
+    for (i, b1) in bodies.iter().enumerate() {
+        for b2 in bodies.iter().skip(i + 1) {
+            if let Some(record) = aspect_record(
+                config,
+                ChartPoint::Bodie(b1.bodie),
+                b1.longitude,
+                b1.speed,
+                ChartPoint::Bodie(b2.bodie),
+                b2.longitude,
+                b2.speed,
+            ) {
+                res.push(record);
+            }
+        }
+        for a in angles
+            .iter()
+            .filter(|a| a.angle == Angle::Asc || a.angle == Angle::Mc)
+        {
+            if let Some(record) = aspect_record(
+                config,
+                ChartPoint::Bodie(b1.bodie),
+                b1.longitude,
+                b1.speed,
+                ChartPoint::Angle(a.angle),
+                a.longitude,
+                a.speed,
+            ) {
+                res.push(record);
+            }
+        }
+    }
+    res
+}
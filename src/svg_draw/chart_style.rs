@@ -0,0 +1,179 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+use super::aspect_config::ChartAspect;
+use super::chart_data::CHART_BODIES;
+use super::serde_sweconst;
+use libswe_sys::sweconst::{Bodies, Colors, Element, Signs, Theme};
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+/// Color of a bodie
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BodieColor {
+    #[serde(with = "serde_sweconst::bodies")]
+    pub bodie: Bodies,
+    pub color: String,
+}
+
+/// Color of the line of an aspect
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AspectColor {
+    pub aspect: ChartAspect,
+    pub color: String,
+}
+
+/// Colors of the chart
+/// All colors are svg colors ("#RRGGBB", "black", "none"...)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChartStyle {
+    /// Background of the chart, "none" for transparent
+    pub background: String,
+    /// Circles, rules of the zodiac and separation of the houses
+    pub circle: String,
+    /// Number of the houses and glyph of the aspects
+    pub text: String,
+    /// Glyph, degree and minute of the angles
+    pub angle: String,
+    pub fire: String,
+    pub earth: String,
+    pub air: String,
+    pub water: String,
+    pub bodies: Vec<BodieColor>,
+    /// Color of a bodie not in bodies
+    pub bodie_default: String,
+    pub aspects: Vec<AspectColor>,
+    /// Color of an aspect not in aspects
+    pub aspect_default: String,
+}
+
+impl Default for ChartStyle {
+    fn default() -> Self {
+        ChartStyle::light()
+    }
+}
+
+impl From<Theme> for ChartStyle {
+    fn from(theme: Theme) -> Self {
+        match theme {
+            Theme::Light => ChartStyle::light(),
+            Theme::Dark => ChartStyle::dark(),
+        }
+    }
+}
+
+/// Color of libswe_sys in svg format
+fn hex(color: u32) -> String {
+    format!("#{:06X}", color)
+}
+
+impl ChartStyle {
+    /// Light theme, black on transparent background
+    pub fn light() -> Self {
+        let mut style = ChartStyle::theme(Theme::Light);
+        style.background = "none".to_string();
+        style.circle = "black".to_string();
+        style.text = "black".to_string();
+        style
+    }
+
+    /// Dark theme, white on black background
+    pub fn dark() -> Self {
+        ChartStyle::theme(Theme::Dark)
+    }
+
+    /// Colors of libswe_sys for a theme
+    fn theme(theme: Theme) -> Self {
+        ChartStyle {
+            background: hex(theme.color(Colors::Background) as u32),
+            circle: hex(theme.color(Colors::Primary) as u32),
+            text: hex(theme.color(Colors::Primary) as u32),
+            angle: hex(Bodies::EclNut.object_color(theme) as u32),
+            fire: hex(Element::Fire.color(theme)),
+            earth: hex(Element::Earth.color(theme)),
+            air: hex(Element::Wind.color(theme)),
+            water: hex(Element::Water.color(theme)),
+            bodies: CHART_BODIES
+                .iter()
+                .map(|b| BodieColor {
+                    bodie: *b,
+                    color: hex(b.object_color(theme) as u32),
+                })
+                .collect(),
+            bodie_default: hex(Bodies::EclNut.object_color(theme) as u32),
+            aspects: ChartAspect::iter()
+                .map(|a| AspectColor {
+                    aspect: a,
+                    color: match a {
+                        ChartAspect::Conjunction
+                        | ChartAspect::Opposition
+                        | ChartAspect::Square => "red",
+                        ChartAspect::Trine => "blue",
+                        ChartAspect::Sextile => "green",
+                        ChartAspect::Inconjunction
+                        | ChartAspect::Semisextile => "violet",
+                        ChartAspect::Sesquisquare | ChartAspect::Semisquare => {
+                            "brown"
+                        },
+                        ChartAspect::Quintile | ChartAspect::Biquintile => {
+                            "darkcyan"
+                        },
+                        ChartAspect::Septile
+                        | ChartAspect::Biseptile
+                        | ChartAspect::Triseptile => "purple",
+                        ChartAspect::Novile
+                        | ChartAspect::Binovile
+                        | ChartAspect::Quadrinovile => "olive",
+                    }
+                    .to_string(),
+                })
+                .collect(),
+            aspect_default: hex(theme.color(Colors::Primary) as u32),
+        }
+    }
+
+    /// Color of a bodie, Bodies::EclNut is used for the angles
+    pub fn bodie_color(&self, bodie: Bodies) -> String {
+        if bodie == Bodies::EclNut {
+            return self.angle.clone();
+        }
+        self.bodies
+            .iter()
+            .find(|b| b.bodie == bodie)
+            .map(|b| b.color.clone())
+            .unwrap_or_else(|| self.bodie_default.clone())
+    }
+
+    /// Color of the line of an aspect
+    pub fn aspect_color(&self, aspect: ChartAspect) -> String {
+        self.aspects
+            .iter()
+            .find(|a| a.aspect == aspect)
+            .map(|a| a.color.clone())
+            .unwrap_or_else(|| self.aspect_default.clone())
+    }
+
+    /// Color of a sign by element
+    pub fn sign_color(&self, sign: Signs) -> String {
+        match sign.element() {
+            Element::Fire => self.fire.clone(),
+            Element::Earth => self.earth.clone(),
+            Element::Wind => self.air.clone(),
+            Element::Water => self.water.clone(),
+        }
+    }
+}
@@ -15,24 +15,42 @@
  * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
  */
 extern crate libswe_sys;
+use super::aspect_config::{filter_aspects, AspectConfig, ChartAspect};
+use super::aspectarian::{aspectarian, aspectarian_transit};
 use super::aspects::{
     aspects_all_aspects, aspects_draw, aspects_maj_aspects,
     aspects_min_aspects, aspects_no_aspect,
 };
+use super::body_selection::BodySelection;
+use super::calc_options::CalcOptions;
+use super::chart_data::{
+    compute_composite, compute_davison, compute_natal, compute_synastry,
+    compute_transit, AspectRecord, ChartData, ChartPoint,
+};
+use super::chart_style::ChartStyle;
+use super::fixed_stars::{
+    check_star_file, fixed_stars, FixedStarData, FixedStarOptions,
+};
+use super::house_system::HouseSystem;
+use super::lots::LotOptions;
+use super::progression::{
+    compute_directed, compute_progressed, DirectionOptions, ProgressedAngles,
+};
+use super::returns::ReturnData;
+use super::sect::sect_label;
+use super::sidereal::Zodiac;
 use super::svg_draw::{
-    CalcDraw, Draw, WorkingStorageDrawPolyMorphNatal,
-    WorkingStorageDrawPolyMorphTransit, WorkingStoragePolyMorphNatal,
-    WorkingStoragePolyMorphTransit,
+    draw_label, draw_sect_label, CalcDraw, Draw, LabelCorner, SvgObject,
+    WorkingStorageDrawPolyMorphNatal, WorkingStorageDrawPolyMorphTransit,
+    WorkingStoragePolyMorphNatal, WorkingStoragePolyMorphTransit,
 };
+use crate::error::AstrologyError;
 use base64::encode;
 use libswe_sys::sweconst::{
-    Angle, Aspects, AspectsFilter, Bodies, Calandar, Language, Object,
-    ObjectType, OptionalFlag, Signs, Theme,
+    Angle, Aspects, AspectsFilter, Bodies, Language, Signs,
 };
-use libswe_sys::swerust;
 use serde::{Deserialize, Serialize};
 use std::f32;
-use strum::AsStaticRef;
 use strum::IntoEnumIterator;
 use svg::node::element::path::Number;
 
@@ -50,6 +68,67 @@ pub struct DataChartNatal {
     pub time_zone: f32,
 }
 
+/// Options of the chart
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChartOptions {
+    #[serde(default)]
+    pub house_system: HouseSystem,
+    /// Colors of the chart (light by default)
+    #[serde(default)]
+    pub style: ChartStyle,
+    /// Aspects and orbs (aspects of libswe_sys by default)
+    #[serde(default)]
+    pub aspects: AspectConfig,
+    /// Transit chart: draw the houses and angles of the transit location
+    /// (relocated transit wheel) in the transit circle, in addition to the
+    /// natal houses (synastry chart: the houses of person B, progressed
+    /// chart: the progressed houses)
+    #[serde(default)]
+    pub transit_houses: bool,
+    /// Chart with two rings: label in the top left corner (natal chart,
+    /// person A of a synastry), not drawn if empty
+    #[serde(default)]
+    pub label_a: String,
+    /// Chart with two rings: label in the top right corner (transit, person B
+    /// of a synastry), not drawn if empty
+    #[serde(default)]
+    pub label_b: String,
+    /// Progressed chart: angles and houses (Naibod by default)
+    #[serde(default)]
+    pub progressed_angles: ProgressedAngles,
+    /// Directed chart: arc (solar arc by default) and orb (1° by default) of
+    /// the directions
+    #[serde(default)]
+    pub directions: DirectionOptions,
+    /// Transit chart: markers of the prenatal syzygy and of the last eclipse
+    /// before the transit
+    #[serde(default)]
+    pub lunation_markers: bool,
+    /// Tropical (by default) or sidereal zodiac, the ayanamsa is drawn in the
+    /// bottom left corner of the svg
+    #[serde(default)]
+    pub zodiac: Zodiac,
+    /// Center (geocentric by default), true positions and equatorial
+    /// coordinates of the bodies
+    #[serde(default)]
+    pub calc: CalcOptions,
+    /// Bodies computed and drawn (CHART_BODIES by default)
+    #[serde(default)]
+    pub bodies: BodySelection,
+    /// Fixed stars: catalog, orbs of the conjunctions and of the parans,
+    /// outer ring of the natal chart
+    #[serde(default)]
+    pub stars: FixedStarOptions,
+    /// Arabic lots: formulas (hermetic lots by default), markers on the
+    /// natal wheel
+    #[serde(default)]
+    pub lots: LotOptions,
+    /// Natal chart: sect, ruler of the day and planetary hour in the bottom
+    /// right corner of the svg
+    #[serde(default)]
+    pub sect_label: bool,
+}
+
 /// Data object for json svg
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct DataObjectSvg {
@@ -59,7 +138,9 @@ pub struct DataObjectSvg {
     pub size_y: f32,
     pub pos_x: f32,
     pub pos_y: f32,
-    pub aspects: Vec<Aspects>, // If null no aspects
+    pub aspects: Vec<ChartAspect>, // If null no aspects
+    /// Aspect of a DataObjectType::Aspect
+    pub aspect_record: Option<AspectRecord>,
 }
 
 /// Type of object used in struct DataObjectSvg
@@ -72,6 +153,10 @@ pub enum DataObjectType {
     Aspect,
     Chart,
     House,
+    Label,
+    Lot,
+    Marker,
+    Star,
     Zodiac,
     Planet,
     PlanetDeg,
@@ -93,108 +178,100 @@ pub fn chart(
     data: DataChartNatal,
     path: &str,
     lang: Language,
-) -> Vec<DataObjectSvg> {
-    // To do theme
-    let theme = Theme::Light;
+    options: &ChartOptions,
+) -> Result<Vec<DataObjectSvg>, AstrologyError> {
     // Natal chart
-    println!("Version swephem: {}", swerust::handler_swe02::version());
-    //let swe02_path: &str =
-    //    "/Users/stephanebressani/Code/Rust/libswe-sys/src/swisseph/sweph/";
-    swerust::handler_swe02::set_ephe_path(&path);
-    // println!("{}", data.year);
     //TODO: set_ephe_path(&path) work but get_library_path() return the path
     //      of this directory, this is wrong, but the ephem files are loaded
     //println!(
     //    "Library path: {}",
     //    swerust::handler_swe02::get_library_path()
     //);
-    // House natal chart
-    println!("Hsys: {}", swerust::handler_swe14::house_name('P')); // Placidus
-    let utc_time_zone: swerust::handler_swe08::UtcTimeZoneResult =
-        swerust::handler_swe08::utc_time_zone(
-            data.year,
-            data.month as i32,     //TODO
-            data.day as i32,       //TODO
-            data.hour as i32,      //TODO
-            data.min as i32,       //TODO
-            data.sec.into(),       //TODO need to change libswe_sys f64 -> f32
-            data.time_zone.into(), //TODO
-        );
-    //println!("UtcTimeZone: {:?}", utc_time_zone);
-    let utc_to_jd: swerust::handler_swe08::UtcToJdResult =
-        swerust::handler_swe08::utc_to_jd(
-            utc_time_zone.year[0],
-            utc_time_zone.month[0],
-            utc_time_zone.day[0],
-            utc_time_zone.hour[0],
-            utc_time_zone.min[0],
-            utc_time_zone.sec[0],
-            Calandar::Gregorian,
-        );
-    //println!("GregorianTimeZone: {:?}", utc_to_jd);
-    let house_result = swerust::handler_swe14::houses(
-        utc_to_jd.julian_day_ut,
-        data.lat as f64, //TODO in libswe_sys f64 -> f32
-        data.lng as f64, //TODO in libswe_sys f64 -> f32
-        'P',             // Placidus
-    );
+    let chart_data = compute_natal(data, path, options)?;
+    let stars = if options.stars.ring {
+        check_star_file(path)?;
+        fixed_stars(
+            chart_data.julian_day,
+            chart_data.zodiac,
+            &options.stars.stars,
+        )?
+    } else {
+        Vec::new()
+    };
+    Ok(chart_natal(max_size, &chart_data, &stars, lang, options))
+}
 
-    let mut object: Vec<Object> = Vec::new();
-    let mut calc: swerust::handler_swe03::CalcUtResult;
-    for bodie in Bodies::iter() {
-        if bodie.clone().object_type() == ObjectType::PlanetOrStar
-            || bodie.clone().object_type() == ObjectType::Fiction
-        {
-            calc = if bodie.clone() == Bodies::FortunaPart {
-                swerust::handler_swe03::calc_ut_fp(
-                    utc_to_jd.julian_day_ut,
-                    data.lat as f64, //TODO
-                    data.lng as f64, //TODO
-                    'P',
-                    OptionalFlag::Speed as i32,
-                )
-            } else {
-                swerust::handler_swe03::calc_ut(
-                    utc_to_jd.julian_day_ut, // debug julianday in orginal file
-                    bodie.clone(),
-                    OptionalFlag::Speed as i32,
-                )
-            };
-            object.push(Object::new(
-                bodie.clone(),
-                bodie.clone().as_static(),
-                bodie.clone().object_type(),
-                calc.longitude,
-                calc.latitude,
-                calc.speed_longitude,
-            ));
-        }
-    }
+/// Create a composite chart (midpoints of two natal charts)
+pub fn chart_composite(
+    max_size: Number,
+    data_a: DataChartNatal,
+    data_b: DataChartNatal,
+    path: &str,
+    lang: Language,
+    options: &ChartOptions,
+) -> Result<Vec<DataObjectSvg>, AstrologyError> {
+    let chart_data = compute_composite(data_a, data_b, path, options)?;
+    Ok(chart_natal(max_size, &chart_data, &[], lang, options))
+}
+
+/// Create a Davison chart (midpoint in time and space of two natal charts)
+pub fn chart_davison(
+    max_size: Number,
+    data_a: DataChartNatal,
+    data_b: DataChartNatal,
+    path: &str,
+    lang: Language,
+    options: &ChartOptions,
+) -> Result<Vec<DataObjectSvg>, AstrologyError> {
+    let chart_data = compute_davison(data_a, data_b, path, options)?;
+    Ok(chart_natal(max_size, &chart_data, &[], lang, options))
+}
 
+/// Create a return chart (solar_return, lunar_return)
+pub fn chart_return(
+    max_size: Number,
+    return_data: &ReturnData,
+    lang: Language,
+    options: &ChartOptions,
+) -> Vec<DataObjectSvg> {
+    chart_natal(max_size, &return_data.chart, &[], lang, options)
+}
+
+/// Objects of a chart with one ring of bodies (natal, composite, Davison,
+/// return), the stars are drawn on an outer ring
+fn chart_natal(
+    max_size: Number,
+    chart_data: &ChartData,
+    stars: &[FixedStarData],
+    lang: Language,
+    options: &ChartOptions,
+) -> Vec<DataObjectSvg> {
     // Object calc draw for calcul in svg x,y width, height
     let mut ws = WorkingStoragePolyMorphNatal::new(
         max_size,
-        theme,
+        options.style.clone(),
         lang,
-        house_result,
-        object,
+        chart_data.houses(),
+        chart_data.angles_house(),
+        chart_data.objects(),
     );
     ws.set_fix_compute(false);
     let ws_draw = WorkingStorageDrawPolyMorphNatal::new(ws.clone());
 
     let mut res: Vec<DataObjectSvg> = Vec::new();
 
-    let aspects_null: Vec<Aspects> = Vec::new();
+    let aspects_null: Vec<ChartAspect> = Vec::new();
 
     // Chart
     res.push(DataObjectSvg {
         svg: ws_draw.draw_base().to_string(),
         object_type: DataObjectType::Chart,
-        size_x: max_size as f32,
-        size_y: max_size as f32,
+        size_x: max_size,
+        size_y: max_size,
         pos_x: 0.0,
         pos_y: 0.0,
         aspects: aspects_null.clone(),
+        aspect_record: None,
     });
 
     // Zodiac
@@ -203,11 +280,12 @@ pub fn chart(
         res.push(DataObjectSvg {
             svg: draw.svg,
             object_type: DataObjectType::Zodiac,
-            size_x: draw.size_x as f32,
-            size_y: draw.size_y as f32,
-            pos_x: draw.pos_x as f32,
-            pos_y: draw.pos_y as f32,
+            size_x: draw.size_x,
+            size_y: draw.size_y,
+            pos_x: draw.pos_x,
+            pos_y: draw.pos_y,
             aspects: aspects_null.clone(),
+            aspect_record: None,
         });
     }
 
@@ -216,11 +294,12 @@ pub fn chart(
         res.push(DataObjectSvg {
             svg: draw.svg,
             object_type: DataObjectType::House,
-            size_x: draw.size_x as f32,
-            size_y: draw.size_y as f32,
-            pos_x: draw.pos_x as f32,
-            pos_y: draw.pos_y as f32,
+            size_x: draw.size_x,
+            size_y: draw.size_y,
+            pos_x: draw.pos_x,
+            pos_y: draw.pos_y,
             aspects: aspects_null.clone(),
+            aspect_record: None,
         });
     }
 
@@ -230,38 +309,42 @@ pub fn chart(
             res.push(DataObjectSvg {
                 svg: draw.svg,
                 object_type: DataObjectType::Angle,
-                size_x: draw.size_x as f32,
-                size_y: draw.size_y as f32,
-                pos_x: draw.pos_x as f32,
-                pos_y: draw.pos_y as f32,
+                size_x: draw.size_x,
+                size_y: draw.size_y,
+                pos_x: draw.pos_x,
+                pos_y: draw.pos_y,
                 aspects: aspects_null.clone(),
+                aspect_record: None,
             });
             res.push(DataObjectSvg {
                 svg: draw.deg_svg,
                 object_type: DataObjectType::AngleDeg,
-                size_x: draw.deg_size_x as f32,
-                size_y: draw.deg_size_y as f32,
-                pos_x: draw.deg_pos_x as f32,
-                pos_y: draw.deg_pos_y as f32,
+                size_x: draw.deg_size_x,
+                size_y: draw.deg_size_y,
+                pos_x: draw.deg_pos_x,
+                pos_y: draw.deg_pos_y,
                 aspects: aspects_null.clone(),
+                aspect_record: None,
             });
             res.push(DataObjectSvg {
                 svg: draw.min_svg,
                 object_type: DataObjectType::AngleMin,
-                size_x: draw.min_size_x as f32,
-                size_y: draw.min_size_y as f32,
-                pos_x: draw.min_pos_x as f32,
-                pos_y: draw.min_pos_y as f32,
+                size_x: draw.min_size_x,
+                size_y: draw.min_size_y,
+                pos_x: draw.min_pos_x,
+                pos_y: draw.min_pos_y,
                 aspects: aspects_null.clone(),
+                aspect_record: None,
             });
             res.push(DataObjectSvg {
                 svg: draw.trait_svg,
                 object_type: DataObjectType::AngleTrait,
-                size_x: draw.trait_size_x as f32,
-                size_y: draw.trait_size_y as f32,
-                pos_x: draw.trait_pos_x as f32,
-                pos_y: draw.trait_pos_y as f32,
+                size_x: draw.trait_size_x,
+                size_y: draw.trait_size_y,
+                pos_x: draw.trait_pos_x,
+                pos_y: draw.trait_pos_y,
                 aspects: aspects_null.clone(),
+                aspect_record: None,
             });
         }
     }
@@ -271,140 +354,186 @@ pub fn chart(
             res.push(DataObjectSvg {
                 svg: draw.svg,
                 object_type: DataObjectType::Planet,
-                size_x: draw.size_x as f32,
-                size_y: draw.size_y as f32,
-                pos_x: draw.pos_x as f32,
-                pos_y: draw.pos_y as f32,
+                size_x: draw.size_x,
+                size_y: draw.size_y,
+                pos_x: draw.pos_x,
+                pos_y: draw.pos_y,
                 aspects: aspects_null.clone(),
+                aspect_record: None,
             });
             res.push(DataObjectSvg {
                 svg: draw.deg_svg,
                 object_type: DataObjectType::PlanetDeg,
-                size_x: draw.deg_size_x as f32,
-                size_y: draw.deg_size_y as f32,
-                pos_x: draw.deg_pos_x as f32,
-                pos_y: draw.deg_pos_y as f32,
+                size_x: draw.deg_size_x,
+                size_y: draw.deg_size_y,
+                pos_x: draw.deg_pos_x,
+                pos_y: draw.deg_pos_y,
                 aspects: aspects_null.clone(),
+                aspect_record: None,
             });
             res.push(DataObjectSvg {
                 svg: draw.min_svg,
                 object_type: DataObjectType::PlanetMin,
-                size_x: draw.min_size_x as f32,
-                size_y: draw.min_size_y as f32,
-                pos_x: draw.min_pos_x as f32,
-                pos_y: draw.min_pos_y as f32,
+                size_x: draw.min_size_x,
+                size_y: draw.min_size_y,
+                pos_x: draw.min_pos_x,
+                pos_y: draw.min_pos_y,
                 aspects: aspects_null.clone(),
+                aspect_record: None,
             });
             res.push(DataObjectSvg {
                 svg: draw.trait_svg,
                 object_type: DataObjectType::PlanetTrait,
-                size_x: draw.trait_size_x as f32,
-                size_y: draw.trait_size_y as f32,
-                pos_x: draw.trait_pos_x as f32,
-                pos_y: draw.trait_pos_y as f32,
+                size_x: draw.trait_size_x,
+                size_y: draw.trait_size_y,
+                pos_x: draw.trait_pos_x,
+                pos_y: draw.trait_pos_y,
                 aspects: aspects_null.clone(),
+                aspect_record: None,
             });
         }
     }
     // Aspects
-    let mut asp_vec: Vec<Aspects> = Vec::new();
-    let mut asp: u16;
-    let mut orb: u16;
-    let mut abs_separation: Number;
-    let mut separation: Number;
-    let mut pair: Vec<(Bodies, Bodies)> = Vec::new();
-    for bodie in ws.object.clone() {
-        if ws.get_bodie_is_on_chart(bodie.object_enum) {
-            for b in ws.object.clone() {
-                let mut sw = false;
-                for p in pair.clone() {
-                    if (p.0 == bodie.object_enum && p.1 == b.object_enum)
-                        || (p.0 == b.object_enum && p.1 == bodie.object_enum)
-                    {
-                        sw = true;
-                        break;
-                    }
-                }
-                if ws.get_bodie_is_on_chart(b.object_enum)
-                    && !sw
-                    && bodie.object_enum != b.object_enum
-                {
-                    pair.push((bodie.object_enum, b.object_enum));
-                    separation = ws.get_closest_distance(
-                        ws.get_bodie_longitude(bodie.object_enum, false),
-                        ws.get_bodie_longitude(b.object_enum, false),
-                    );
-                    abs_separation = separation.abs();
-
-                    for record_asp in Aspects::iter() {
-                        asp = record_asp.angle().0;
-                        orb = record_asp.angle().1;
-                        if (abs_separation - asp as f32).abs() <= orb as f32 {
-                            asp_vec.push(record_asp.clone());
-                            let draw = ws_draw.draw_aspect(
-                                ws.get_bodie_longitude(
-                                    bodie.object_enum,
-                                    false,
-                                ),
-                                ws.get_bodie_longitude(b.object_enum, false),
-                                record_asp.clone(),
-                            );
-                            res.push(DataObjectSvg {
-                                svg: draw.svg,
-                                object_type: DataObjectType::Aspect,
-                                size_x: draw.size_x as f32,
-                                size_y: draw.size_y as f32,
-                                pos_x: draw.pos_x as f32,
-                                pos_y: draw.pos_y as f32,
-                                aspects: asp_vec.clone(),
-                            });
-                            asp_vec.clear();
-                        }
-                    }
-                }
-            }
-            for i in 0..12 {
-                if i == 0 || i == 9 {
-                    // Only Asc et Mc
-                    separation = ws.get_closest_distance(
-                        bodie.longitude as f32,
-                        ws.house.clone()[i].longitude as f32,
-                    );
-                    abs_separation = separation.abs();
-                    for record_asp in Aspects::iter() {
-                        asp = record_asp.angle().0;
-                        orb = record_asp.angle().1;
-                        if (abs_separation - asp as f32).abs() <= orb as f32 {
-                            asp_vec.push(record_asp.clone());
-                            let draw = ws_draw.draw_aspect(
-                                ws.get_bodie_longitude(
-                                    bodie.object_enum,
-                                    false,
-                                ),
-                                ws.get_angle_longitude(
-                                    ws.house.clone()[i].angle,
-                                ),
-                                record_asp.clone(),
-                            );
-                            res.push(DataObjectSvg {
-                                svg: draw.svg,
-                                object_type: DataObjectType::Aspect,
-                                size_x: draw.size_x as f32,
-                                size_y: draw.size_y as f32,
-                                pos_x: draw.pos_x as f32,
-                                pos_y: draw.pos_y as f32,
-                                aspects: asp_vec.clone(),
-                            });
-                            asp_vec.clear();
-                        }
-                    }
-                }
-            }
+    for record in chart_data.aspects.iter() {
+        let draw = ws_draw.draw_aspect(
+            point_longitude(&ws, record.body_a),
+            point_longitude(&ws, record.body_b),
+            record.aspect,
+        );
+        res.push(DataObjectSvg {
+            svg: draw.svg,
+            object_type: DataObjectType::Aspect,
+            size_x: draw.size_x as f32,
+            size_y: draw.size_y as f32,
+            pos_x: draw.pos_x as f32,
+            pos_y: draw.pos_y as f32,
+            aspects: vec![record.aspect],
+            aspect_record: Some(record.clone()),
+        });
+    }
+    // Lots
+    if options.lots.draw {
+        for lot in chart_data.lots.iter() {
+            let draw = ws_draw.draw_marker(lot.longitude, &lot.label());
+            res.push(DataObjectSvg {
+                svg: draw.svg,
+                object_type: DataObjectType::Lot,
+                size_x: draw.size_x,
+                size_y: draw.size_y,
+                pos_x: draw.pos_x,
+                pos_y: draw.pos_y,
+                aspects: aspects_null.clone(),
+                aspect_record: None,
+            });
         }
     }
+    // Fixed stars
+    if !stars.is_empty() {
+        let stars: Vec<(f64, &str)> = stars
+            .iter()
+            .map(|s| (s.longitude, s.name.as_str()))
+            .collect();
+        let draw = ws_draw.draw_stars(&stars);
+        res.push(DataObjectSvg {
+            svg: draw.svg,
+            object_type: DataObjectType::Star,
+            size_x: draw.size_x,
+            size_y: draw.size_y,
+            pos_x: draw.pos_x,
+            pos_y: draw.pos_y,
+            aspects: aspects_null.clone(),
+            aspect_record: None,
+        });
+    }
+    // Ayanamsa
+    if let Some(label) = chart_data.zodiac.label(chart_data.ayanamsa) {
+        res.push(label_object(
+            &label,
+            &options.style,
+            max_size,
+            LabelCorner::BottomLeft,
+        ));
+    }
+    // Sect and planetary hour
+    if options.sect_label {
+        let parts = sect_label(
+            chart_data.diurnal,
+            chart_data.planetary_hour.as_ref(),
+            lang,
+        );
+        res.push(svg_label_object(draw_sect_label(
+            &parts,
+            &options.style,
+            max_size,
+            LabelCorner::BottomRight,
+        )));
+    }
     res
 }
 
+/// Label in a corner of the chart
+fn label_object(
+    label: &str,
+    style: &ChartStyle,
+    max_size: Number,
+    corner: LabelCorner,
+) -> DataObjectSvg {
+    svg_label_object(draw_label(label, style, max_size, corner))
+}
+
+/// Label already drawn
+fn svg_label_object(draw: SvgObject) -> DataObjectSvg {
+    DataObjectSvg {
+        svg: draw.svg,
+        object_type: DataObjectType::Label,
+        size_x: draw.size_x,
+        size_y: draw.size_y,
+        pos_x: draw.pos_x,
+        pos_y: draw.pos_y,
+        aspects: Vec::new(),
+        aspect_record: None,
+    }
+}
+
+/// Longitude on the svg of a point of the natal chart
+fn point_longitude(
+    ws: &WorkingStoragePolyMorphNatal,
+    point: ChartPoint,
+) -> Number {
+    match point {
+        ChartPoint::Bodie(b) => ws.get_bodie_longitude(b, false),
+        ChartPoint::Angle(a) => ws.get_angle_longitude(a),
+    }
+}
+
+/// Longitude on the svg of a longitude of the zodiac (chart with two rings)
+fn svg_longitude(
+    ws: &WorkingStoragePolyMorphTransit,
+    longitude: f64,
+) -> Number {
+    ws.get_fix_pos(360.0 - ws.get_asc_longitude() + longitude as Number)
+}
+
+/// Longitude on the svg of a point of the natal chart (second None) or of
+/// the second chart in the ring of the transit
+/// The angles of the second chart are at their longitude (directed Asc/Mc)
+fn transit_point_longitude(
+    ws: &WorkingStoragePolyMorphTransit,
+    point: ChartPoint,
+    second: Option<&ChartData>,
+) -> Number {
+    match (point, second) {
+        (ChartPoint::Bodie(b), _) => {
+            ws.get_bodie_longitude(b, second.is_some())
+        },
+        (ChartPoint::Angle(a), Some(chart)) => match chart.angle_longitude(a) {
+            Some(longitude) => svg_longitude(ws, longitude),
+            None => ws.get_angle_longitude(a),
+        },
+        (ChartPoint::Angle(a), None) => ws.get_angle_longitude(a),
+    }
+}
+
 /// Create a chart for C export
 /// Without path like chart_html for now
 pub fn chart_with_transit(
@@ -413,158 +542,217 @@ pub fn chart_with_transit(
     data_transit: DataChartNatal,
     path: &str,
     lang: Language,
-) -> Vec<DataObjectSvg> {
-    // To do better
-    let theme: Theme = Theme::Light;
+    options: &ChartOptions,
+) -> Result<Vec<DataObjectSvg>, AstrologyError> {
     // Natal chart
-    println!("Version swephem: {}", swerust::handler_swe02::version());
     //let swe02_path: &str =
     //    "/Users/stephanebressani/Code/Rust/libswe-sys/src/swisseph/sweph/";
-    swerust::handler_swe02::set_ephe_path(&path);
-    println!("{}", data.year);
     //println!(
     //    "Library path (Todo): {}",
     //    swerust::handler_swe02::get_library_path()
     //);
-    // House natal chart
-    println!("Hsys: {}", swerust::handler_swe14::house_name('P')); // Placidus
-    let utc_time_zone: swerust::handler_swe08::UtcTimeZoneResult =
-        swerust::handler_swe08::utc_time_zone(
-            data.year,
-            data.month as i32,     //TODO
-            data.day as i32,       //TODO
-            data.hour as i32,      //TODO
-            data.min as i32,       //TODO
-            data.sec.into(),       //TODO need to change libswe_sys f64 -> f32
-            data.time_zone.into(), //TODO need to change f64 -> f32 without .into()
-        );
-    // println!("UtcTimeZone: {:?}", utc_time_zone);
-    let utc_to_jd: swerust::handler_swe08::UtcToJdResult =
-        swerust::handler_swe08::utc_to_jd(
-            utc_time_zone.year[0],
-            utc_time_zone.month[0],
-            utc_time_zone.day[0],
-            utc_time_zone.hour[0],
-            utc_time_zone.min[0],
-            utc_time_zone.sec[0],
-            Calandar::Gregorian,
-        );
-    // println!("GregorianTimeZone: {:?}", utc_to_jd);
-    let utc_time_zone_transit: swerust::handler_swe08::UtcTimeZoneResult =
-        swerust::handler_swe08::utc_time_zone(
-            data_transit.year,
-            data_transit.month as i32, //TODO
-            data_transit.day as i32,   //TODO
-            data_transit.hour as i32,  //TODO
-            data_transit.min as i32,   //TODO
-            data_transit.sec.into(), //TODO need to change libswe_sys f64 -> f32
-            data.time_zone.into(), //TODO need to change f64 -> f32 without .into()
-        );
-    // println!("UtcTimeZone transit: {:?}", utc_time_zone_transit);
-    let utc_to_jd_transit: swerust::handler_swe08::UtcToJdResult =
-        swerust::handler_swe08::utc_to_jd(
-            utc_time_zone_transit.year[0],
-            utc_time_zone_transit.month[0],
-            utc_time_zone_transit.day[0],
-            utc_time_zone_transit.hour[0],
-            utc_time_zone_transit.min[0],
-            utc_time_zone_transit.sec[0],
-            Calandar::Gregorian,
-        );
-    // println!("GregorianTimeZonei transit: {:?}", utc_to_jd_transit);
-    let house_result = swerust::handler_swe14::houses(
-        utc_to_jd.julian_day_ut,
-        data.lat as f64, // Todo in libswe_sys f64 -> f32
-        data.lng as f64, // Todo in libswe_sys f64 -> f32
-        'P',             // Placidus
-    );
+    let transit_data = compute_transit(data, data_transit, path, options)?;
+    let mut markers: Vec<(f64, &str)> = Vec::new();
+    if let Some(syzygy) = &transit_data.prenatal_syzygy {
+        markers.push((
+            syzygy.longitude(),
+            match lang {
+                Language::French => "Syzygie",
+                _ => "Syz",
+            },
+        ));
+    }
+    if let Some(eclipse) = &transit_data.last_eclipse {
+        markers.push((
+            eclipse.longitude(),
+            match lang {
+                Language::French => "Écl",
+                _ => "Ecl",
+            },
+        ));
+    }
+    Ok(chart_natal_second(
+        max_size,
+        &transit_data.natal,
+        &transit_data.transit,
+        &transit_data.aspects,
+        &markers,
+        lang,
+        options,
+    ))
+}
 
-    let mut object: Vec<Object> = Vec::new();
-    let mut object_transit: Vec<Object> = Vec::new();
-    let mut calc: swerust::handler_swe03::CalcUtResult;
-    for bodie in Bodies::iter() {
-        if bodie.clone().object_type() == ObjectType::PlanetOrStar
-            || bodie.clone().object_type() == ObjectType::Fiction
-        {
-            // Natal
-            calc = if bodie.clone() == Bodies::FortunaPart {
-                swerust::handler_swe03::calc_ut_fp(
-                    utc_to_jd.julian_day_ut,
-                    data.lat as f64,
-                    data.lng as f64,
-                    'P',
-                    OptionalFlag::Speed as i32,
-                )
-            } else {
-                swerust::handler_swe03::calc_ut(
-                    utc_to_jd.julian_day_ut, // debug julianday in orginal file
-                    bodie.clone(),
-                    OptionalFlag::Speed as i32,
-                )
-            };
-            object.push(Object::new(
-                bodie.clone(),
-                bodie.clone().as_static(),
-                bodie.clone().object_type(),
-                calc.longitude,
-                calc.latitude,
-                calc.speed_longitude,
-            ));
+/// Create a progressed chart (secondary progressions for the date of target),
+/// the progressed bodies in the ring of the transit
+pub fn chart_progressed(
+    max_size: Number,
+    data: DataChartNatal,
+    target: DataChartNatal,
+    path: &str,
+    lang: Language,
+    options: &ChartOptions,
+) -> Result<Vec<DataObjectSvg>, AstrologyError> {
+    let progressed_data = compute_progressed(data, target, path, options)?;
+    Ok(chart_natal_second(
+        max_size,
+        &progressed_data.natal,
+        &progressed_data.progressed,
+        &progressed_data.aspects,
+        &[],
+        lang,
+        options,
+    ))
+}
 
-            // Transit
-            calc = if bodie.clone() == Bodies::FortunaPart {
-                swerust::handler_swe03::calc_ut_fp(
-                    utc_to_jd_transit.julian_day_ut,
-                    data_transit.lat as f64,
-                    data_transit.lng as f64,
-                    'P',
-                    OptionalFlag::Speed as i32,
-                )
-            } else {
-                swerust::handler_swe03::calc_ut(
-                    utc_to_jd_transit.julian_day_ut, // debug julianday
-                    // in orginal file
-                    bodie.clone(),
-                    OptionalFlag::Speed as i32,
-                )
-            };
-            object_transit.push(Object::new(
-                bodie.clone(),
-                bodie.clone().as_static(),
-                bodie.clone().object_type(),
-                calc.longitude,
-                calc.latitude,
-                calc.speed_longitude,
-            ));
-        }
-    }
+/// Create a solar arc directed chart for the date of target, the directed
+/// points in the ring of the transit
+pub fn chart_directed(
+    max_size: Number,
+    data: DataChartNatal,
+    target: DataChartNatal,
+    path: &str,
+    lang: Language,
+    options: &ChartOptions,
+) -> Result<Vec<DataObjectSvg>, AstrologyError> {
+    let directed_data = compute_directed(data, target, path, options)?;
+    let aspects: Vec<AspectRecord> = directed_data
+        .aspects
+        .iter()
+        .map(|a| a.record.clone())
+        .collect();
+    Ok(chart_natal_second(
+        max_size,
+        &directed_data.natal,
+        &directed_data.directed,
+        &aspects,
+        &[],
+        lang,
+        options,
+    ))
+}
 
+/// Objects of a natal chart with a second chart in the ring of the transit
+/// (transit, progressions), aspects are the aspects of the second chart to
+/// the natal chart (body_a natal), markers are (longitude, text)
+fn chart_natal_second(
+    max_size: Number,
+    chart_data: &ChartData,
+    second: &ChartData,
+    second_aspects: &[AspectRecord],
+    markers: &[(f64, &str)],
+    lang: Language,
+    options: &ChartOptions,
+) -> Vec<DataObjectSvg> {
     // Object calc draw for calcul in svg x,y width, height
     let mut ws = WorkingStoragePolyMorphTransit::new(
         max_size,
-        theme,
+        options.style.clone(),
         lang,
-        house_result,
-        object,
-        object_transit,
+        chart_data.houses(),
+        chart_data.angles_house(),
+        chart_data.objects(),
+        second.objects(),
     );
+    if options.transit_houses {
+        ws.set_transit_houses(second.houses(), second.angles_house());
+    }
     ws.set_fix_compute(true);
     ws.set_fix_compute(false);
+    let mut aspects: Vec<(Number, Number, AspectRecord)> = Vec::new();
+    // Natal
+    for record in chart_data.aspects.iter() {
+        aspects.push((
+            transit_point_longitude(&ws, record.body_a, None),
+            transit_point_longitude(&ws, record.body_b, None),
+            record.clone(),
+        ));
+    }
+    // Transit
+    for record in second_aspects.iter() {
+        aspects.push((
+            transit_point_longitude(&ws, record.body_a, None),
+            transit_point_longitude(&ws, record.body_b, Some(second)),
+            record.clone(),
+        ));
+    }
+    chart_two_rings(&ws, chart_data, aspects, markers, options)
+}
+
+/// Create a synastry chart, the bodies of person A in the ring of the natal
+/// chart and the bodies of person B in the ring of the transit
+/// The aspects are the aspects between the two charts, the labels of the
+/// persons are options.label_a and options.label_b
+pub fn chart_synastry(
+    max_size: Number,
+    data_a: DataChartNatal,
+    data_b: DataChartNatal,
+    path: &str,
+    lang: Language,
+    options: &ChartOptions,
+) -> Result<Vec<DataObjectSvg>, AstrologyError> {
+    let synastry_data = compute_synastry(data_a, data_b, path, options)?;
+    let chart_a = &synastry_data.chart_a;
+    let chart_b = &synastry_data.chart_b;
+
+    let mut ws = WorkingStoragePolyMorphTransit::new(
+        max_size,
+        options.style.clone(),
+        lang,
+        chart_a.houses(),
+        chart_a.angles_house(),
+        chart_a.objects(),
+        chart_b.objects(),
+    );
+    if options.transit_houses {
+        ws.set_transit_houses(chart_b.houses(), chart_b.angles_house());
+    }
+    ws.set_fix_compute(true);
+    ws.set_fix_compute(false);
+
+    let mut aspects: Vec<(Number, Number, AspectRecord)> = Vec::new();
+    for record in synastry_data.aspects.iter() {
+        if let (Some(lng_a), Some(lng_b)) = (
+            chart_a.point_longitude(record.body_a),
+            chart_b.point_longitude(record.body_b),
+        ) {
+            aspects.push((
+                svg_longitude(&ws, lng_a),
+                svg_longitude(&ws, lng_b),
+                record.clone(),
+            ));
+        }
+    }
+    Ok(chart_two_rings(&ws, chart_a, aspects, &[], options))
+}
+
+/// Objects of a chart with two rings of bodies (transit, synastry)
+/// The aspects are drawn between the two longitudes on the svg, the markers
+/// (longitude, text) on the circle of the aspects, natal is the chart of the
+/// inner ring
+fn chart_two_rings(
+    ws: &WorkingStoragePolyMorphTransit,
+    natal: &ChartData,
+    aspects: Vec<(Number, Number, AspectRecord)>,
+    markers: &[(f64, &str)],
+    options: &ChartOptions,
+) -> Vec<DataObjectSvg> {
+    let max_size = ws.max_size;
     let ws_draw = WorkingStorageDrawPolyMorphTransit::new(ws.clone());
 
     let mut res: Vec<DataObjectSvg> = Vec::new();
 
-    let aspects_null: Vec<Aspects> = Vec::new();
+    let aspects_null: Vec<ChartAspect> = Vec::new();
 
     // Chart
     res.push(DataObjectSvg {
         svg: ws_draw.draw_base().to_string(),
         object_type: DataObjectType::Chart,
-        size_x: max_size as f32,
-        size_y: max_size as f32,
+        size_x: max_size,
+        size_y: max_size,
         pos_x: 0.0,
         pos_y: 0.0,
         aspects: aspects_null.clone(),
+        aspect_record: None,
     });
 
     // Zodiac
@@ -573,11 +761,12 @@ pub fn chart_with_transit(
         res.push(DataObjectSvg {
             svg: draw.svg,
             object_type: DataObjectType::Zodiac,
-            size_x: draw.size_x as f32,
-            size_y: draw.size_y as f32,
-            pos_x: draw.pos_x as f32,
-            pos_y: draw.pos_y as f32,
+            size_x: draw.size_x,
+            size_y: draw.size_y,
+            pos_x: draw.pos_x,
+            pos_y: draw.pos_y,
             aspects: aspects_null.clone(),
+            aspect_record: None,
         });
     }
 
@@ -586,11 +775,12 @@ pub fn chart_with_transit(
         res.push(DataObjectSvg {
             svg: draw.svg,
             object_type: DataObjectType::House,
-            size_x: draw.size_x as f32,
-            size_y: draw.size_y as f32,
-            pos_x: draw.pos_x as f32,
-            pos_y: draw.pos_y as f32,
+            size_x: draw.size_x,
+            size_y: draw.size_y,
+            pos_x: draw.pos_x,
+            pos_y: draw.pos_y,
             aspects: aspects_null.clone(),
+            aspect_record: None,
         });
     }
 
@@ -600,11 +790,12 @@ pub fn chart_with_transit(
             res.push(DataObjectSvg {
                 svg: draw.svg,
                 object_type: DataObjectType::Angle,
-                size_x: draw.size_x as f32,
-                size_y: draw.size_y as f32,
-                pos_x: draw.pos_x as f32,
-                pos_y: draw.pos_y as f32,
+                size_x: draw.size_x,
+                size_y: draw.size_y,
+                pos_x: draw.pos_x,
+                pos_y: draw.pos_y,
                 aspects: aspects_null.clone(),
+                aspect_record: None,
             });
             /*
             res.push(DataObjectSvg {
@@ -615,6 +806,7 @@ pub fn chart_with_transit(
                 pos_x: draw.deg_pos_x as f32,
                 pos_y: draw.deg_pos_y as f32,
                 aspects: aspects_null.clone(),
+                aspect_record: None,
             });
             res.push(DataObjectSvg {
                 svg: draw.min_svg,
@@ -624,30 +816,33 @@ pub fn chart_with_transit(
                 pos_x: draw.min_pos_x as f32,
                 pos_y: draw.min_pos_y as f32,
                 aspects: aspects_null.clone(),
+                aspect_record: None,
             });*/
             res.push(DataObjectSvg {
                 svg: draw.trait_svg,
                 object_type: DataObjectType::AngleTrait,
-                size_x: draw.trait_size_x as f32,
-                size_y: draw.trait_size_y as f32,
-                pos_x: draw.trait_pos_x as f32,
-                pos_y: draw.trait_pos_y as f32,
+                size_x: draw.trait_size_x,
+                size_y: draw.trait_size_y,
+                pos_x: draw.trait_pos_x,
+                pos_y: draw.trait_pos_y,
                 aspects: aspects_null.clone(),
+                aspect_record: None,
             });
         }
     }
     for b in Bodies::iter() {
-        if ws.get_bodie_is_on_chart(b.clone()) {
+        if ws.get_bodie_is_on_ring(b, false) {
             // Natal
             let draw = ws_draw.draw_bodie(b, false);
             res.push(DataObjectSvg {
                 svg: draw.svg,
                 object_type: DataObjectType::Planet,
-                size_x: draw.size_x as f32,
-                size_y: draw.size_y as f32,
-                pos_x: draw.pos_x as f32,
-                pos_y: draw.pos_y as f32,
+                size_x: draw.size_x,
+                size_y: draw.size_y,
+                pos_x: draw.pos_x,
+                pos_y: draw.pos_y,
                 aspects: aspects_null.clone(),
+                aspect_record: None,
             });
             /*
             res.push(DataObjectSvg {
@@ -658,6 +853,7 @@ pub fn chart_with_transit(
                 pos_x: draw.deg_pos_x as f32,
                 pos_y: draw.deg_pos_y as f32,
                 aspects: aspects_null.clone(),
+                aspect_record: None,
             });
             res.push(DataObjectSvg {
                 svg: draw.min_svg,
@@ -667,27 +863,31 @@ pub fn chart_with_transit(
                 pos_x: draw.min_pos_x as f32,
                 pos_y: draw.min_pos_y as f32,
                 aspects: aspects_null.clone(),
+                aspect_record: None,
             });*/
             res.push(DataObjectSvg {
                 svg: draw.trait_svg,
                 object_type: DataObjectType::PlanetTrait,
-                size_x: draw.trait_size_x as f32,
-                size_y: draw.trait_size_y as f32,
-                pos_x: draw.trait_pos_x as f32,
-                pos_y: draw.trait_pos_y as f32,
+                size_x: draw.trait_size_x,
+                size_y: draw.trait_size_y,
+                pos_x: draw.trait_pos_x,
+                pos_y: draw.trait_pos_y,
                 aspects: aspects_null.clone(),
+                aspect_record: None,
             });
-
+        }
+        if ws.get_bodie_is_on_ring(b, true) {
             // Transit
             let draw = ws_draw.draw_bodie(b, true);
             res.push(DataObjectSvg {
                 svg: draw.svg,
                 object_type: DataObjectType::Planet,
-                size_x: draw.size_x as f32,
-                size_y: draw.size_y as f32,
-                pos_x: draw.pos_x as f32,
-                pos_y: draw.pos_y as f32,
+                size_x: draw.size_x,
+                size_y: draw.size_y,
+                pos_x: draw.pos_x,
+                pos_y: draw.pos_y,
                 aspects: aspects_null.clone(),
+                aspect_record: None,
             });
             /*
             res.push(DataObjectSvg {
@@ -698,6 +898,7 @@ pub fn chart_with_transit(
                 pos_x: draw.deg_pos_x as f32,
                 pos_y: draw.deg_pos_y as f32,
                 aspects: aspects_null.clone(),
+                aspect_record: None,
             });
             res.push(DataObjectSvg {
                 svg: draw.min_svg,
@@ -707,189 +908,68 @@ pub fn chart_with_transit(
                 pos_x: draw.min_pos_x as f32,
                 pos_y: draw.min_pos_y as f32,
                 aspects: aspects_null.clone(),
+                aspect_record: None,
             });*/
             res.push(DataObjectSvg {
                 svg: draw.trait_svg,
                 object_type: DataObjectType::PlanetTrait,
-                size_x: draw.trait_size_x as f32,
-                size_y: draw.trait_size_y as f32,
-                pos_x: draw.trait_pos_x as f32,
-                pos_y: draw.trait_pos_y as f32,
+                size_x: draw.trait_size_x,
+                size_y: draw.trait_size_y,
+                pos_x: draw.trait_pos_x,
+                pos_y: draw.trait_pos_y,
                 aspects: aspects_null.clone(),
+                aspect_record: None,
             });
         }
     }
     // Aspects
-    let mut asp_vec: Vec<Aspects> = Vec::new();
-    let mut asp: u16;
-    let mut orb: u16;
-    let mut abs_separation: Number;
-    let mut separation: Number;
-    let mut pair: Vec<(Bodies, Bodies)> = Vec::new();
-    for bodie in ws.object_natal.clone() {
-        if ws.get_bodie_is_on_chart(bodie.object_enum) {
-            // Transit
-            for bt in ws.object_transit.clone() {
-                if ws.get_bodie_is_on_chart(bt.object_enum) {
-                    separation = ws.get_closest_distance(
-                        ws.get_bodie_longitude(bodie.object_enum, false), // no transit
-                        ws.get_bodie_longitude(bt.object_enum, true), // transit
-                    );
-                    abs_separation = separation.abs();
-                    for record_asp in Aspects::iter() {
-                        asp = record_asp.angle().0;
-                        orb = record_asp.angle().1;
-                        if (abs_separation - asp as f32).abs() <= orb as f32 {
-                            asp_vec.push(record_asp.clone());
-                            let draw = ws_draw.draw_aspect(
-                                ws.get_bodie_longitude(
-                                    bodie.object_enum,
-                                    false, // no transit
-                                ),
-                                ws.get_bodie_longitude(bt.object_enum, true), // transit
-                                record_asp.clone(),
-                            );
-                            res.push(DataObjectSvg {
-                                svg: draw.svg,
-                                object_type: DataObjectType::Aspect,
-                                size_x: draw.size_x as f32,
-                                size_y: draw.size_y as f32,
-                                pos_x: draw.pos_x as f32,
-                                pos_y: draw.pos_y as f32,
-                                aspects: asp_vec.clone(),
-                            });
-                            asp_vec.clear();
-                        }
-                    }
-                }
-            }
-            // Regular
-            for b in ws.object_natal.clone() {
-                let mut sw = false;
-                for p in pair.clone() {
-                    if (p.0 == bodie.object_enum && p.1 == b.object_enum)
-                        || (p.0 == b.object_enum && p.1 == bodie.object_enum)
-                    {
-                        sw = true;
-                        break;
-                    }
-                }
-                if ws.get_bodie_is_on_chart(b.object_enum)
-                    && !sw
-                    && bodie.object_enum != b.object_enum
-                {
-                    pair.push((bodie.object_enum, b.object_enum));
-                    separation = ws.get_closest_distance(
-                        ws.get_bodie_longitude(bodie.object_enum, false),
-                        ws.get_bodie_longitude(b.object_enum, false),
-                    );
-                    abs_separation = separation.abs();
-
-                    for record_asp in Aspects::iter() {
-                        asp = record_asp.angle().0;
-                        orb = record_asp.angle().1;
-                        if (abs_separation - asp as f32).abs() <= orb as f32 {
-                            asp_vec.push(record_asp.clone());
-                            let draw = ws_draw.draw_aspect(
-                                ws.get_bodie_longitude(
-                                    bodie.object_enum,
-                                    false, // no transit
-                                ),
-                                ws.get_bodie_longitude(b.object_enum, false),
-                                record_asp.clone(),
-                            );
-                            res.push(DataObjectSvg {
-                                svg: draw.svg,
-                                object_type: DataObjectType::Aspect,
-                                size_x: draw.size_x as f32,
-                                size_y: draw.size_y as f32,
-                                pos_x: draw.pos_x as f32,
-                                pos_y: draw.pos_y as f32,
-                                aspects: asp_vec.clone(),
-                            });
-                            asp_vec.clear();
-                        }
-                    }
-                }
-            }
-            // Transit
-            for i in 0..12 {
-                if i == 0 || i == 9 {
-                    // Only Asc et Mc
-                    separation = ws.get_closest_distance(
-                        bodie.longitude as f32,
-                        ws.house.clone()[i].longitude as f32,
-                    );
-                    abs_separation = separation.abs();
-                    for record_asp in Aspects::iter() {
-                        asp = record_asp.angle().0;
-                        orb = record_asp.angle().1;
-                        if (abs_separation - asp as f32).abs() <= orb as f32 {
-                            asp_vec.push(record_asp.clone());
-                            let draw = ws_draw.draw_aspect(
-                                ws.get_bodie_longitude(
-                                    bodie.object_enum,
-                                    true, // true = transit
-                                ),
-                                ws.get_angle_longitude(
-                                    ws.house.clone()[i].angle,
-                                ),
-                                record_asp.clone(),
-                            );
-                            res.push(DataObjectSvg {
-                                svg: draw.svg,
-                                object_type: DataObjectType::Aspect,
-                                size_x: draw.size_x as f32,
-                                size_y: draw.size_y as f32,
-                                pos_x: draw.pos_x as f32,
-                                pos_y: draw.pos_y as f32,
-                                aspects: asp_vec.clone(),
-                            });
-                            asp_vec.clear();
-                        }
-                    }
-                }
-            }
-            // Regular
-            for i in 0..12 {
-                if i == 0 || i == 9 {
-                    // Only Asc et Mc
-                    separation = ws.get_closest_distance(
-                        bodie.longitude as f32,
-                        ws.house.clone()[i].longitude as f32,
-                    );
-                    abs_separation = separation.abs();
-                    for record_asp in Aspects::iter() {
-                        asp = record_asp.angle().0;
-                        orb = record_asp.angle().1;
-                        if (abs_separation - asp as f32).abs() <= orb as f32 {
-                            asp_vec.push(record_asp.clone());
-                            let draw = ws_draw.draw_aspect(
-                                ws.get_bodie_longitude(
-                                    bodie.object_enum,
-                                    false, // no transit
-                                ),
-                                ws.get_angle_longitude(
-                                    ws.house.clone()[i].angle,
-                                ),
-                                record_asp.clone(),
-                            );
-                            res.push(DataObjectSvg {
-                                svg: draw.svg,
-                                object_type: DataObjectType::Aspect,
-                                size_x: draw.size_x as f32,
-                                size_y: draw.size_y as f32,
-                                pos_x: draw.pos_x as f32,
-                                pos_y: draw.pos_y as f32,
-                                aspects: asp_vec.clone(),
-                            });
-                            asp_vec.clear();
-                        }
-                    }
-                }
-            }
+    for (lng_a, lng_b, record) in aspects {
+        let draw = ws_draw.draw_aspect(lng_a, lng_b, record.aspect);
+        res.push(DataObjectSvg {
+            svg: draw.svg,
+            object_type: DataObjectType::Aspect,
+            size_x: draw.size_x,
+            size_y: draw.size_y,
+            pos_x: draw.pos_x,
+            pos_y: draw.pos_y,
+            aspects: vec![record.aspect],
+            aspect_record: Some(record),
+        });
+    }
+    // Markers
+    for (longitude, text) in markers.iter() {
+        let draw = ws_draw.draw_marker(*longitude, text);
+        res.push(DataObjectSvg {
+            svg: draw.svg,
+            object_type: DataObjectType::Marker,
+            size_x: draw.size_x,
+            size_y: draw.size_y,
+            pos_x: draw.pos_x,
+            pos_y: draw.pos_y,
+            aspects: aspects_null.clone(),
+            aspect_record: None,
+        });
+    }
+    // Labels
+    for (label, corner) in [
+        (&options.label_a, LabelCorner::TopLeft),
+        (&options.label_b, LabelCorner::TopRight),
+    ]
+    .iter()
+    {
+        if !label.is_empty() {
+            res.push(label_object(label, &ws.style, max_size, *corner));
         }
     }
+    // Ayanamsa
+    if let Some(label) = natal.zodiac.label(natal.ayanamsa) {
+        res.push(label_object(
+            &label,
+            &ws.style,
+            max_size,
+            LabelCorner::BottomLeft,
+        ));
+    }
     res
 }
 
@@ -900,8 +980,87 @@ pub fn chart_svg(
     path: &str,
     lang: Language,
     aspects: AspectsFilter,
+    options: &ChartOptions,
+) -> Result<String, AstrologyError> {
+    Ok(parse_svg(
+        chart(max_size, data, path, lang, options)?,
+        aspects,
+    ))
+}
+
+/// Svg progressed chart
+pub fn chart_svg_progressed(
+    max_size: Number,
+    data: DataChartNatal,
+    target: DataChartNatal,
+    path: &str,
+    lang: Language,
+    aspects: AspectsFilter,
+    options: &ChartOptions,
+) -> Result<String, AstrologyError> {
+    Ok(parse_svg(
+        chart_progressed(max_size, data, target, path, lang, options)?,
+        aspects,
+    ))
+}
+
+/// Svg solar arc directed chart
+pub fn chart_svg_directed(
+    max_size: Number,
+    data: DataChartNatal,
+    target: DataChartNatal,
+    path: &str,
+    lang: Language,
+    aspects: AspectsFilter,
+    options: &ChartOptions,
+) -> Result<String, AstrologyError> {
+    Ok(parse_svg(
+        chart_directed(max_size, data, target, path, lang, options)?,
+        aspects,
+    ))
+}
+
+/// Svg composite chart
+pub fn chart_svg_composite(
+    max_size: Number,
+    data_a: DataChartNatal,
+    data_b: DataChartNatal,
+    path: &str,
+    lang: Language,
+    aspects: AspectsFilter,
+    options: &ChartOptions,
+) -> Result<String, AstrologyError> {
+    Ok(parse_svg(
+        chart_composite(max_size, data_a, data_b, path, lang, options)?,
+        aspects,
+    ))
+}
+
+/// Svg Davison chart
+pub fn chart_svg_davison(
+    max_size: Number,
+    data_a: DataChartNatal,
+    data_b: DataChartNatal,
+    path: &str,
+    lang: Language,
+    aspects: AspectsFilter,
+    options: &ChartOptions,
+) -> Result<String, AstrologyError> {
+    Ok(parse_svg(
+        chart_davison(max_size, data_a, data_b, path, lang, options)?,
+        aspects,
+    ))
+}
+
+/// Svg return chart
+pub fn chart_svg_return(
+    max_size: Number,
+    return_data: &ReturnData,
+    lang: Language,
+    aspects: AspectsFilter,
+    options: &ChartOptions,
 ) -> String {
-    parse_svg(chart(max_size, data, path, lang.clone()), aspects)
+    parse_svg(chart_return(max_size, return_data, lang, options), aspects)
 }
 
 /// Svg transit chart
@@ -912,15 +1071,73 @@ pub fn chart_svg_with_transit(
     path: &str,
     lang: Language,
     aspects: AspectsFilter,
-) -> String {
-    parse_svg(
-        chart_with_transit(max_size, data_n, data_t, path, lang),
+    options: &ChartOptions,
+) -> Result<String, AstrologyError> {
+    Ok(parse_svg(
+        chart_with_transit(max_size, data_n, data_t, path, lang, options)?,
         aspects,
+    ))
+}
+
+/// Svg synastry chart
+pub fn chart_svg_synastry(
+    max_size: Number,
+    data_a: DataChartNatal,
+    data_b: DataChartNatal,
+    path: &str,
+    lang: Language,
+    aspects: AspectsFilter,
+    options: &ChartOptions,
+) -> Result<String, AstrologyError> {
+    Ok(parse_svg(
+        chart_synastry(max_size, data_a, data_b, path, lang, options)?,
+        aspects,
+    ))
+}
+
+/// Svg aspectarian of a natal chart
+pub fn aspectarian_svg(
+    max_size: Number,
+    data: DataChartNatal,
+    path: &str,
+    lang: Language,
+    options: &ChartOptions,
+) -> Result<String, AstrologyError> {
+    let chart_data = compute_natal(data, path, options)?;
+    Ok(aspectarian(&chart_data, max_size, &options.style, lang).to_string())
+}
+
+/// Svg aspectarian natal x transit
+pub fn aspectarian_svg_with_transit(
+    max_size: Number,
+    data_n: DataChartNatal,
+    data_t: DataChartNatal,
+    path: &str,
+    lang: Language,
+    options: &ChartOptions,
+) -> Result<String, AstrologyError> {
+    let transit_data = compute_transit(data_n, data_t, path, options)?;
+    Ok(aspectarian_transit(
+        &transit_data.natal,
+        &transit_data.transit.bodies,
+        &transit_data.aspects,
+        max_size,
+        &options.style,
+        lang,
     )
+    .to_string())
 }
 
 /// DataObjectAspectSvg of Aspects with svg + text
 pub fn all_aspects(lang: Language) -> Vec<DataObjectAspectSvg> {
+    all_aspects_style(lang, &ChartStyle::default())
+}
+
+/// DataObjectAspectSvg of Aspects with svg + text in the colors of style
+pub fn all_aspects_style(
+    lang: Language,
+    style: &ChartStyle,
+) -> Vec<DataObjectAspectSvg> {
     let mut res: Vec<DataObjectAspectSvg> = Vec::new();
     // No aspect
     let va_no_aspect: Vec<Aspects> = Vec::new();
@@ -931,7 +1148,7 @@ pub fn all_aspects(lang: Language) -> Vec<DataObjectAspectSvg> {
         Language::French => "Pas d'aspect".to_string(),
     };
     res.push(DataObjectAspectSvg {
-        svg: aspects_no_aspect(Theme::Light, lang).to_string(),
+        svg: aspects_no_aspect(style, lang).to_string(),
         text: t,
         aspects: va_no_aspect,
     });
@@ -949,7 +1166,7 @@ pub fn all_aspects(lang: Language) -> Vec<DataObjectAspectSvg> {
         Language::French => "Aspects majeurs".to_string(),
     };
     res.push(DataObjectAspectSvg {
-        svg: aspects_maj_aspects(Theme::Light, lang).to_string(),
+        svg: aspects_maj_aspects(style, lang).to_string(),
         text: t,
         aspects: va_maj_aspects,
     });
@@ -960,7 +1177,7 @@ pub fn all_aspects(lang: Language) -> Vec<DataObjectAspectSvg> {
             let mut va: Vec<Aspects> = Vec::new();
             va.push(a as Aspects);
             res.push(DataObjectAspectSvg {
-                svg: aspects_draw(a, Theme::Light, lang).to_string(),
+                svg: aspects_draw(a, style, lang).to_string(),
                 text: a.text(lang),
                 aspects: va.clone(),
             });
@@ -981,7 +1198,7 @@ pub fn all_aspects(lang: Language) -> Vec<DataObjectAspectSvg> {
         Language::French => "Aspects mineurs".to_string(),
     };
     res.push(DataObjectAspectSvg {
-        svg: aspects_min_aspects(Theme::Light, lang).to_string(),
+        svg: aspects_min_aspects(style, lang).to_string(),
         text: t,
         aspects: va_min_aspects,
     });
@@ -992,7 +1209,7 @@ pub fn all_aspects(lang: Language) -> Vec<DataObjectAspectSvg> {
             let mut va: Vec<Aspects> = Vec::new();
             va.push(a as Aspects);
             res.push(DataObjectAspectSvg {
-                svg: aspects_draw(a, Theme::Light, lang).to_string(),
+                svg: aspects_draw(a, style, lang).to_string(),
                 text: a.text(lang),
                 aspects: va.clone(),
             });
@@ -1011,7 +1228,7 @@ pub fn all_aspects(lang: Language) -> Vec<DataObjectAspectSvg> {
         Language::French => "Tous les aspects".to_string(),
     };
     res.push(DataObjectAspectSvg {
-        svg: aspects_all_aspects(Theme::Light, lang).to_string(),
+        svg: aspects_all_aspects(style, lang).to_string(),
         text: t,
         aspects: va_all_aspects,
     });
@@ -1034,7 +1251,7 @@ fn parse_svg(data: Vec<DataObjectSvg>, aspects: AspectsFilter) -> String {
                 if d.object_type == DataObjectType::Aspect {
                     let mut sw_res = false;
                     for a in d.aspects {
-                        for aa in &aspects.vec_aspects() {
+                        for aa in &filter_aspects(aspects) {
                             if a == *aa {
                                 sw_res = true;
                             }
@@ -0,0 +1,412 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+//! Essential dignities of the classical planets: domicile, exaltation,
+//! triplicity (Dorothean), Egyptian terms, Chaldean faces, detriment and fall,
+//! points of William Lilly and mutual receptions
+use super::chart_data::{compute_natal, ChartData};
+use super::compute_chart::{ChartOptions, DataChartNatal};
+use super::serde_sweconst;
+use crate::error::AstrologyError;
+use libswe_sys::sweconst::{Bodies, Signs};
+use libswe_sys::swerust::handler_swe17;
+use serde::{Deserialize, Serialize};
+
+/// Planets of the traditional astrology: the two luminaries, then the five
+/// planets in the order of their spheres (Mercury to Saturn, from the fastest
+/// to the slowest)
+pub const CLASSICAL_PLANETS: [Bodies; 7] = [
+    Bodies::Sun,
+    Bodies::Moon,
+    Bodies::Mercury,
+    Bodies::Venus,
+    Bodies::Mars,
+    Bodies::Jupiter,
+    Bodies::Saturn,
+];
+
+// Ruler of the domicile of each sign (Aries to Pisces)
+const DOMICILE: [Bodies; 12] = [
+    Bodies::Mars,
+    Bodies::Venus,
+    Bodies::Mercury,
+    Bodies::Moon,
+    Bodies::Sun,
+    Bodies::Mercury,
+    Bodies::Venus,
+    Bodies::Mars,
+    Bodies::Jupiter,
+    Bodies::Saturn,
+    Bodies::Saturn,
+    Bodies::Jupiter,
+];
+
+// Sign of the exaltation of each planet (0 = Aries)
+const EXALTATION: [(Bodies, usize); 7] = [
+    (Bodies::Sun, 0),
+    (Bodies::Moon, 1),
+    (Bodies::Mercury, 5),
+    (Bodies::Venus, 11),
+    (Bodies::Mars, 9),
+    (Bodies::Jupiter, 3),
+    (Bodies::Saturn, 6),
+];
+
+// Triplicity rulers of Dorotheus by element (fire, earth, air, water): day,
+// night and participating ruler
+const TRIPLICITY: [[Bodies; 3]; 4] = [
+    [Bodies::Sun, Bodies::Jupiter, Bodies::Saturn],
+    [Bodies::Venus, Bodies::Moon, Bodies::Mars],
+    [Bodies::Saturn, Bodies::Mercury, Bodies::Jupiter],
+    [Bodies::Venus, Bodies::Mars, Bodies::Moon],
+];
+
+// Egyptian terms of each sign: ruler and end of the term in degree
+const TERMS: [[(Bodies, f64); 5]; 12] = [
+    [
+        (Bodies::Jupiter, 6.0),
+        (Bodies::Venus, 12.0),
+        (Bodies::Mercury, 20.0),
+        (Bodies::Mars, 25.0),
+        (Bodies::Saturn, 30.0),
+    ],
+    [
+        (Bodies::Venus, 8.0),
+        (Bodies::Mercury, 14.0),
+        (Bodies::Jupiter, 22.0),
+        (Bodies::Saturn, 27.0),
+        (Bodies::Mars, 30.0),
+    ],
+    [
+        (Bodies::Mercury, 6.0),
+        (Bodies::Jupiter, 12.0),
+        (Bodies::Venus, 17.0),
+        (Bodies::Mars, 24.0),
+        (Bodies::Saturn, 30.0),
+    ],
+    [
+        (Bodies::Mars, 7.0),
+        (Bodies::Venus, 13.0),
+        (Bodies::Mercury, 19.0),
+        (Bodies::Jupiter, 26.0),
+        (Bodies::Saturn, 30.0),
+    ],
+    [
+        (Bodies::Jupiter, 6.0),
+        (Bodies::Venus, 11.0),
+        (Bodies::Saturn, 18.0),
+        (Bodies::Mercury, 24.0),
+        (Bodies::Mars, 30.0),
+    ],
+    [
+        (Bodies::Mercury, 7.0),
+        (Bodies::Venus, 17.0),
+        (Bodies::Jupiter, 21.0),
+        (Bodies::Mars, 28.0),
+        (Bodies::Saturn, 30.0),
+    ],
+    [
+        (Bodies::Saturn, 6.0),
+        (Bodies::Mercury, 14.0),
+        (Bodies::Jupiter, 21.0),
+        (Bodies::Venus, 28.0),
+        (Bodies::Mars, 30.0),
+    ],
+    [
+        (Bodies::Mars, 7.0),
+        (Bodies::Venus, 11.0),
+        (Bodies::Mercury, 19.0),
+        (Bodies::Jupiter, 24.0),
+        (Bodies::Saturn, 30.0),
+    ],
+    [
+        (Bodies::Jupiter, 12.0),
+        (Bodies::Venus, 17.0),
+        (Bodies::Mercury, 21.0),
+        (Bodies::Saturn, 26.0),
+        (Bodies::Mars, 30.0),
+    ],
+    [
+        (Bodies::Mercury, 7.0),
+        (Bodies::Jupiter, 14.0),
+        (Bodies::Venus, 22.0),
+        (Bodies::Saturn, 26.0),
+        (Bodies::Mars, 30.0),
+    ],
+    [
+        (Bodies::Mercury, 7.0),
+        (Bodies::Venus, 13.0),
+        (Bodies::Jupiter, 20.0),
+        (Bodies::Mars, 25.0),
+        (Bodies::Saturn, 30.0),
+    ],
+    [
+        (Bodies::Venus, 12.0),
+        (Bodies::Jupiter, 16.0),
+        (Bodies::Mercury, 19.0),
+        (Bodies::Mars, 28.0),
+        (Bodies::Saturn, 30.0),
+    ],
+];
+
+// Chaldean order (faces and planetary hours), the first face of Aries is the
+// one of Mars (index 2) and the faces of 10 degree follow this order
+pub(crate) const CHALDEAN_ORDER: [Bodies; 7] = [
+    Bodies::Saturn,
+    Bodies::Jupiter,
+    Bodies::Mars,
+    Bodies::Sun,
+    Bodies::Venus,
+    Bodies::Mercury,
+    Bodies::Moon,
+];
+const FIRST_FACE: usize = 2;
+
+/// Essential dignity or debility of a planet
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Dignity {
+    Domicile,
+    Exaltation,
+    Triplicity,
+    Term,
+    Face,
+    Detriment,
+    Fall,
+    /// No dignity (domicile, exaltation, triplicity, term or face)
+    Peregrine,
+}
+
+impl Dignity {
+    /// Points of William Lilly (Christian Astrology, table of the fortitudes
+    /// and debilities)
+    pub fn points(self) -> i32 {
+        match self {
+            Dignity::Domicile => 5,
+            Dignity::Exaltation => 4,
+            Dignity::Triplicity => 3,
+            Dignity::Term => 2,
+            Dignity::Face => 1,
+            Dignity::Detriment => -5,
+            Dignity::Fall => -4,
+            Dignity::Peregrine => -5,
+        }
+    }
+}
+
+/// Rulers of a longitude
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rulers {
+    #[serde(with = "serde_sweconst::bodies")]
+    pub domicile: Bodies,
+    /// None in a sign without exaltation
+    #[serde(with = "serde_sweconst::bodies_option")]
+    pub exaltation: Option<Bodies>,
+    /// Day, night and participating ruler
+    #[serde(with = "serde_sweconst::bodies_vec")]
+    pub triplicity: Vec<Bodies>,
+    #[serde(with = "serde_sweconst::bodies")]
+    pub term: Bodies,
+    #[serde(with = "serde_sweconst::bodies")]
+    pub face: Bodies,
+}
+
+/// Dignities of a planet at its longitude
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BodieDignities {
+    #[serde(with = "serde_sweconst::bodies")]
+    pub bodie: Bodies,
+    pub longitude: f64,
+    #[serde(with = "serde_sweconst::signs")]
+    pub sign: Signs,
+    pub rulers: Rulers,
+    pub dignities: Vec<Dignity>,
+    /// Sum of the points of Lilly of the dignities
+    pub score: i32,
+}
+
+/// Kind of a mutual reception
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ReceptionKind {
+    /// Each planet in the domicile of the other
+    Domicile,
+    /// Each planet in the exaltation of the other
+    Exaltation,
+    /// One planet in the domicile and the other in the exaltation of the
+    /// other
+    Mixed,
+}
+
+/// Mutual reception of two planets
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MutualReception {
+    #[serde(with = "serde_sweconst::bodies")]
+    pub bodie_a: Bodies,
+    #[serde(with = "serde_sweconst::bodies")]
+    pub bodie_b: Bodies,
+    pub kind: ReceptionKind,
+}
+
+/// Natal chart with the dignities of the classical planets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DignitiesData {
+    pub natal: ChartData,
+    /// Day chart (Sun above the horizon), sect of the triplicity rulers
+    pub diurnal: bool,
+    pub bodies: Vec<BodieDignities>,
+    pub receptions: Vec<MutualReception>,
+}
+
+/// Compute a natal chart and the dignities of the classical planets, the
+/// classical planets are added to the bodies of options
+pub fn compute_dignities(
+    data: DataChartNatal,
+    path: &str,
+    options: &ChartOptions,
+) -> Result<DignitiesData, AstrologyError> {
+    let mut options = options.clone();
+    for bodie in CLASSICAL_PLANETS.iter() {
+        if !options.bodies.planets.contains(bodie) {
+            options.bodies.planets.push(*bodie);
+        }
+    }
+    let natal = compute_natal(data, path, &options)?;
+    let diurnal = natal.diurnal;
+    let bodies: Vec<BodieDignities> = CLASSICAL_PLANETS
+        .iter()
+        .filter_map(|b| {
+            natal
+                .bodie_longitude(*b)
+                .map(|longitude| bodie_dignities(*b, longitude, diurnal))
+        })
+        .collect();
+    let receptions = mutual_receptions(&bodies);
+    Ok(DignitiesData {
+        natal,
+        diurnal,
+        bodies,
+        receptions,
+    })
+}
+
+/// Rulers of a longitude
+pub fn rulers(longitude: f64) -> Rulers {
+    let (sign, deg) = sign_and_degree(longitude);
+    Rulers {
+        domicile: DOMICILE[sign],
+        exaltation: EXALTATION
+            .iter()
+            .find(|(_, s)| *s == sign)
+            .map(|(b, _)| *b),
+        triplicity: TRIPLICITY[sign % 4].to_vec(),
+        term: TERMS[sign]
+            .iter()
+            .find(|(_, end)| deg < *end)
+            .map(|(b, _)| *b)
+            .unwrap_or(TERMS[sign][4].0),
+        face: CHALDEAN_ORDER
+            [(FIRST_FACE + sign * 3 + (deg / 10.0) as usize % 3) % 7],
+    }
+}
+
+/// Dignities of a classical planet at a longitude, the triplicity is the one
+/// of the ruler of the sect (day or night ruler) and of the participating
+/// ruler
+pub fn bodie_dignities(
+    bodie: Bodies,
+    longitude: f64,
+    diurnal: bool,
+) -> BodieDignities {
+    let (sign, _) = sign_and_degree(longitude);
+    let rulers = rulers(longitude);
+    let mut dignities: Vec<Dignity> = Vec::new();
+    if rulers.domicile == bodie {
+        dignities.push(Dignity::Domicile);
+    }
+    if rulers.exaltation == Some(bodie) {
+        dignities.push(Dignity::Exaltation);
+    }
+    let sect_ruler = if diurnal {
+        rulers.triplicity[0]
+    } else {
+        rulers.triplicity[1]
+    };
+    if sect_ruler == bodie || rulers.triplicity[2] == bodie {
+        dignities.push(Dignity::Triplicity);
+    }
+    if rulers.term == bodie {
+        dignities.push(Dignity::Term);
+    }
+    if rulers.face == bodie {
+        dignities.push(Dignity::Face);
+    }
+    if dignities.is_empty() {
+        dignities.push(Dignity::Peregrine);
+    }
+    if DOMICILE[(sign + 6) % 12] == bodie {
+        dignities.push(Dignity::Detriment);
+    }
+    if EXALTATION
+        .iter()
+        .any(|(b, s)| *b == bodie && (s + 6) % 12 == sign)
+    {
+        dignities.push(Dignity::Fall);
+    }
+    BodieDignities {
+        bodie,
+        longitude,
+        sign: handler_swe17::split_deg(longitude, 0).sign,
+        rulers,
+        score: dignities.iter().map(|d| d.points()).sum(),
+        dignities,
+    }
+}
+
+/// Mutual receptions by domicile and exaltation of the planets
+pub fn mutual_receptions(bodies: &[BodieDignities]) -> Vec<MutualReception> {
+    let mut receptions: Vec<MutualReception> = Vec::new();
+    for (i, a) in bodies.iter().enumerate() {
+        for b in bodies.iter().skip(i + 1) {
+            let domicile_a = b.rulers.domicile == a.bodie;
+            let domicile_b = a.rulers.domicile == b.bodie;
+            let exaltation_a = b.rulers.exaltation == Some(a.bodie);
+            let exaltation_b = a.rulers.exaltation == Some(b.bodie);
+            let mut push = |kind: ReceptionKind| {
+                receptions.push(MutualReception {
+                    bodie_a: a.bodie,
+                    bodie_b: b.bodie,
+                    kind,
+                })
+            };
+            if domicile_a && domicile_b {
+                push(ReceptionKind::Domicile);
+            }
+            if exaltation_a && exaltation_b {
+                push(ReceptionKind::Exaltation);
+            }
+            if (domicile_a && exaltation_b) || (exaltation_a && domicile_b) {
+                push(ReceptionKind::Mixed);
+            }
+        }
+    }
+    receptions
+}
+
+/// Sign (0 = Aries) and degree in the sign of a longitude
+fn sign_and_degree(longitude: f64) -> (usize, f64) {
+    let longitude = handler_swe17::degnorm(longitude);
+    let sign = ((longitude / 30.0) as usize).min(11);
+    (sign, longitude - sign as f64 * 30.0)
+}
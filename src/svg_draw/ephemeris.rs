@@ -0,0 +1,211 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+//! Ephemeris table: longitude, declination and speed of the bodies of the
+//! chart for a date range, exported in csv or json
+use super::chart_data::{
+    calc_bodie, calc_error, julian_day, set_ephe_path, DateTimeUtc,
+};
+use super::compute_chart::{ChartOptions, DataChartNatal};
+use super::serde_sweconst;
+use crate::error::AstrologyError;
+use libswe_sys::sweconst::Bodies;
+use libswe_sys::swerust::handler_swe17;
+use serde::{Deserialize, Serialize};
+use strum::AsStaticRef;
+
+/// Part of a step under which the end is still a row of the ephemeris
+const STEP_PRECISION: f64 = 1e-6;
+
+/// Station of a bodie (its speed changes of sign)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Station {
+    /// The bodie turns retrograde
+    Retrograde,
+    /// The bodie turns direct
+    Direct,
+}
+
+impl Station {
+    /// Marker of the station in an ephemeris, "SR" or "SD"
+    pub fn marker(self) -> &'static str {
+        match self {
+            Station::Retrograde => "SR",
+            Station::Direct => "SD",
+        }
+    }
+}
+
+/// Position of a bodie in an ephemeris row
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EphemerisPosition {
+    #[serde(with = "serde_sweconst::bodies")]
+    pub bodie: Bodies,
+    pub longitude: f64,
+    pub declination: f64,
+    /// Speed in longitude, degree by day
+    pub speed: f64,
+    pub retrograde: bool,
+    /// Station between this row and the next one
+    pub station: Option<Station>,
+}
+
+/// Row of an ephemeris
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EphemerisRow {
+    /// Julian day (UT)
+    pub julian_day: f64,
+    pub date: Option<DateTimeUtc>,
+    pub positions: Vec<EphemerisPosition>,
+}
+
+/// Ephemeris from start to end (included) every step days
+/// The bodies are the ones of options.bodies without the Part of Fortune (it
+/// needs a location), in the zodiac and with the center of options, the
+/// location of start is only used for the topocentric positions
+pub fn ephemeris(
+    start: DataChartNatal,
+    end: DataChartNatal,
+    step: f64,
+    path: &str,
+    options: &ChartOptions,
+) -> Result<Vec<EphemerisRow>, AstrologyError> {
+    let julian_day_start = julian_day(&start)?;
+    let julian_day_end = julian_day(&end)?;
+    if julian_day_end < julian_day_start {
+        return Err(AstrologyError::InvalidDateTime(format!(
+            "the end of the ephemeris ({}.{}.{}) is before the start \
+             ({}.{}.{})",
+            end.day, end.month, end.year, start.day, start.month, start.year
+        )));
+    }
+    if step.is_nan() || step <= 0.0 {
+        return Err(AstrologyError::InvalidArgument(format!(
+            "step: {} isn't a positive number of days",
+            step
+        )));
+    }
+    set_ephe_path(path)?;
+    options.calc.set_topo(start.lat as f64, start.lng as f64);
+    let bodies: Vec<Bodies> = options
+        .bodies
+        .list()
+        .into_iter()
+        .filter(|b| *b != Bodies::FortunaPart)
+        .collect();
+    // The julian days of utc_to_jd aren't exact, the end is included
+    let count = ((julian_day_end - julian_day_start) / step + STEP_PRECISION)
+        .floor() as usize;
+    let mut rows: Vec<EphemerisRow> = Vec::new();
+    let mut positions =
+        ephemeris_positions(julian_day_start, &bodies, options)?;
+    for i in 0..=count {
+        let julian_day = julian_day_start + i as f64 * step;
+        let next = ephemeris_positions(julian_day + step, &bodies, options)?;
+        for (p, n) in positions.iter_mut().zip(next.iter()) {
+            p.station = if p.speed >= 0.0 && n.speed < 0.0 {
+                Some(Station::Retrograde)
+            } else if p.speed < 0.0 && n.speed >= 0.0 {
+                Some(Station::Direct)
+            } else {
+                None
+            };
+        }
+        rows.push(EphemerisRow {
+            julian_day,
+            date: DateTimeUtc::from_julian_day(julian_day),
+            positions,
+        });
+        positions = next;
+    }
+    Ok(rows)
+}
+
+/// Ephemeris in csv, one column by value of each bodie
+/// date,julian_day,Sun longitude,Sun declination,Sun speed,Sun station,...
+pub fn ephemeris_csv(rows: &[EphemerisRow]) -> String {
+    let mut csv = String::from("date,julian_day");
+    if let Some(row) = rows.first() {
+        for p in row.positions.iter() {
+            csv.push_str(&format!(
+                ",{0} longitude,{0} declination,{0} speed,{0} station",
+                p.bodie.as_static()
+            ));
+        }
+    }
+    csv.push('\n');
+    for row in rows.iter() {
+        let date = row.date.map(|d| d.to_string()).unwrap_or_default();
+        csv.push_str(&format!("{},{:.6}", date, row.julian_day));
+        for p in row.positions.iter() {
+            csv.push_str(&format!(
+                ",{:.6},{:.6},{:.6},{}",
+                p.longitude,
+                p.declination,
+                p.speed,
+                p.station.map(|s| s.marker()).unwrap_or_default()
+            ));
+        }
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Ephemeris in json
+pub fn ephemeris_json(rows: &[EphemerisRow]) -> Result<String, AstrologyError> {
+    serde_json::to_string_pretty(rows).map_err(|e| {
+        AstrologyError::CalculationFailed(format!("ephemeris: {}", e))
+    })
+}
+
+/// Positions of the bodies at a julian day
+fn ephemeris_positions(
+    julian_day: f64,
+    bodies: &[Bodies],
+    options: &ChartOptions,
+) -> Result<Vec<EphemerisPosition>, AstrologyError> {
+    let ayanamsa = options.zodiac.ayanamsa(julian_day)?;
+    let nodes = options.bodies.nodes;
+    bodies
+        .iter()
+        .map(|bodie| {
+            let calc =
+                calc_bodie(julian_day, *bodie, options.calc.flag(), nodes);
+            calc_error(*bodie, &calc)?;
+            let equatorial = calc_bodie(
+                julian_day,
+                *bodie,
+                options.calc.flag_equatorial(),
+                nodes,
+            );
+            calc_error(*bodie, &equatorial)?;
+            // The South Node is computed from the North Node
+            let declination = if *bodie == Bodies::SouthNode {
+                -equatorial.latitude
+            } else {
+                equatorial.latitude
+            };
+            Ok(EphemerisPosition {
+                bodie: *bodie,
+                longitude: handler_swe17::degnorm(calc.longitude - ayanamsa),
+                declination,
+                speed: calc.speed_longitude,
+                retrograde: calc.speed_longitude < 0.0,
+                station: None,
+            })
+        })
+        .collect()
+}
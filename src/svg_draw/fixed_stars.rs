@@ -0,0 +1,365 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+//! Fixed stars of the catalog sefstars.txt of the swiss ephemeris: position at
+//! the date of the chart, conjunctions to the bodies and angles and parans
+//! (star and bodie on an angle at the same time at the place of the chart)
+use super::chart_data::{
+    calc_bodie, calc_error, compute_natal, separation, signed_distance,
+    ChartData, ChartPoint,
+};
+use super::compute_chart::{ChartOptions, DataChartNatal};
+use super::serde_sweconst;
+use super::sidereal::Zodiac;
+use super::swe_fixstar;
+use crate::error::AstrologyError;
+use libswe_sys::sweconst::{Bodies, OptionalFlag};
+use libswe_sys::swerust::handler_swe17;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+// Catalog of the fixed stars in the path of the ephemeris files
+const STAR_FILE: &str = "sefstars.txt";
+
+/// Stars of the traditional astrology, default catalog of FixedStarOptions
+/// (traditional names of sefstars.txt)
+pub const FIXED_STARS: [&str; 30] = [
+    "Alpheratz",
+    "Algol",
+    "Alcyone",
+    "Aldebaran",
+    "Rigel",
+    "Capella",
+    "Betelgeuse",
+    "Sirius",
+    "Canopus",
+    "Castor",
+    "Pollux",
+    "Procyon",
+    "Regulus",
+    "Zosma",
+    "Denebola",
+    "Vindemiatrix",
+    "Spica",
+    "Arcturus",
+    "Zuben Elgenubi",
+    "Zuben Eschamali",
+    "Alphecca",
+    "Antares",
+    "Vega",
+    "Altair",
+    "Deneb Algedi",
+    "Fomalhaut",
+    "Deneb Adige",
+    "Markab",
+    "Scheat",
+    "Achernar",
+];
+
+/// Options of the fixed stars
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FixedStarOptions {
+    /// Names of the stars in sefstars.txt (FIXED_STARS by default)
+    pub stars: Vec<String>,
+    /// Orb in degree of the conjunctions to the bodies and the angles
+    pub orb: f64,
+    /// Orb in degree of right ascension of the parans (1° is 4 minutes)
+    pub paran_orb: f64,
+    /// Natal chart: draw the stars on an outer ring
+    pub ring: bool,
+}
+
+impl Default for FixedStarOptions {
+    fn default() -> Self {
+        Self {
+            stars: FIXED_STARS.iter().map(|s| s.to_string()).collect(),
+            orb: 1.0,
+            paran_orb: 1.0,
+            ring: false,
+        }
+    }
+}
+
+/// Position of a fixed star at the date of the chart
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FixedStarData {
+    /// Traditional name ("Regulus")
+    pub name: String,
+    /// Nomenclature name ("alLeo")
+    pub nomenclature: String,
+    /// Longitude in the zodiac of the chart
+    pub longitude: f64,
+    pub latitude: f64,
+    pub right_ascension: f64,
+    pub declination: f64,
+    /// Visual magnitude
+    pub magnitude: f64,
+}
+
+/// Conjunction of a fixed star to a bodie or an angle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StarConjunction {
+    pub star: String,
+    pub point: ChartPoint,
+    /// Distance in longitude in degree
+    pub orb: f64,
+}
+
+/// Angle of a paran, the hour circle of a star or a bodie
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ParanAngle {
+    /// On the Asc (eastern horizon)
+    Rising,
+    /// On the Mc (upper meridian)
+    Culminating,
+    /// On the Desc (western horizon)
+    Setting,
+    /// On the Ic (lower meridian)
+    AntiCulminating,
+}
+
+/// Paran: a fixed star and a bodie on an angle at the same sidereal time (on
+/// the day of the chart, at its latitude)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StarParan {
+    pub star: String,
+    pub star_angle: ParanAngle,
+    #[serde(with = "serde_sweconst::bodies")]
+    pub bodie: Bodies,
+    pub bodie_angle: ParanAngle,
+    /// Difference of the sidereal times (RAMC) in degree
+    pub orb: f64,
+}
+
+/// Fixed stars of a natal chart computed without svg
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FixedStarsData {
+    pub natal: ChartData,
+    /// Stars of options.stars.stars
+    pub stars: Vec<FixedStarData>,
+    /// Conjunctions within options.stars.orb, sorted by orb
+    pub conjunctions: Vec<StarConjunction>,
+    /// Parans within options.stars.paran_orb, sorted by orb
+    pub parans: Vec<StarParan>,
+}
+
+/// Compute the fixed stars of a natal chart, sefstars.txt must be in path
+pub fn compute_fixed_stars(
+    data: DataChartNatal,
+    path: &str,
+    options: &ChartOptions,
+) -> Result<FixedStarsData, AstrologyError> {
+    let lat = data.lat as f64;
+    let natal = compute_natal(data, path, options)?;
+    check_star_file(path)?;
+    let stars =
+        fixed_stars(natal.julian_day, natal.zodiac, &options.stars.stars)?;
+    let conjunctions = star_conjunctions(&natal, &stars, options.stars.orb);
+    let parans =
+        star_parans(&natal, lat, &stars, options.stars.paran_orb, options)?;
+    Ok(FixedStarsData {
+        natal,
+        stars,
+        conjunctions,
+        parans,
+    })
+}
+
+/// Position of the stars at a julian day (UT) in a zodiac, the path of the
+/// ephemeris files is already set and checked (check_star_file)
+pub(crate) fn fixed_stars(
+    julian_day: f64,
+    zodiac: Zodiac,
+    names: &[String],
+) -> Result<Vec<FixedStarData>, AstrologyError> {
+    names
+        .iter()
+        .map(|name| {
+            let ecliptic = swe_fixstar::fixstar2_ut(
+                name,
+                julian_day,
+                OptionalFlag::Speed as i32,
+            );
+            star_error(name, ecliptic.result, &ecliptic.serr)?;
+            let equatorial = swe_fixstar::fixstar2_ut(
+                name,
+                julian_day,
+                OptionalFlag::Speed as i32
+                    | OptionalFlag::EquatorialPosition as i32,
+            );
+            star_error(name, equatorial.result, &equatorial.serr)?;
+            let (result, magnitude, serr) = swe_fixstar::fixstar2_mag(name);
+            star_error(name, result, &serr)?;
+            let mut split = ecliptic.star.splitn(2, ',');
+            Ok(FixedStarData {
+                name: split.next().unwrap_or_default().trim().to_string(),
+                nomenclature: split.next().unwrap_or_default().to_string(),
+                longitude: zodiac.longitude(ecliptic.longitude, julian_day)?,
+                latitude: ecliptic.latitude,
+                right_ascension: equatorial.longitude,
+                declination: equatorial.latitude,
+                magnitude,
+            })
+        })
+        .collect()
+}
+
+/// Check that the catalog of the stars is in the path of the ephemeris files
+/// (swe_fixstar2 reports a missing file as an unknown star)
+pub(crate) fn check_star_file(path: &str) -> Result<(), AstrologyError> {
+    if Path::new(path).join(STAR_FILE).exists() {
+        Ok(())
+    } else {
+        Err(AstrologyError::MissingEphemerisFiles(format!(
+            "{} not found in the path {}",
+            STAR_FILE, path
+        )))
+    }
+}
+
+/// Error of swe_fixstar2 (unknown star)
+fn star_error(
+    name: &str,
+    result: i32,
+    serr: &str,
+) -> Result<(), AstrologyError> {
+    if result >= 0 {
+        Ok(())
+    } else {
+        Err(AstrologyError::InvalidArgument(format!(
+            "{}: {}",
+            name, serr
+        )))
+    }
+}
+
+/// Conjunctions in longitude of the stars to the bodies and Asc/Mc of a chart
+/// within orb, sorted by orb
+pub fn star_conjunctions(
+    chart: &ChartData,
+    stars: &[FixedStarData],
+    orb: f64,
+) -> Vec<StarConjunction> {
+    let points = chart.points();
+    let mut res: Vec<StarConjunction> = Vec::new();
+    for star in stars.iter() {
+        for (point, longitude, _) in points.iter().copied() {
+            let distance = separation(star.longitude, longitude);
+            if distance <= orb {
+                res.push(StarConjunction {
+                    star: star.name.clone(),
+                    point,
+                    orb: distance,
+                });
+            }
+        }
+    }
+    res.sort_by(|a, b| {
+        a.orb
+            .partial_cmp(&b.orb)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    res
+}
+
+/// Parans of the stars with the bodies of a chart (without the Part of
+/// Fortune) at the latitude lat within orb, sorted by orb
+/// A star or a bodie that doesn't rise at this latitude has only the meridian
+/// angles
+fn star_parans(
+    chart: &ChartData,
+    lat: f64,
+    stars: &[FixedStarData],
+    orb: f64,
+    options: &ChartOptions,
+) -> Result<Vec<StarParan>, AstrologyError> {
+    let mut bodies: Vec<(Bodies, Vec<(ParanAngle, f64)>)> = Vec::new();
+    for b in chart.bodies.iter() {
+        if b.bodie == Bodies::FortunaPart {
+            continue;
+        }
+        let calc = calc_bodie(
+            chart.julian_day,
+            b.bodie,
+            options.calc.flag_equatorial(),
+            options.bodies.nodes,
+        );
+        calc_error(b.bodie, &calc)?;
+        // The South Node is computed from the North Node
+        let declination = if b.bodie == Bodies::SouthNode {
+            -calc.latitude
+        } else {
+            calc.latitude
+        };
+        bodies.push((b.bodie, paran_ramc(calc.longitude, declination, lat)));
+    }
+    let mut res: Vec<StarParan> = Vec::new();
+    for star in stars.iter() {
+        let star_ramc = paran_ramc(star.right_ascension, star.declination, lat);
+        for (bodie, bodie_ramc) in bodies.iter() {
+            for (star_angle, ramc_s) in star_ramc.iter().copied() {
+                for (bodie_angle, ramc_b) in bodie_ramc.iter().copied() {
+                    let distance = signed_distance(ramc_s, ramc_b).abs();
+                    if distance <= orb {
+                        res.push(StarParan {
+                            star: star.name.clone(),
+                            star_angle,
+                            bodie: *bodie,
+                            bodie_angle,
+                            orb: distance,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    res.sort_by(|a, b| {
+        a.orb
+            .partial_cmp(&b.orb)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    Ok(res)
+}
+
+/// Sidereal time (RAMC in degree) of a point on each angle at the latitude
+/// lat, the ascensional difference gives the rising and the setting
+fn paran_ramc(
+    right_ascension: f64,
+    declination: f64,
+    lat: f64,
+) -> Vec<(ParanAngle, f64)> {
+    let mut res = vec![
+        (ParanAngle::Culminating, right_ascension),
+        (
+            ParanAngle::AntiCulminating,
+            handler_swe17::degnorm(right_ascension + 180.0),
+        ),
+    ];
+    let sin_ad = lat.to_radians().tan() * declination.to_radians().tan();
+    if sin_ad.abs() <= 1.0 {
+        let ad = sin_ad.asin().to_degrees();
+        res.push((
+            ParanAngle::Rising,
+            handler_swe17::degnorm(right_ascension - 90.0 - ad),
+        ));
+        res.push((
+            ParanAngle::Setting,
+            handler_swe17::degnorm(right_ascension + 90.0 + ad),
+        ));
+    }
+    res
+}
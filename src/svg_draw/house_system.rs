@@ -0,0 +1,109 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+use libswe_sys::sweconst::Language;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use strum_macros::EnumIter;
+
+/// House system
+/// The char of each variant is the hsys code of swiss ephemeris
+#[derive(
+    Debug, Clone, Copy, PartialEq, Serialize, Deserialize, EnumIter, Default,
+)]
+pub enum HouseSystem {
+    #[default]
+    Placidus,
+    Koch,
+    Porphyry,
+    Regiomontanus,
+    Campanus,
+    Alcabitius,
+    Equal,
+    WholeSign,
+}
+
+impl HouseSystem {
+    /// Code hsys for swiss ephemeris
+    pub fn code(self) -> char {
+        match self {
+            HouseSystem::Placidus => 'P',
+            HouseSystem::Koch => 'K',
+            HouseSystem::Porphyry => 'O',
+            HouseSystem::Regiomontanus => 'R',
+            HouseSystem::Campanus => 'C',
+            HouseSystem::Alcabitius => 'B',
+            HouseSystem::Equal => 'E',
+            HouseSystem::WholeSign => 'W',
+        }
+    }
+
+    /// Code hsys for the computation of the Part of Fortune
+    /// calc_ut_fp read the Asc and the Mc in the cusps 1 and 10, this is only
+    /// true for the quadrant systems, Porphyry is used for the others
+    pub fn code_fortuna(self) -> char {
+        match self {
+            HouseSystem::Equal | HouseSystem::WholeSign => 'O',
+            _ => self.code(),
+        }
+    }
+
+    /// Text for translation
+    pub fn text(self, lang: Language) -> String {
+        match lang {
+            Language::English => match self {
+                HouseSystem::Placidus => "Placidus".to_string(),
+                HouseSystem::Koch => "Koch".to_string(),
+                HouseSystem::Porphyry => "Porphyry".to_string(),
+                HouseSystem::Regiomontanus => "Regiomontanus".to_string(),
+                HouseSystem::Campanus => "Campanus".to_string(),
+                HouseSystem::Alcabitius => "Alcabitius".to_string(),
+                HouseSystem::Equal => "Equal".to_string(),
+                HouseSystem::WholeSign => "Whole sign".to_string(),
+            },
+            Language::French => match self {
+                HouseSystem::Placidus => "Placidus".to_string(),
+                HouseSystem::Koch => "Koch".to_string(),
+                HouseSystem::Porphyry => "Porphyre".to_string(),
+                HouseSystem::Regiomontanus => "Regiomontanus".to_string(),
+                HouseSystem::Campanus => "Campanus".to_string(),
+                HouseSystem::Alcabitius => "Alcabitius".to_string(),
+                HouseSystem::Equal => "Maisons égales".to_string(),
+                HouseSystem::WholeSign => "Signes entiers".to_string(),
+            },
+        }
+    }
+}
+
+/// Parse the hsys code of swiss ephemeris (P, K, O, R, C, B, E, W) or the
+/// english name (placidus, whole_sign, ...)
+impl FromStr for HouseSystem {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace(['-', ' '], "_").as_str() {
+            "p" | "placidus" => Ok(HouseSystem::Placidus),
+            "k" | "koch" => Ok(HouseSystem::Koch),
+            "o" | "porphyry" | "porphyrius" => Ok(HouseSystem::Porphyry),
+            "r" | "regiomontanus" => Ok(HouseSystem::Regiomontanus),
+            "c" | "campanus" => Ok(HouseSystem::Campanus),
+            "b" | "alcabitius" => Ok(HouseSystem::Alcabitius),
+            "e" | "equal" => Ok(HouseSystem::Equal),
+            "w" | "whole_sign" | "wholesign" => Ok(HouseSystem::WholeSign),
+            _ => Err(format!("{} isn't a known house system", s)),
+        }
+    }
+}
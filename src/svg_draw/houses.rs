@@ -15,10 +15,10 @@
  * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
  */
 extern crate strum;
+use crate::svg_draw::chart_style::ChartStyle;
 use crate::svg_draw::svg_draw::{
     WorkingStorageDrawPolyMorphNatal, WorkingStorageDrawPolyMorphTransit,
 };
-use libswe_sys::sweconst::Theme;
 use svg::node::element::path::{Data, Number};
 use svg::node::element::{Group, Path};
 use svg::Document;
@@ -27,17 +27,18 @@ pub const HOUSE_SIZE: Number = 50.0;
 
 impl WorkingStorageDrawPolyMorphNatal {
     pub fn houses_draw(&self, house: i16) -> Document {
-        draw_house(house, self.ws.theme)
+        draw_house(house, &self.ws.style)
     }
 }
 
 impl WorkingStorageDrawPolyMorphTransit {
     pub fn houses_draw(&self, house: i16) -> Document {
-        draw_house(house, self.ws.theme)
+        draw_house(house, &self.ws.style)
     }
 }
 
-fn draw_house(house: i16, _theme: Theme) -> Document {
+fn draw_house(house: i16, style: &ChartStyle) -> Document {
+    let color: &str = style.text.as_str();
     let size: (Number, Number) = (HOUSE_SIZE, HOUSE_SIZE);
     let document: Document;
     match house {
@@ -54,8 +55,8 @@ fn draw_house(house: i16, _theme: Theme) -> Document {
                 .vertical_line_to(14.3) // V
                 .close(); // z
             let path = Path::new()
-                .set("fill", "black")
-                .set("stroke", "black")
+                .set("fill", color)
+                .set("stroke", color)
                 .set("stroke-width", 0)
                 .set("d", data);
             document = Document::new()
@@ -81,8 +82,8 @@ fn draw_house(house: i16, _theme: Theme) -> Document {
                 .horizontal_line_to(15.5) // H
                 .close(); // z
             let path = Path::new()
-                .set("fill", "black")
-                .set("stroke", "black")
+                .set("fill", color)
+                .set("stroke", color)
                 .set("stroke-width", 0)
                 .set("d", data);
             document = Document::new()
@@ -112,8 +113,8 @@ fn draw_house(house: i16, _theme: Theme) -> Document {
                 .line_to((16.3, 32.3)) // L
                 .close(); // z
             let path = Path::new()
-                .set("fill", "black")
-                .set("stroke", "black")
+                .set("fill", color)
+                .set("stroke", color)
                 .set("stroke-width", 0)
                 .set("d", data);
             document = Document::new()
@@ -145,8 +146,8 @@ fn draw_house(house: i16, _theme: Theme) -> Document {
                 .horizontal_line_to(26.0) // H
                 .close(); // z
             let path = Path::new()
-                .set("fill", "black")
-                .set("stroke", "black")
+                .set("fill", color)
+                .set("stroke", color)
                 .set("stroke-width", 0)
                 .set("d", data);
             document = Document::new()
@@ -173,8 +174,8 @@ fn draw_house(house: i16, _theme: Theme) -> Document {
                 .vertical_line_to(13.9) // V
                 .close(); // z
             let path = Path::new()
-                .set("fill", "black")
-                .set("stroke", "black")
+                .set("fill", color)
+                .set("stroke", color)
                 .set("stroke-width", 0)
                 .set("d", data);
             document = Document::new()
@@ -204,8 +205,8 @@ fn draw_house(house: i16, _theme: Theme) -> Document {
                 .cubic_curve_to((26.9, 34.0, 28.9, 31.6, 28.9, 28.0)) // C
                 .close(); // z
             let path = Path::new()
-                .set("fill", "black")
-                .set("stroke", "black")
+                .set("fill", color)
+                .set("stroke", color)
                 .set("stroke-width", 0)
                 .set("d", data);
             document = Document::new()
@@ -225,8 +226,8 @@ fn draw_house(house: i16, _theme: Theme) -> Document {
                 .horizontal_line_to(31.8) // H
                 .close(); // z
             let path = Path::new()
-                .set("fill", "black")
-                .set("stroke", "black")
+                .set("fill", color)
+                .set("stroke", color)
                 .set("stroke-width", 0)
                 .set("d", data);
             document = Document::new()
@@ -260,8 +261,8 @@ fn draw_house(house: i16, _theme: Theme) -> Document {
                 .cubic_curve_to((20.9, 13.1, 19.4, 14.9, 19.4, 17.1)) // C
                 .close(); // z
             let path = Path::new()
-                .set("fill", "black")
-                .set("stroke", "black")
+                .set("fill", color)
+                .set("stroke", color)
                 .set("stroke-width", 0)
                 .set("d", data);
             document = Document::new()
@@ -292,8 +293,8 @@ fn draw_house(house: i16, _theme: Theme) -> Document {
                 .cubic_curve_to((20.8, 13.2, 18.7, 15.8, 18.7, 19.4)) // C
                 .close(); // z
             let path = Path::new()
-                .set("fill", "black")
-                .set("stroke", "black")
+                .set("fill", color)
+                .set("stroke", color)
                 .set("stroke-width", 0)
                 .set("d", data);
             document = Document::new()
@@ -326,13 +327,13 @@ fn draw_house(house: i16, _theme: Theme) -> Document {
                 .cubic_curve_to((30.6, 13.3, 28.5, 16.9, 28.5, 23.8)) // C
                 .close(); // z
             let path1 = Path::new()
-                .set("fill", "black")
-                .set("stroke", "black")
+                .set("fill", color)
+                .set("stroke", color)
                 .set("stroke-width", 0)
                 .set("d", data1);
             let path2 = Path::new()
-                .set("fill", "black")
-                .set("stroke", "black")
+                .set("fill", color)
+                .set("stroke", color)
                 .set("stroke-width", 0)
                 .set("d", data2);
             let group = Group::new().add(path1).add(path2);
@@ -364,13 +365,13 @@ fn draw_house(house: i16, _theme: Theme) -> Document {
                 .vertical_line_to(14.3) // V
                 .close(); // z
             let path1 = Path::new()
-                .set("fill", "black")
-                .set("stroke", "black")
+                .set("fill", color)
+                .set("stroke", color)
                 .set("stroke-width", 0)
                 .set("d", data1);
             let path2 = Path::new()
-                .set("fill", "black")
-                .set("stroke", "black")
+                .set("fill", color)
+                .set("stroke", color)
                 .set("stroke-width", 0)
                 .set("d", data2);
             let group = Group::new().add(path1).add(path2);
@@ -408,13 +409,13 @@ fn draw_house(house: i16, _theme: Theme) -> Document {
                 .horizontal_line_to(25.4) // H
                 .close(); // z
             let path1 = Path::new()
-                .set("fill", "black")
-                .set("stroke", "black")
+                .set("fill", color)
+                .set("stroke", color)
                 .set("stroke-width", 0)
                 .set("d", data1);
             let path2 = Path::new()
-                .set("fill", "black")
-                .set("stroke", "black")
+                .set("fill", color)
+                .set("stroke", color)
                 .set("stroke-width", 0)
                 .set("d", data2);
             let group = Group::new().add(path1).add(path2);
@@ -0,0 +1,140 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+//! Sign ingresses and stations of the bodies over a date range
+//! The bodies are followed step by step with calc_ut (longitude and speed),
+//! a change of sign or of the sign of the speed is refined by bisection
+use super::chart_data::{julian_day, set_ephe_path, DateTimeUtc};
+use super::compute_chart::{ChartOptions, DataChartNatal};
+use super::serde_sweconst;
+use super::transit_search::{bisection, zodiac_position};
+use crate::error::AstrologyError;
+use libswe_sys::sweconst::{Bodies, Signs};
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+// Step of the search in days, the Moon moves less than 30° in one step
+const STEP: f64 = 0.5;
+
+/// Kind of an event of a bodie
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum BodieEventKind {
+    /// The bodie enters a sign
+    Ingress(#[serde(with = "serde_sweconst::signs")] Signs),
+    /// The bodie turns retrograde
+    StationRetrograde,
+    /// The bodie turns direct
+    StationDirect,
+}
+
+/// Ingress or station of a bodie
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BodieEvent {
+    pub kind: BodieEventKind,
+    #[serde(with = "serde_sweconst::bodies")]
+    pub bodie: Bodies,
+    /// Julian day (UT) of the event
+    pub julian_day: f64,
+    pub date: Option<DateTimeUtc>,
+    pub longitude: f64,
+    /// Ingress in retrograde motion (back in the previous sign)
+    pub retrograde: bool,
+}
+
+/// Sign ingresses and stations of the bodies of options.bodies between start
+/// and end, sorted by date, the signs are the ones of options.zodiac
+/// The Part of Fortune (it needs a location) is not searched, the location of
+/// start is only used for the topocentric positions
+pub fn ingresses_stations(
+    start: DataChartNatal,
+    end: DataChartNatal,
+    path: &str,
+    options: &ChartOptions,
+) -> Result<Vec<BodieEvent>, AstrologyError> {
+    let julian_day_start = julian_day(&start)?;
+    let julian_day_end = julian_day(&end)?;
+    if julian_day_end < julian_day_start {
+        return Err(AstrologyError::InvalidDateTime(format!(
+            "the end of the search ({}.{}.{}) is before the start ({}.{}.{})",
+            end.day, end.month, end.year, start.day, start.month, start.year
+        )));
+    }
+    set_ephe_path(path)?;
+    options.calc.set_topo(start.lat as f64, start.lng as f64);
+    let mut events: Vec<BodieEvent> = Vec::new();
+    let bodies = options.bodies.list();
+    for bodie in bodies.iter().filter(|b| **b != Bodies::FortunaPart) {
+        let mut jd = julian_day_start;
+        let mut position = zodiac_position(*bodie, jd, options)?;
+        while jd < julian_day_end {
+            let jd_next = (jd + STEP).min(julian_day_end);
+            let next = zodiac_position(*bodie, jd_next, options)?;
+            let sign = sign_index(position.0);
+            if sign != sign_index(next.0) {
+                let jd_event = bisection(jd, jd_next, |jd| {
+                    Ok(sign_index(zodiac_position(*bodie, jd, options)?.0)
+                        == sign)
+                })?;
+                let (longitude, speed) =
+                    zodiac_position(*bodie, jd_event, options)?;
+                events.push(BodieEvent {
+                    kind: BodieEventKind::Ingress(
+                        Signs::iter()
+                            .nth(sign_index(next.0))
+                            .unwrap_or(Signs::Aries),
+                    ),
+                    bodie: *bodie,
+                    julian_day: jd_event,
+                    date: DateTimeUtc::from_julian_day(jd_event),
+                    longitude,
+                    retrograde: speed < 0.0,
+                });
+            }
+            let retrograde = position.1 < 0.0;
+            if retrograde != (next.1 < 0.0) {
+                let jd_event = bisection(jd, jd_next, |jd| {
+                    Ok((zodiac_position(*bodie, jd, options)?.1 < 0.0)
+                        == retrograde)
+                })?;
+                events.push(BodieEvent {
+                    kind: if retrograde {
+                        BodieEventKind::StationDirect
+                    } else {
+                        BodieEventKind::StationRetrograde
+                    },
+                    bodie: *bodie,
+                    julian_day: jd_event,
+                    date: DateTimeUtc::from_julian_day(jd_event),
+                    longitude: zodiac_position(*bodie, jd_event, options)?.0,
+                    retrograde: false,
+                });
+            }
+            jd = jd_next;
+            position = next;
+        }
+    }
+    events.sort_by(|a, b| {
+        a.julian_day
+            .partial_cmp(&b.julian_day)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    Ok(events)
+}
+
+/// Index of the sign of a longitude, 0 for Aries
+fn sign_index(longitude: f64) -> usize {
+    ((longitude / 30.0).floor() as usize).min(11)
+}
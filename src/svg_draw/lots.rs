@@ -0,0 +1,237 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+//! Arabic lots: longitude Asc + A - B of a formula, A and B are swapped in a
+//! night chart (sect::is_diurnal) if the formula is reversed at night
+use super::chart_data::{house_position, AngleData, CuspData};
+use super::serde_sweconst;
+use crate::error::AstrologyError;
+use libswe_sys::sweconst::{Angle, Bodies, Signs};
+use libswe_sys::swerust::handler_swe17;
+use serde::{Deserialize, Serialize};
+
+// Number of letters of the name of a lot drawn on the wheel
+const LOT_LABEL: usize = 3;
+
+/// Point of the formula of a lot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LotPoint {
+    Bodie(#[serde(with = "serde_sweconst::bodies")] Bodies),
+    Angle(#[serde(with = "serde_sweconst::angle")] Angle),
+    /// Name of a lot before this one in the list of the formulas
+    Lot(String),
+}
+
+/// Formula of a lot: Asc + a - b, Asc + b - a in a night chart if
+/// reverse_at_night
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LotFormula {
+    pub name: String,
+    pub a: LotPoint,
+    pub b: LotPoint,
+    pub reverse_at_night: bool,
+}
+
+impl LotFormula {
+    pub fn new(
+        name: &str,
+        a: LotPoint,
+        b: LotPoint,
+        reverse_at_night: bool,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            a,
+            b,
+            reverse_at_night,
+        }
+    }
+}
+
+/// Hermetic lots of Paulus Alexandrinus (day formulas, reversed at night) and
+/// the lot of Marriage of Dorotheus (Asc + Venus - Saturn, not reversed)
+pub fn hermetic_lots() -> Vec<LotFormula> {
+    let bodie = LotPoint::Bodie;
+    let lot = |name: &str| LotPoint::Lot(name.to_string());
+    vec![
+        LotFormula::new(
+            "Fortune",
+            bodie(Bodies::Moon),
+            bodie(Bodies::Sun),
+            true,
+        ),
+        LotFormula::new(
+            "Spirit",
+            bodie(Bodies::Sun),
+            bodie(Bodies::Moon),
+            true,
+        ),
+        LotFormula::new("Eros", bodie(Bodies::Venus), lot("Spirit"), true),
+        LotFormula::new(
+            "Necessity",
+            lot("Fortune"),
+            bodie(Bodies::Mercury),
+            true,
+        ),
+        LotFormula::new("Courage", lot("Fortune"), bodie(Bodies::Mars), true),
+        LotFormula::new("Victory", bodie(Bodies::Jupiter), lot("Spirit"), true),
+        LotFormula::new("Nemesis", lot("Fortune"), bodie(Bodies::Saturn), true),
+        LotFormula::new(
+            "Marriage",
+            bodie(Bodies::Venus),
+            bodie(Bodies::Saturn),
+            false,
+        ),
+    ]
+}
+
+/// Lots of a chart
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LotOptions {
+    /// Formulas in the order of the computation, a lot can use the lots
+    /// before it (hermetic_lots() by default)
+    pub formulas: Vec<LotFormula>,
+    /// Draw the lots on the natal wheel (markers on the circle of the
+    /// aspects)
+    pub draw: bool,
+}
+
+impl Default for LotOptions {
+    fn default() -> Self {
+        Self {
+            formulas: hermetic_lots(),
+            draw: false,
+        }
+    }
+}
+
+/// Position of a lot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LotData {
+    pub name: String,
+    pub longitude: f64,
+    #[serde(with = "serde_sweconst::signs")]
+    pub sign: Signs,
+    pub deg: i32,
+    pub min: i32,
+    pub sec: i32,
+    pub house: i32,
+    /// Night formula: Asc + b - a
+    pub reversed: bool,
+}
+
+impl LotData {
+    /// Text of the lot on the wheel (first letters of the name)
+    pub fn label(&self) -> String {
+        self.name.chars().take(LOT_LABEL).collect()
+    }
+}
+
+/// Lots of the formulas in a day or night chart, position gives the longitude
+/// of a bodie in the zodiac of the chart (the bodies of the formulas aren't
+/// always selected)
+pub(crate) fn compute_lots<F>(
+    formulas: &[LotFormula],
+    diurnal: bool,
+    angles: &[AngleData],
+    cusps: &[CuspData],
+    position: F,
+) -> Result<Vec<LotData>, AstrologyError>
+where
+    F: Fn(Bodies) -> Result<f64, AstrologyError>,
+{
+    if formulas.is_empty() {
+        return Ok(Vec::new());
+    }
+    let asc = angle_longitude(angles, Angle::Asc)?;
+    let mut lots: Vec<LotData> = Vec::new();
+    for formula in formulas.iter() {
+        let a = point_longitude(&formula.a, angles, &lots, &position)?;
+        let b = point_longitude(&formula.b, angles, &lots, &position)?;
+        let reversed = formula.reverse_at_night && !diurnal;
+        let longitude = if reversed {
+            handler_swe17::degnorm(asc + b - a)
+        } else {
+            handler_swe17::degnorm(asc + a - b)
+        };
+        lots.push(lot_data(&formula.name, longitude, reversed, cusps));
+    }
+    Ok(lots)
+}
+
+/// Lot with its sign and house
+pub(crate) fn lot_data(
+    name: &str,
+    longitude: f64,
+    reversed: bool,
+    cusps: &[CuspData],
+) -> LotData {
+    let split = handler_swe17::split_deg(longitude, 0);
+    LotData {
+        name: name.to_string(),
+        longitude,
+        sign: split.sign,
+        deg: split.deg.abs(),
+        min: split.min,
+        sec: split.sec,
+        house: house_position(longitude, cusps),
+        reversed,
+    }
+}
+
+/// Longitude of a point of a formula
+fn point_longitude<F>(
+    point: &LotPoint,
+    angles: &[AngleData],
+    lots: &[LotData],
+    position: &F,
+) -> Result<f64, AstrologyError>
+where
+    F: Fn(Bodies) -> Result<f64, AstrologyError>,
+{
+    match point {
+        LotPoint::Bodie(bodie) => position(*bodie),
+        LotPoint::Angle(angle) => angle_longitude(angles, *angle),
+        LotPoint::Lot(name) => lots
+            .iter()
+            .find(|l| &l.name == name)
+            .map(|l| l.longitude)
+            .ok_or_else(|| {
+                AstrologyError::InvalidArgument(format!(
+                    "unknown lot: {} (a lot can only use the lots before it)",
+                    name
+                ))
+            }),
+    }
+}
+
+/// Longitude of an angle
+fn angle_longitude(
+    angles: &[AngleData],
+    angle: Angle,
+) -> Result<f64, AstrologyError> {
+    angles
+        .iter()
+        .find(|a| a.angle == angle)
+        .map(|a| a.longitude)
+        .ok_or_else(|| {
+            AstrologyError::InvalidArgument(format!(
+                "{:?} isn't an angle of the chart",
+                angle
+            ))
+        })
+}
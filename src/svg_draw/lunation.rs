@@ -0,0 +1,377 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+//! Lunar phases, eclipses and prenatal syzygy
+//! The phases are found by Newton's method on the elongation of the Moon, the
+//! eclipses with the eclipse functions of the swiss ephemeris
+//! The Sun and the Moon are always computed, whatever ChartOptions::bodies
+use super::calc_options::Center;
+use super::chart_data::{
+    julian_day, set_ephe_path, signed_distance, DateTimeUtc,
+};
+use super::compute_chart::{ChartOptions, DataChartNatal};
+use super::swe_eclipse;
+use super::transit_search::zodiac_position;
+use crate::error::AstrologyError;
+use libswe_sys::sweconst::{Bodies, OptionalFlag};
+use libswe_sys::swerust::handler_swe17;
+use serde::{Deserialize, Serialize};
+
+// Mean motion of the Moon from the Sun in one day, in degree
+const SYNODIC_SPEED: f64 = 12.190_749;
+const LUNATION_ITERATIONS: usize = 10;
+const LUNATION_PRECISION: f64 = 0.000_000_1;
+
+/// Phase of the Moon
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LunarPhase {
+    NewMoon,
+    FirstQuarter,
+    FullMoon,
+    LastQuarter,
+}
+
+// Phases by elongation / 90°
+const PHASES: [LunarPhase; 4] = [
+    LunarPhase::NewMoon,
+    LunarPhase::FirstQuarter,
+    LunarPhase::FullMoon,
+    LunarPhase::LastQuarter,
+];
+
+impl LunarPhase {
+    /// Elongation of the Moon from the Sun, in degree
+    pub fn elongation(self) -> f64 {
+        match self {
+            LunarPhase::NewMoon => 0.0,
+            LunarPhase::FirstQuarter => 90.0,
+            LunarPhase::FullMoon => 180.0,
+            LunarPhase::LastQuarter => 270.0,
+        }
+    }
+}
+
+/// Exact phase of the Moon
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lunation {
+    pub phase: LunarPhase,
+    /// Julian day (UT) of the phase
+    pub julian_day: f64,
+    pub date: Option<DateTimeUtc>,
+    pub sun_longitude: f64,
+    pub moon_longitude: f64,
+}
+
+impl Lunation {
+    /// Degree of the lunation (longitude of the Moon)
+    pub fn longitude(&self) -> f64 {
+        self.moon_longitude
+    }
+}
+
+/// Solar or lunar eclipse
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum EclipseKind {
+    Solar,
+    Lunar,
+}
+
+/// Type of an eclipse
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum EclipseType {
+    Total,
+    Annular,
+    /// Annular-total (solar)
+    Hybrid,
+    Partial,
+    /// Lunar only
+    Penumbral,
+}
+
+/// Eclipse at its maximum
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Eclipse {
+    pub kind: EclipseKind,
+    pub eclipse_type: EclipseType,
+    /// Julian day (UT) of the maximum
+    pub julian_day: f64,
+    pub date: Option<DateTimeUtc>,
+    pub sun_longitude: f64,
+    pub moon_longitude: f64,
+}
+
+impl Eclipse {
+    /// Eclipse point: longitude of the Sun for a solar eclipse, of the Moon
+    /// for a lunar eclipse
+    pub fn longitude(&self) -> f64 {
+        match self.kind {
+            EclipseKind::Solar => self.sun_longitude,
+            EclipseKind::Lunar => self.moon_longitude,
+        }
+    }
+}
+
+/// New moons, full moons and quarters between start and end, sorted by date
+/// The longitudes are in the zodiac of options, the location of start is the
+/// observer of a topocentric chart
+pub fn lunar_phases(
+    start: DataChartNatal,
+    end: DataChartNatal,
+    path: &str,
+    options: &ChartOptions,
+) -> Result<Vec<Lunation>, AstrologyError> {
+    check_center(options)?;
+    let (julian_day_start, julian_day_end) = julian_day_range(&start, &end)?;
+    set_ephe_path(path)?;
+    // The observer is a global state of the swiss ephemeris
+    options.calc.set_topo(start.lat as f64, start.lng as f64);
+    let mut res: Vec<Lunation> = Vec::new();
+    let mut jd = julian_day_start;
+    loop {
+        let (e, _) = elongation(jd, options)?;
+        let phase = PHASES[((e / 90.0).floor() as usize + 1) % 4];
+        let guess =
+            jd + handler_swe17::degnorm(phase.elongation() - e) / SYNODIC_SPEED;
+        let julian_day_phase = lunation_julian_day(guess, phase, options)?;
+        if julian_day_phase > julian_day_end {
+            break;
+        }
+        if julian_day_phase >= julian_day_start {
+            res.push(lunation(phase, julian_day_phase, options)?);
+        }
+        // Next phase in ~7 days
+        jd = julian_day_phase + 1.0;
+    }
+    Ok(res)
+}
+
+/// Solar and lunar eclipses (maximum) between start and end, sorted by date
+/// The longitudes are in the zodiac of options, the location of start is the
+/// observer of a topocentric chart
+pub fn eclipses(
+    start: DataChartNatal,
+    end: DataChartNatal,
+    path: &str,
+    options: &ChartOptions,
+) -> Result<Vec<Eclipse>, AstrologyError> {
+    check_center(options)?;
+    let (julian_day_start, julian_day_end) = julian_day_range(&start, &end)?;
+    set_ephe_path(path)?;
+    // The observer is a global state of the swiss ephemeris
+    options.calc.set_topo(start.lat as f64, start.lng as f64);
+    let mut res: Vec<Eclipse> = Vec::new();
+    for kind in [EclipseKind::Solar, EclipseKind::Lunar].iter() {
+        let mut jd = julian_day_start;
+        loop {
+            let eclipse = eclipse_when(*kind, jd, false, options)?;
+            if eclipse.julian_day > julian_day_end {
+                break;
+            }
+            // Next eclipse in ~6 months (or ~1 month)
+            jd = eclipse.julian_day + 1.0;
+            res.push(eclipse);
+        }
+    }
+    res.sort_by(|a, b| {
+        a.julian_day
+            .partial_cmp(&b.julian_day)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    Ok(res)
+}
+
+/// Last new or full moon before the birth, in the zodiac of options
+pub fn prenatal_syzygy(
+    data: DataChartNatal,
+    path: &str,
+    options: &ChartOptions,
+) -> Result<Lunation, AstrologyError> {
+    set_ephe_path(path)?;
+    syzygy_before(
+        julian_day(&data)?,
+        (data.lat as f64, data.lng as f64),
+        options,
+    )
+}
+
+/// Last eclipse (solar or lunar) before a date, in the zodiac of options
+pub fn last_eclipse(
+    data: DataChartNatal,
+    path: &str,
+    options: &ChartOptions,
+) -> Result<Eclipse, AstrologyError> {
+    set_ephe_path(path)?;
+    eclipse_before(
+        julian_day(&data)?,
+        (data.lat as f64, data.lng as f64),
+        options,
+    )
+}
+
+/// Last new or full moon before julian_day, (lat, lng) is the observer of a
+/// topocentric chart
+pub(crate) fn syzygy_before(
+    julian_day: f64,
+    (lat, lng): (f64, f64),
+    options: &ChartOptions,
+) -> Result<Lunation, AstrologyError> {
+    check_center(options)?;
+    // The observer is a global state of the swiss ephemeris
+    options.calc.set_topo(lat, lng);
+    let (e, _) = elongation(julian_day, options)?;
+    let (phase, distance) = if e < 180.0 {
+        (LunarPhase::NewMoon, e)
+    } else {
+        (LunarPhase::FullMoon, e - 180.0)
+    };
+    let julian_day_phase = lunation_julian_day(
+        julian_day - distance / SYNODIC_SPEED,
+        phase,
+        options,
+    )?;
+    lunation(phase, julian_day_phase, options)
+}
+
+/// Last eclipse (solar or lunar) before julian_day, (lat, lng) is the
+/// observer of a topocentric chart
+pub(crate) fn eclipse_before(
+    julian_day: f64,
+    (lat, lng): (f64, f64),
+    options: &ChartOptions,
+) -> Result<Eclipse, AstrologyError> {
+    check_center(options)?;
+    // The observer is a global state of the swiss ephemeris
+    options.calc.set_topo(lat, lng);
+    let solar = eclipse_when(EclipseKind::Solar, julian_day, true, options)?;
+    let lunar = eclipse_when(EclipseKind::Lunar, julian_day, true, options)?;
+    if solar.julian_day > lunar.julian_day {
+        Ok(solar)
+    } else {
+        Ok(lunar)
+    }
+}
+
+/// Julian days of start and end, end can't be before start
+fn julian_day_range(
+    start: &DataChartNatal,
+    end: &DataChartNatal,
+) -> Result<(f64, f64), AstrologyError> {
+    let julian_day_start = julian_day(start)?;
+    let julian_day_end = julian_day(end)?;
+    if julian_day_end < julian_day_start {
+        return Err(AstrologyError::InvalidDateTime(format!(
+            "the end of the search ({}.{}.{}) is before the start ({}.{}.{})",
+            end.day, end.month, end.year, start.day, start.month, start.year
+        )));
+    }
+    Ok((julian_day_start, julian_day_end))
+}
+
+/// The lunations are geocentric or topocentric (the Sun of a heliocentric
+/// chart doesn't move)
+fn check_center(options: &ChartOptions) -> Result<(), AstrologyError> {
+    if options.calc.center == Center::Heliocentric {
+        return Err(AstrologyError::InvalidArgument(
+            "no lunation in a heliocentric chart".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Elongation of the Moon from the Sun (0-360) and its speed by day
+fn elongation(
+    julian_day: f64,
+    options: &ChartOptions,
+) -> Result<(f64, f64), AstrologyError> {
+    let sun = zodiac_position(Bodies::Sun, julian_day, options)?;
+    let moon = zodiac_position(Bodies::Moon, julian_day, options)?;
+    Ok((handler_swe17::degnorm(moon.0 - sun.0), moon.1 - sun.1))
+}
+
+/// Julian day of a phase close to julian_day
+fn lunation_julian_day(
+    julian_day: f64,
+    phase: LunarPhase,
+    options: &ChartOptions,
+) -> Result<f64, AstrologyError> {
+    let mut jd = julian_day;
+    for _ in 0..LUNATION_ITERATIONS {
+        let (e, speed) = elongation(jd, options)?;
+        let distance = signed_distance(e, phase.elongation());
+        if distance.abs() < LUNATION_PRECISION {
+            break;
+        }
+        jd += distance / speed;
+    }
+    Ok(jd)
+}
+
+/// Lunation at a julian day, the longitudes are in the zodiac of options
+fn lunation(
+    phase: LunarPhase,
+    julian_day: f64,
+    options: &ChartOptions,
+) -> Result<Lunation, AstrologyError> {
+    Ok(Lunation {
+        phase,
+        julian_day,
+        date: DateTimeUtc::from_julian_day(julian_day),
+        sun_longitude: zodiac_position(Bodies::Sun, julian_day, options)?.0,
+        moon_longitude: zodiac_position(Bodies::Moon, julian_day, options)?.0,
+    })
+}
+
+/// Next (or previous if backward) eclipse after julian_day, the longitudes
+/// are in the zodiac of options
+fn eclipse_when(
+    kind: EclipseKind,
+    julian_day: f64,
+    backward: bool,
+    options: &ChartOptions,
+) -> Result<Eclipse, AstrologyError> {
+    let iflag = OptionalFlag::SwissEph as i32;
+    let res = match kind {
+        EclipseKind::Solar => {
+            swe_eclipse::sol_eclipse_when_glob(julian_day, iflag, backward)
+        },
+        EclipseKind::Lunar => {
+            swe_eclipse::lun_eclipse_when(julian_day, iflag, backward)
+        },
+    };
+    if res.result < 0 {
+        return Err(AstrologyError::CalculationFailed(res.serr));
+    }
+    let eclipse_type = if res.result & swe_eclipse::ECL_ANNULAR_TOTAL != 0 {
+        EclipseType::Hybrid
+    } else if res.result & swe_eclipse::ECL_TOTAL != 0 {
+        EclipseType::Total
+    } else if res.result & swe_eclipse::ECL_ANNULAR != 0 {
+        EclipseType::Annular
+    } else if res.result & swe_eclipse::ECL_PARTIAL != 0 {
+        EclipseType::Partial
+    } else {
+        EclipseType::Penumbral
+    };
+    let julian_day = res.tret[0];
+    Ok(Eclipse {
+        kind,
+        eclipse_type,
+        julian_day,
+        date: DateTimeUtc::from_julian_day(julian_day),
+        sun_longitude: zodiac_position(Bodies::Sun, julian_day, options)?.0,
+        moon_longitude: zodiac_position(Bodies::Moon, julian_day, options)?.0,
+    })
+}
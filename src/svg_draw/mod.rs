@@ -15,22 +15,1452 @@
  * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
  */
 pub mod angles;
+pub mod aspect_config;
+pub mod aspectarian;
 pub mod aspects;
 pub mod bodies;
+pub mod body_selection;
+pub mod calc_options;
+pub mod chart_data;
+pub mod chart_style;
 pub mod compute_chart;
+pub mod dignities;
+pub mod ephemeris;
+pub mod fixed_stars;
+pub mod house_system;
 pub mod houses;
+pub mod ingress;
+pub mod lots;
+pub mod lunation;
 pub mod numbers;
+pub mod progression;
+pub mod returns;
+pub mod sect;
+pub(crate) mod serde_sweconst;
+pub mod sidereal;
 pub mod svg_draw;
+pub(crate) mod swe_eclipse;
+pub(crate) mod swe_fixstar;
+pub(crate) mod swe_rise;
+pub(crate) mod swe_sidereal;
+pub(crate) mod swe_topo;
+pub mod time_lords;
+pub mod transit_search;
 pub mod zodiacs;
+pub use self::aspect_config::{
+    AspectConfig, AspectFamily, AspectOrb, BodieOrb, ChartAspect,
+};
+pub use self::aspectarian::{aspectarian, aspectarian_transit};
+pub use self::body_selection::{BodySelection, NodeKind};
+pub use self::calc_options::{CalcOptions, Center};
+pub use self::chart_data::{
+    aspect_record, compute_composite, compute_davison, compute_natal,
+    compute_synastry, compute_transit, midpoint, synastry_aspects,
+    transit_aspects, AngleData, AspectRecord, BodieData, ChartData, ChartPoint,
+    CuspData, DateTimeUtc, SynastryData, TransitData,
+};
+pub use self::chart_style::{AspectColor, BodieColor, ChartStyle};
 pub use self::compute_chart::{
-    all_aspects, chart, chart_svg, chart_svg_with_transit, chart_with_transit,
+    all_aspects, all_aspects_style, aspectarian_svg,
+    aspectarian_svg_with_transit, chart, chart_composite, chart_davison,
+    chart_directed, chart_progressed, chart_return, chart_svg,
+    chart_svg_composite, chart_svg_davison, chart_svg_directed,
+    chart_svg_progressed, chart_svg_return, chart_svg_synastry,
+    chart_svg_with_transit, chart_synastry, chart_with_transit, ChartOptions,
     DataChartNatal, DataObjectAspectSvg, DataObjectSvg, DataObjectType,
 };
+pub use self::dignities::{
+    bodie_dignities, compute_dignities, mutual_receptions, rulers,
+    BodieDignities, DignitiesData, Dignity, MutualReception, ReceptionKind,
+    Rulers, CLASSICAL_PLANETS,
+};
+pub use self::ephemeris::{
+    ephemeris, ephemeris_csv, ephemeris_json, EphemerisPosition, EphemerisRow,
+    Station,
+};
+pub use self::fixed_stars::{
+    compute_fixed_stars, star_conjunctions, FixedStarData, FixedStarOptions,
+    FixedStarsData, ParanAngle, StarConjunction, StarParan, FIXED_STARS,
+};
+pub use self::house_system::HouseSystem;
+pub use self::ingress::{ingresses_stations, BodieEvent, BodieEventKind};
+pub use self::lots::{
+    hermetic_lots, LotData, LotFormula, LotOptions, LotPoint,
+};
+pub use self::lunation::{
+    eclipses, last_eclipse, lunar_phases, prenatal_syzygy, Eclipse,
+    EclipseKind, EclipseType, LunarPhase, Lunation,
+};
+pub use self::progression::{
+    compute_directed, compute_progressed, solar_arc, DirectedAspect,
+    DirectedData, DirectionArc, DirectionOptions, ProgressedAngles,
+    ProgressedData,
+};
+pub use self::returns::{lunar_return, solar_return, ReturnData};
+pub use self::sect::{
+    day_ruler, is_diurnal, planetary_hour, sect_label, PlanetaryHour,
+    SectLabelPart,
+};
+pub use self::sidereal::{Ayanamsa, Zodiac};
+pub use self::time_lords::{
+    firdaria, profections, profections_at, zodiacal_releasing, FirdarPeriod,
+    Profection, ReleasingPeriod,
+};
+pub use self::transit_search::{
+    transit_search, TransitEvent, TransitEventKind,
+};
 
 #[cfg(test)]
 mod tests {
+    use super::chart_data::{julian_day, separation, CHART_BODIES};
+    use super::progression::NAIBOD_ARC;
+    use super::{
+        aspect_record, midpoint, AspectConfig, AspectFamily, ChartAspect,
+        ChartPoint,
+    };
+    use super::{
+        aspectarian, aspectarian_transit, bodie_dignities, chart,
+        chart_directed, compute_composite, compute_davison, compute_dignities,
+        compute_directed, compute_fixed_stars, compute_natal,
+        compute_progressed, compute_synastry, compute_transit, eclipses,
+        ephemeris, ephemeris_csv, firdaria, ingresses_stations, last_eclipse,
+        lunar_phases, lunar_return, prenatal_syzygy, profections,
+        profections_at, rulers, sect_label, solar_return, transit_search,
+        zodiacal_releasing, Ayanamsa, BodySelection, CalcOptions, Center,
+        ChartOptions, ChartStyle, DataChartNatal, DataObjectSvg, DateTimeUtc,
+        Dignity, DirectionOptions, EclipseKind, EclipseType, FixedStarOptions,
+        HouseSystem, LotFormula, LotOptions, LotPoint, LunarPhase, NodeKind,
+        ReceptionKind, SectLabelPart, Zodiac, FIXED_STARS,
+    };
+    use crate::AstrologyError;
+    use libswe_sys::sweconst::{Angle, Bodies, Language, OptionalFlag, Signs};
+    use strum::IntoEnumIterator;
+
+    fn data(day: u32, lat: f32) -> DataChartNatal {
+        DataChartNatal {
+            year: 2000,
+            month: 2,
+            day,
+            hour: 12,
+            min: 0,
+            sec: 0.0,
+            lat,
+            lng: 6.14,
+            time_zone: 1.0,
+        }
+    }
+
     #[test]
     fn it_works() {
         assert_eq!(2 + 2, 4);
     }
+
+    #[test]
+    fn house_systems() {
+        let cusps = |house_system, lat| {
+            let options = ChartOptions {
+                house_system,
+                bodies: "Sun, Moon".parse().unwrap(),
+                ..ChartOptions::default()
+            };
+            let chart = compute_natal(data(1, lat), ".", &options).unwrap();
+            let cusps: Vec<f64> =
+                chart.cusps.iter().map(|c| c.longitude).collect();
+            (chart.house_system, cusps)
+        };
+        let (_, placidus) = cusps(HouseSystem::Placidus, 46.2);
+        let (_, regiomontanus) = cusps(HouseSystem::Regiomontanus, 46.2);
+        let (_, whole_sign) = cusps(HouseSystem::WholeSign, 46.2);
+        // Same Asc, the intermediate cusps differ
+        assert!(separation(placidus[0], regiomontanus[0]) < 1e-9);
+        assert!(separation(placidus[1], regiomontanus[1]) > 1.0);
+        // Whole sign: 0° of the sign of the Asc, then one sign by house
+        assert!(whole_sign[0] <= placidus[0]);
+        assert!(placidus[0] - whole_sign[0] < 30.0);
+        for (i, c) in whole_sign.iter().enumerate() {
+            assert!((c % 30.0).abs() < 1e-9);
+            assert!(separation(*c, whole_sign[0] + i as f64 * 30.0) < 1e-9);
+        }
+        // Placidus inside the polar circle: Porphyry
+        let (house_system, polar) = cusps(HouseSystem::Placidus, 70.0);
+        assert_eq!(house_system, HouseSystem::Porphyry);
+        assert_eq!(polar, cusps(HouseSystem::Porphyry, 70.0).1);
+        let (house_system, _) = cusps(HouseSystem::Regiomontanus, 70.0);
+        assert_eq!(house_system, HouseSystem::Regiomontanus);
+    }
+
+    #[test]
+    fn error_missing_path() {
+        let res = compute_natal(data(1, 46.2), "/no/path", &Default::default());
+        match res {
+            Err(AstrologyError::MissingEphemerisFiles(_)) => {},
+            _ => panic!("MissingEphemerisFiles expected"),
+        }
+    }
+
+    #[test]
+    fn error_invalid_date() {
+        let res = compute_natal(data(30, 46.2), ".", &ChartOptions::default());
+        match res {
+            Err(AstrologyError::InvalidDateTime(_)) => {},
+            _ => panic!("InvalidDateTime expected"),
+        }
+    }
+
+    #[test]
+    fn error_invalid_coordinates() {
+        let res = compute_natal(data(1, 91.0), ".", &ChartOptions::default());
+        match res {
+            Err(AstrologyError::InvalidCoordinates(_)) => {},
+            _ => panic!("InvalidCoordinates expected"),
+        }
+    }
+
+    #[test]
+    fn style_json_default() {
+        let options: ChartOptions = serde_json::from_str(
+            r##"{"style": {"background": "#101010", "circle": "white"}}"##,
+        )
+        .unwrap();
+        assert_eq!(options.style.background, "#101010");
+        assert_eq!(options.style.circle, "white");
+        assert_eq!(options.style.text, ChartStyle::light().text);
+        assert_eq!(options.style.aspect_color(ChartAspect::Trine), "blue");
+        assert_eq!(options.style.bodie_color(Bodies::Sun), "#FFA300");
+        assert_eq!(ChartStyle::dark().background, "#000000");
+    }
+
+    #[test]
+    fn aspect_config_orbs() {
+        let sun = ChartPoint::Bodie(Bodies::Sun);
+        let mars = ChartPoint::Bodie(Bodies::Mars);
+        let venus = ChartPoint::Bodie(Bodies::Venus);
+        let config = AspectConfig::default();
+        // Default: orbs of libswe_sys, no quintile
+        assert_eq!(config.find_aspect(mars, 10.0, venus, 140.0), None);
+        assert_eq!(config.find_aspect(mars, 10.0, venus, 82.0), None);
+        let config = config
+            .with_family(AspectFamily::Quintile)
+            .with_bodie(Bodies::Sun, 1.5);
+        assert_eq!(
+            config.find_aspect(mars, 10.0, venus, 82.0),
+            Some((ChartAspect::Quintile, 0.0))
+        );
+        // Sun: orb of the trine 7 * 1.5
+        assert_eq!(
+            config.find_aspect(sun, 10.0, venus, 140.0),
+            Some((ChartAspect::Trine, 10.0))
+        );
+        let json = AspectConfig::from_json(
+            r#"{"aspects": [{"aspect": "Septile", "orb": 1.5}],
+                "bodies": [{"bodie": "Moon", "factor": 2.0}]}"#,
+        )
+        .unwrap();
+        assert_eq!(json.orb(ChartAspect::Septile, mars, venus), Some(1.5));
+        assert_eq!(
+            json.orb(
+                ChartAspect::Septile,
+                ChartPoint::Bodie(Bodies::Moon),
+                mars
+            ),
+            Some(3.0)
+        );
+        assert_eq!(json.orb(ChartAspect::Conjunction, mars, venus), None);
+        match AspectConfig::from_json("{\"aspects\": 1}") {
+            Err(AstrologyError::InvalidArgument(_)) => {},
+            _ => panic!("InvalidArgument expected"),
+        }
+    }
+
+    #[test]
+    fn aspect_record_applying() {
+        let config = AspectConfig::default();
+        let mars = ChartPoint::Bodie(Bodies::Mars);
+        let venus = ChartPoint::Bodie(Bodies::Venus);
+        // Venus faster than Mars and 2° before the trine
+        let r =
+            aspect_record(&config, mars, 10.0, 0.5, venus, 128.0, 1.0).unwrap();
+        assert_eq!(r.aspect, ChartAspect::Trine);
+        assert!(r.applying);
+        assert!((r.exact_in_days.unwrap() - 4.0).abs() < 1e-9);
+        // 2° after the trine
+        let r =
+            aspect_record(&config, mars, 10.0, 0.5, venus, 132.0, 1.0).unwrap();
+        assert!(!r.applying);
+        assert!((r.exact_in_days.unwrap() + 4.0).abs() < 1e-9);
+        // Conjunction on 0° Aries, Venus ahead and faster
+        let r =
+            aspect_record(&config, mars, 359.0, 0.5, venus, 1.0, 1.0).unwrap();
+        assert_eq!(r.aspect, ChartAspect::Conjunction);
+        assert!(!r.applying);
+        // Same speed
+        let r =
+            aspect_record(&config, mars, 10.0, 1.0, venus, 12.0, 1.0).unwrap();
+        assert_eq!(r.exact_in_days, None);
+    }
+
+    #[test]
+    fn aspectarian_cells() {
+        let options = ChartOptions {
+            bodies: "Sun, Moon, Mercury, Venus, Mars".parse().unwrap(),
+            ..ChartOptions::default()
+        };
+        let chart = compute_natal(data(1, 46.2), ".", &options).unwrap();
+        // Borders of the cells
+        let cells = |svg: &str, cell: f32| {
+            svg.matches(&format!("<rect fill=\"none\" height=\"{}\"", cell))
+                .count()
+        };
+        let orb_labels = |svg: &str| {
+            svg.matches("'a\n</text>").count()
+                + svg.matches("'s\n</text>").count()
+        };
+        // 5 bodies and Asc/Mc: triangle of 7 * 6 / 2 cells, one orb by
+        // aspect of the chart
+        let svg = aspectarian(&chart, 700.0, &options.style, Language::English)
+            .to_string();
+        assert_eq!(cells(&svg, 100.0), 21);
+        assert!(!chart.aspects.is_empty());
+        assert_eq!(orb_labels(&svg), chart.aspects.len());
+        for r in chart.aspects.iter() {
+            let orb = format!(
+                ">\n{}°{:02}'{}\n</text>",
+                r.orb as i32,
+                (r.orb.fract() * 60.0) as i32,
+                if r.applying { "a" } else { "s" }
+            );
+            assert!(svg.contains(&orb));
+        }
+        // Natal (bodies and Asc/Mc) x transit (bodies): 7 x 5 cells of
+        // 700 / (7 + 1)
+        let transit = DataChartNatal {
+            year: 2020,
+            ..data(1, 46.2)
+        };
+        let res =
+            compute_transit(data(1, 46.2), transit, ".", &options).unwrap();
+        let svg = aspectarian_transit(
+            &res.natal,
+            &res.transit.bodies,
+            &res.aspects,
+            700.0,
+            &options.style,
+            Language::French,
+        )
+        .to_string();
+        assert_eq!(cells(&svg, 87.5), 35);
+        assert_eq!(orb_labels(&svg), res.aspects.len());
+    }
+
+    #[test]
+    fn transit_time_zone() {
+        // 12:00 UTC+1 (natal time zone) and 06:00 UTC-5 are the same moment
+        let natal = data(1, 46.2);
+        let transit = DataChartNatal {
+            hour: 6,
+            time_zone: -5.0,
+            ..data(1, 40.7)
+        };
+        let jd_natal = julian_day(&natal).unwrap();
+        let jd_transit = julian_day(&transit).unwrap();
+        assert!((jd_natal - jd_transit).abs() < 1e-6);
+        let jd_natal_time_zone = julian_day(&DataChartNatal {
+            time_zone: natal.time_zone,
+            ..transit
+        })
+        .unwrap();
+        assert!((jd_natal_time_zone + 0.25 - jd_natal).abs() < 1e-6);
+    }
+
+    #[test]
+    fn transit_time_zone_positions() {
+        let options = ChartOptions {
+            bodies: "Sun, Moon".parse().unwrap(),
+            ..ChartOptions::default()
+        };
+        let transit = |hour, time_zone| {
+            let transit = DataChartNatal {
+                hour,
+                time_zone,
+                lng: -74.0,
+                ..data(1, 40.7)
+            };
+            compute_transit(data(1, 46.2), transit, ".", &options)
+                .unwrap()
+                .transit
+        };
+        let utc_plus_1 = transit(12, 1.0);
+        let utc_minus_5 = transit(12, -5.0);
+        let same_moment = transit(6, -5.0);
+        // Same local time in two time zones: 6 hours later, the Moon moves
+        // about 3°
+        assert!(
+            (utc_minus_5.julian_day - utc_plus_1.julian_day - 0.25).abs()
+                < 1e-6
+        );
+        let moon =
+            |c: &super::ChartData| c.bodie_longitude(Bodies::Moon).unwrap();
+        let moved = separation(moon(&utc_plus_1), moon(&utc_minus_5));
+        assert!(moved > 2.5 && moved < 4.5);
+        // Same moment in UT: same transit positions
+        assert!((same_moment.julian_day - utc_plus_1.julian_day).abs() < 1e-6);
+        for bodie in &[Bodies::Sun, Bodies::Moon] {
+            assert!(
+                separation(
+                    same_moment.bodie_longitude(*bodie).unwrap(),
+                    utc_plus_1.bodie_longitude(*bodie).unwrap()
+                ) < 1e-6
+            );
+        }
+    }
+
+    #[test]
+    fn transit_data_checked() {
+        // The time zone and the coordinates of the transit are the ones of the
+        // transit, not of the natal chart
+        let options = ChartOptions::default();
+        let transit = DataChartNatal {
+            time_zone: 20.0,
+            ..data(1, 46.2)
+        };
+        match compute_transit(data(1, 46.2), transit, ".", &options) {
+            Err(AstrologyError::InvalidDateTime(_)) => {},
+            _ => panic!("InvalidDateTime expected"),
+        }
+        let transit = DataChartNatal {
+            lat: 91.0,
+            ..data(1, 46.2)
+        };
+        match compute_transit(data(1, 46.2), transit, ".", &options) {
+            Err(AstrologyError::InvalidCoordinates(_)) => {},
+            _ => panic!("InvalidCoordinates expected"),
+        }
+    }
+
+    #[test]
+    fn ephemeris_stations() {
+        let day = |month, day| DataChartNatal {
+            year: 2020,
+            month,
+            day,
+            hour: 0,
+            time_zone: 0.0,
+            ..data(1, 0.0)
+        };
+        let options = ChartOptions {
+            bodies: "Sun, Mercury".parse().unwrap(),
+            ..ChartOptions::default()
+        };
+        let rows =
+            ephemeris(day(2, 10), day(3, 15), 1.0, ".", &options).unwrap();
+        assert_eq!(rows.len(), 35);
+        assert!(rows.iter().all(|r| r.positions.len() == 2));
+        // Mercury stations retrograde the 17.02.2020 at 00:54 UT and direct
+        // the 10.03.2020 at 03:49 UT
+        let stations: Vec<(String, &str)> = rows
+            .iter()
+            .flat_map(|r| {
+                r.positions.iter().filter_map(move |p| {
+                    p.station.map(|s| (r.date.unwrap().to_string(), s.marker()))
+                })
+            })
+            .collect();
+        assert_eq!(
+            stations,
+            vec![
+                ("17.02.2020 00:00:00".to_string(), "SR"),
+                ("10.03.2020 00:00:00".to_string(), "SD")
+            ]
+        );
+        assert!(ephemeris_csv(&rows).contains(",SR\n"));
+        // Sidereal: the tropical longitude minus the ayanamsa
+        let sidereal = ChartOptions {
+            zodiac: Zodiac::Sidereal(Ayanamsa::Lahiri),
+            ..options
+        };
+        let rows_sidereal =
+            ephemeris(day(2, 10), day(2, 10), 1.0, ".", &sidereal).unwrap();
+        let ayanamsa = sidereal.zodiac.ayanamsa(rows[0].julian_day).unwrap();
+        assert!(
+            separation(
+                rows_sidereal[0].positions[0].longitude + ayanamsa,
+                rows[0].positions[0].longitude
+            ) < 1e-9
+        );
+    }
+
+    #[test]
+    fn ingress_station_timing() {
+        let day = |month, day| DataChartNatal {
+            year: 2020,
+            month,
+            day,
+            hour: 0,
+            time_zone: 0.0,
+            ..data(1, 0.0)
+        };
+        let options = ChartOptions {
+            bodies: "Sun, Mercury".parse().unwrap(),
+            ..ChartOptions::default()
+        };
+        let events =
+            ingresses_stations(day(2, 15), day(3, 25), ".", &options).unwrap();
+        let kinds: Vec<(Bodies, String, bool)> = events
+            .iter()
+            .map(|e| (e.bodie, format!("{:?}", e.kind), e.retrograde))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (Bodies::Mercury, "StationRetrograde".to_string(), false),
+                (Bodies::Sun, "Ingress(Pisces)".to_string(), false),
+                (Bodies::Mercury, "Ingress(Aquarius)".to_string(), true),
+                (Bodies::Mercury, "StationDirect".to_string(), false),
+                (Bodies::Mercury, "Ingress(Pisces)".to_string(), false),
+                (Bodies::Sun, "Ingress(Aries)".to_string(), false),
+            ]
+        );
+        // March equinox 2020: 20.03.2020 03:49 UT
+        let equinox = julian_day(&DataChartNatal {
+            hour: 3,
+            min: 49,
+            ..day(3, 20)
+        })
+        .unwrap();
+        assert!((events[5].julian_day - equinox).abs() < 2.0 / 1440.0);
+        // Sidereal zodiac: the Sun enters Aries about 24 days later
+        let sidereal = ChartOptions {
+            zodiac: Zodiac::Sidereal(Ayanamsa::Lahiri),
+            bodies: "Sun".parse().unwrap(),
+            ..options
+        };
+        let events =
+            ingresses_stations(day(4, 1), day(4, 30), ".", &sidereal).unwrap();
+        assert_eq!(events.len(), 1);
+        assert!((events[0].julian_day - equinox - 24.4).abs() < 0.5);
+    }
+
+    #[test]
+    fn lunation_dates() {
+        let day = |month, day| DataChartNatal {
+            year: 2020,
+            month,
+            day,
+            hour: 0,
+            time_zone: 0.0,
+            ..data(1, 0.0)
+        };
+        let at = |month, d, hour, min| {
+            julian_day(&DataChartNatal {
+                hour,
+                min,
+                ..day(month, d)
+            })
+            .unwrap()
+        };
+        let options = ChartOptions::default();
+        // New moon: 24.01.2020 21:42 UT
+        let phases =
+            lunar_phases(day(1, 20), day(1, 28), ".", &options).unwrap();
+        assert_eq!(phases.len(), 1);
+        assert_eq!(phases[0].phase, LunarPhase::NewMoon);
+        assert!(
+            (phases[0].julian_day - at(1, 24, 21, 42)).abs() < 2.0 / 1440.0
+        );
+        assert!(
+            (phases[0].sun_longitude - phases[0].moon_longitude).abs() < 1e-3
+        );
+        // Penumbral lunar eclipse 10.01.2020 19:10 UT, lunar eclipse
+        // 05.06.2020 19:25 UT and annular solar eclipse 21.06.2020 06:40 UT
+        let res = eclipses(day(1, 1), day(7, 1), ".", &options).unwrap();
+        let kinds: Vec<(EclipseKind, EclipseType)> =
+            res.iter().map(|e| (e.kind, e.eclipse_type)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (EclipseKind::Lunar, EclipseType::Penumbral),
+                (EclipseKind::Lunar, EclipseType::Penumbral),
+                (EclipseKind::Solar, EclipseType::Annular),
+            ]
+        );
+        assert!((res[0].julian_day - at(1, 10, 19, 10)).abs() < 2.0 / 1440.0);
+        assert!((res[1].julian_day - at(6, 5, 19, 25)).abs() < 2.0 / 1440.0);
+        assert!((res[2].julian_day - at(6, 21, 6, 40)).abs() < 2.0 / 1440.0);
+        // Sidereal zodiac: same instant, the longitudes minus the ayanamsa
+        let zodiac = Zodiac::Sidereal(Ayanamsa::Lahiri);
+        let sidereal = ChartOptions {
+            zodiac,
+            ..ChartOptions::default()
+        };
+        let res_sidereal =
+            eclipses(day(6, 15), day(7, 1), ".", &sidereal).unwrap();
+        assert_eq!(res_sidereal.len(), 1);
+        let ayanamsa = zodiac.ayanamsa(res[2].julian_day).unwrap();
+        assert!((res_sidereal[0].julian_day - res[2].julian_day).abs() < 1e-6);
+        assert!(
+            (res_sidereal[0].sun_longitude + ayanamsa - res[2].sun_longitude)
+                .abs()
+                < 1e-6
+        );
+        // Topocentric markers of a transit chart: the syzygy from the birth
+        // place, the eclipse from the place of the transit
+        let topocentric = ChartOptions {
+            bodies: "Sun, Moon".parse().unwrap(),
+            calc: CalcOptions {
+                center: Center::Topocentric,
+                ..CalcOptions::default()
+            },
+            lunation_markers: true,
+            ..ChartOptions::default()
+        };
+        let transit = || DataChartNatal {
+            year: 2020,
+            lat: -33.9,
+            lng: 151.2,
+            ..data(1, 0.0)
+        };
+        let res = compute_transit(data(1, 46.2), transit(), ".", &topocentric)
+            .unwrap();
+        let syzygy = prenatal_syzygy(data(1, 46.2), ".", &topocentric).unwrap();
+        let eclipse = last_eclipse(transit(), ".", &topocentric).unwrap();
+        let marker_syzygy = res.prenatal_syzygy.unwrap();
+        let marker_eclipse = res.last_eclipse.unwrap();
+        assert!(
+            (marker_syzygy.moon_longitude - syzygy.moon_longitude).abs() < 1e-9
+        );
+        assert!(
+            (marker_eclipse.moon_longitude - eclipse.moon_longitude).abs()
+                < 1e-9
+        );
+        // The parallax of the Moon: not the geocentric longitudes
+        let geocentric =
+            last_eclipse(transit(), ".", &ChartOptions::default()).unwrap();
+        assert!(
+            separation(geocentric.moon_longitude, eclipse.moon_longitude) > 0.1
+        );
+    }
+
+    #[test]
+    fn synastry_aspects() {
+        let options = ChartOptions {
+            bodies: "Sun, Moon, Mercury, Venus, Mars".parse().unwrap(),
+            ..ChartOptions::default()
+        };
+        let data_b = || DataChartNatal {
+            year: 1990,
+            month: 7,
+            hour: 20,
+            ..data(15, 48.85)
+        };
+        let res =
+            compute_synastry(data(1, 46.2), data_b(), ".", &options).unwrap();
+        let longitude = |chart: &super::ChartData, point| match point {
+            ChartPoint::Bodie(b) => chart.bodie_longitude(b).unwrap(),
+            ChartPoint::Angle(a) => chart.angle_longitude(a).unwrap(),
+        };
+        assert!(!res.aspects.is_empty());
+        // body_a is a point of chart_a, body_b a point of chart_b
+        for r in res.aspects.iter() {
+            let distance = separation(
+                longitude(&res.chart_a, r.body_a),
+                longitude(&res.chart_b, r.body_b),
+            );
+            assert!((distance - r.aspect.angle()).abs() - r.orb < 1e-6);
+        }
+        // Same aspects the other way round
+        let res_b =
+            compute_synastry(data_b(), data(1, 46.2), ".", &options).unwrap();
+        assert_eq!(res_b.aspects.len(), res.aspects.len());
+        for r in res.aspects.iter() {
+            assert!(res_b.aspects.iter().any(|b| b.body_a == r.body_b
+                && b.body_b == r.body_a
+                && b.aspect == r.aspect));
+        }
+    }
+
+    #[test]
+    fn composite_davison() {
+        let options = ChartOptions {
+            bodies: "Sun, Moon, Venus".parse().unwrap(),
+            ..ChartOptions::default()
+        };
+        let data_b = || DataChartNatal {
+            hour: 18,
+            lng: -74.0,
+            time_zone: -5.0,
+            ..data(11, 40.7)
+        };
+        let chart_a = compute_natal(data(1, 46.2), ".", &options).unwrap();
+        let chart_b = compute_natal(data_b(), ".", &options).unwrap();
+        // Composite: midpoints of the bodies and of the Asc, Desc opposite
+        let composite =
+            compute_composite(data(1, 46.2), data_b(), ".", &options).unwrap();
+        for bodie in &[Bodies::Sun, Bodies::Moon, Bodies::Venus] {
+            assert!(
+                separation(
+                    composite.bodie_longitude(*bodie).unwrap(),
+                    midpoint(
+                        chart_a.bodie_longitude(*bodie).unwrap(),
+                        chart_b.bodie_longitude(*bodie).unwrap()
+                    )
+                ) < 1e-9
+            );
+        }
+        let asc = composite.angle_longitude(Angle::Asc).unwrap();
+        assert!(
+            separation(
+                asc,
+                midpoint(
+                    chart_a.angle_longitude(Angle::Asc).unwrap(),
+                    chart_b.angle_longitude(Angle::Asc).unwrap()
+                )
+            ) < 1e-9
+        );
+        let desc = composite.angle_longitude(Angle::Desc).unwrap();
+        assert!((separation(asc, desc) - 180.0).abs() < 1e-9);
+        // Davison: chart at the midpoint in time and space
+        let davison =
+            compute_davison(data(1, 46.2), data_b(), ".", &options).unwrap();
+        let julian_day_a = julian_day(&data(1, 46.2)).unwrap();
+        let julian_day_b = julian_day(&data_b()).unwrap();
+        assert!(
+            (davison.julian_day - (julian_day_a + julian_day_b) / 2.0).abs()
+                < 1e-9
+        );
+        // 1.02.2000 11:00 UT and 11.02.2000 23:00 UT: 6.02.2000 17:00 UT
+        let date = DateTimeUtc::from_julian_day(davison.julian_day).unwrap();
+        assert_eq!(date.to_string(), "06.02.2000 17:00:00");
+        // The Sun moves ~1°/day, the Sun of the Davison is close to the
+        // midpoint of the Suns
+        assert!(
+            separation(
+                davison.bodie_longitude(Bodies::Sun).unwrap(),
+                midpoint(
+                    chart_a.bodie_longitude(Bodies::Sun).unwrap(),
+                    chart_b.bodie_longitude(Bodies::Sun).unwrap()
+                )
+            ) < 0.05
+        );
+    }
+
+    #[test]
+    fn progressed_sun() {
+        let options = ChartOptions {
+            bodies: "Sun, Moon".parse().unwrap(),
+            ..ChartOptions::default()
+        };
+        let target = DataChartNatal {
+            year: 2030,
+            ..data(1, 46.2)
+        };
+        let res =
+            compute_progressed(data(1, 46.2), target, ".", &options).unwrap();
+        assert!((res.years - 30.0).abs() < 0.01);
+        // One day after the birth by year of life
+        assert!(
+            (res.progressed.julian_day - res.natal.julian_day - res.years)
+                .abs()
+                < 1e-9
+        );
+        // The Sun moves ~1° by day in February, so ~1° by year
+        let arc = separation(
+            res.progressed.bodie_longitude(Bodies::Sun).unwrap(),
+            res.natal.bodie_longitude(Bodies::Sun).unwrap(),
+        );
+        assert!(arc > 29.5 && arc < 31.0);
+        // Naibod angles: natal Mc + 0.985647° by year
+        let mc = separation(
+            res.progressed.angle_longitude(Angle::Mc).unwrap(),
+            res.natal.angle_longitude(Angle::Mc).unwrap(),
+        );
+        assert!((mc - res.years * NAIBOD_ARC).abs() < 1e-3);
+    }
+
+    #[test]
+    fn directed_perfection() {
+        let options = ChartOptions {
+            bodies: "Sun, Moon, Mercury, Venus, Mars".parse().unwrap(),
+            ..ChartOptions::default()
+        };
+        let target = |year| DataChartNatal {
+            year,
+            ..data(1, 46.2)
+        };
+        let res = compute_directed(data(1, 46.2), target(2030), ".", &options)
+            .unwrap();
+        assert_eq!(res.directed.julian_day, julian_day(&target(2030)).unwrap());
+        assert!(res.directed.planetary_hour.is_none());
+        assert!(!res.aspects.is_empty());
+        for a in res.aspects.iter() {
+            assert!(a.record.orb <= 1.0);
+            // The directions at the date of perfection: exact aspect
+            let exact = a.exact.unwrap();
+            let at_exact = compute_directed(
+                data(1, 46.2),
+                DataChartNatal {
+                    year: exact.year,
+                    month: exact.month,
+                    day: exact.day,
+                    hour: exact.hour,
+                    min: exact.min,
+                    sec: exact.sec as f32,
+                    lat: 46.2,
+                    lng: 6.14,
+                    time_zone: 0.0,
+                },
+                ".",
+                &options,
+            )
+            .unwrap();
+            let record = at_exact
+                .aspects
+                .iter()
+                .find(|b| {
+                    b.record.body_a == a.record.body_a
+                        && b.record.body_b == a.record.body_b
+                })
+                .unwrap();
+            assert_eq!(record.record.aspect, a.record.aspect);
+            assert!(record.record.orb < 1e-3);
+            assert!(
+                (record.exact_julian_day - a.exact_julian_day).abs() < 1e-3
+            );
+        }
+        // The natal orbs would keep an aspect for years
+        let wide = ChartOptions {
+            directions: DirectionOptions {
+                orb: 8.0,
+                ..DirectionOptions::default()
+            },
+            ..options.clone()
+        };
+        let res_wide =
+            compute_directed(data(1, 46.2), target(2030), ".", &wide).unwrap();
+        assert!(res_wide.aspects.len() > res.aspects.len());
+        // The solar arc is measured between two geocentric Suns, whatever the
+        // center of the chart
+        let calc = |center| ChartOptions {
+            calc: CalcOptions {
+                center,
+                ..CalcOptions::default()
+            },
+            ..options.clone()
+        };
+        let topocentric = compute_directed(
+            data(1, 46.2),
+            target(2030),
+            ".",
+            &calc(Center::Topocentric),
+        )
+        .unwrap();
+        assert!((topocentric.arc - res.arc).abs() < 1e-9);
+        match compute_directed(
+            data(1, 46.2),
+            target(2030),
+            ".",
+            &calc(Center::Heliocentric),
+        ) {
+            Err(AstrologyError::InvalidArgument(_)) => {},
+            _ => panic!("InvalidArgument expected"),
+        }
+    }
+
+    #[test]
+    fn directed_angle_aspect_line() {
+        let options = ChartOptions {
+            bodies: "Sun, Moon, Mercury, Venus, Mars".parse().unwrap(),
+            directions: DirectionOptions {
+                orb: 8.0,
+                ..DirectionOptions::default()
+            },
+            ..ChartOptions::default()
+        };
+        let target = || DataChartNatal {
+            year: 2030,
+            ..data(1, 46.2)
+        };
+        let max_size = 1000.0;
+        let res =
+            compute_directed(data(1, 46.2), target(), ".", &options).unwrap();
+        let objects = chart_directed(
+            max_size,
+            data(1, 46.2),
+            target(),
+            ".",
+            Language::English,
+            &options,
+        )
+        .unwrap();
+        let attribute = |svg: &str, name: &str| -> f32 {
+            let start =
+                svg.find(&format!("{}=\"", name)).unwrap() + name.len() + 2;
+            let end = start + svg[start..].find('"').unwrap();
+            svg[start..end].parse().unwrap()
+        };
+        // Angle of a point of the svg around the center of the wheel
+        let angle = |x: f32, y: f32| {
+            (y - max_size / 2.0).atan2(x - max_size / 2.0) as f64
+        };
+        // The line of an aspect to the directed Mc ends at the directed Mc,
+        // not at the natal Mc
+        let directed_mc = res.directed.angle_longitude(Angle::Mc).unwrap();
+        let natal_mc = res.natal.angle_longitude(Angle::Mc).unwrap();
+        assert!(separation(directed_mc, natal_mc) > 10.0);
+        let lines: Vec<&DataObjectSvg> = objects
+            .iter()
+            .filter(|o| match &o.aspect_record {
+                Some(r) => {
+                    r.body_b == ChartPoint::Angle(Angle::Mc)
+                        && matches!(r.body_a, ChartPoint::Bodie(_))
+                },
+                None => false,
+            })
+            .collect();
+        assert!(!lines.is_empty());
+        for line in lines.iter() {
+            let record = line.aspect_record.as_ref().unwrap();
+            let natal = res.natal.point_longitude(record.body_a).unwrap();
+            let svg = line.svg.as_str();
+            let on_wheel = angle(attribute(svg, "x2"), attribute(svg, "y2"))
+                - angle(attribute(svg, "x1"), attribute(svg, "y1"));
+            let expected = (directed_mc - natal).to_radians();
+            assert!((on_wheel.cos() - expected.cos()).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn solar_lunar_return() {
+        let options = ChartOptions {
+            bodies: "Sun, Moon".parse().unwrap(),
+            ..ChartOptions::default()
+        };
+        let res = solar_return(data(1, 46.2), 2020, 40.7, -74.0, ".", &options)
+            .unwrap();
+        let natal_sun = res.natal.bodie_longitude(Bodies::Sun).unwrap();
+        assert!(
+            separation(
+                res.chart.bodie_longitude(Bodies::Sun).unwrap(),
+                natal_sun
+            ) < 1e-4
+        );
+        assert_eq!(res.chart.julian_day, res.julian_day);
+        // Around the birthday (the Sun comes back ~6 hours later each year)
+        let date = res.date.unwrap();
+        assert_eq!((date.year, date.month), (2020, 2));
+        assert!(date.day <= 2);
+        // Lunar return in March 2020
+        let res =
+            lunar_return(data(1, 46.2), 2020, 3, 46.2, 6.14, ".", &options)
+                .unwrap();
+        assert!(
+            separation(
+                res.chart.bodie_longitude(Bodies::Moon).unwrap(),
+                res.natal.bodie_longitude(Bodies::Moon).unwrap()
+            ) < 1e-4
+        );
+        let date = res.date.unwrap();
+        assert_eq!((date.year, date.month), (2020, 3));
+        assert!(date.day <= 28);
+        // The Moon isn't selected: same return
+        let without_moon = ChartOptions {
+            bodies: "Sun, Mars".parse().unwrap(),
+            ..ChartOptions::default()
+        };
+        let res_without = lunar_return(
+            data(1, 46.2),
+            2020,
+            3,
+            46.2,
+            6.14,
+            ".",
+            &without_moon,
+        )
+        .unwrap();
+        assert!((res_without.julian_day - res.julian_day).abs() < 1e-6);
+    }
+
+    #[test]
+    fn transit_search_range() {
+        let options = ChartOptions::default();
+        let start = DataChartNatal {
+            year: 2020,
+            ..data(1, 46.2)
+        };
+        let end = DataChartNatal {
+            year: 2019,
+            ..data(1, 46.2)
+        };
+        match transit_search(data(1, 46.2), start, end, ".", &options) {
+            Err(AstrologyError::InvalidDateTime(_)) => {},
+            _ => panic!("InvalidDateTime expected"),
+        }
+    }
+
+    #[test]
+    fn midpoint_shortest_arc() {
+        assert!((midpoint(100.0, 200.0) - 150.0).abs() < 1e-9);
+        assert!((midpoint(350.0, 10.0)).abs() < 1e-9);
+        assert!((midpoint(10.0, 350.0)).abs() < 1e-9);
+        assert!((midpoint(300.0, 100.0) - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn date_time_of_julian_day() {
+        let date = DateTimeUtc::from_julian_day(2_451_545.0).unwrap();
+        assert_eq!(date.to_string(), "01.01.2000 12:00:00");
+        let date = DateTimeUtc::from_julian_day(2_451_544.5).unwrap();
+        assert_eq!(date.to_string(), "01.01.2000 00:00:00");
+    }
+
+    #[test]
+    fn zodiac_ayanamsa() {
+        assert_eq!("tropical".parse(), Ok(Zodiac::Tropical));
+        assert_eq!(
+            "Fagan-Bradley".parse(),
+            Ok(Zodiac::Sidereal(Ayanamsa::FaganBradley))
+        );
+        assert!("lahir".parse::<Zodiac>().is_err());
+        assert_eq!(Zodiac::Tropical.ayanamsa(2_451_545.0).unwrap(), 0.0);
+        // Lahiri ~23°51' in 2000
+        let lahiri = Zodiac::Sidereal(Ayanamsa::Lahiri)
+            .ayanamsa(2_451_545.0)
+            .unwrap();
+        assert!((lahiri - 23.85).abs() < 0.02);
+    }
+
+    #[test]
+    fn calc_options_flag() {
+        let speed = OptionalFlag::Speed as i32;
+        assert_eq!(CalcOptions::default().flag(), speed);
+        let calc = CalcOptions {
+            center: Center::Topocentric,
+            altitude: 400.0,
+            true_position: true,
+            equatorial: true,
+        };
+        assert_eq!(
+            calc.flag(),
+            speed
+                | OptionalFlag::TopocentricPosition as i32
+                | OptionalFlag::TruePosition as i32
+        );
+        assert_eq!(
+            calc.flag_equatorial() & OptionalFlag::EquatorialPosition as i32,
+            OptionalFlag::EquatorialPosition as i32
+        );
+    }
+
+    #[test]
+    fn heliocentric_sect() {
+        let geocentric = ChartOptions {
+            bodies: "Sun, Moon, Mars".parse().unwrap(),
+            ..ChartOptions::default()
+        };
+        let heliocentric = ChartOptions {
+            calc: CalcOptions {
+                center: Center::Heliocentric,
+                ..CalcOptions::default()
+            },
+            ..geocentric.clone()
+        };
+        let night = || DataChartNatal {
+            hour: 23,
+            ..data(1, 46.2)
+        };
+        for data in [data(1, 46.2), night()].iter() {
+            let geo =
+                compute_natal(DataChartNatal { ..*data }, ".", &geocentric)
+                    .unwrap();
+            let helio =
+                compute_natal(DataChartNatal { ..*data }, ".", &heliocentric)
+                    .unwrap();
+            // The Sun of the chart is at 0°, the sect and the lots are
+            // geocentric
+            assert!(helio.bodie_longitude(Bodies::Sun).unwrap() < 1e-6);
+            assert_eq!(helio.diurnal, geo.diurnal);
+            assert!(!helio.lots.is_empty());
+            for (h, g) in helio.lots.iter().zip(geo.lots.iter()) {
+                assert_eq!(h.name, g.name);
+                assert!((h.longitude - g.longitude).abs() < 1e-9);
+            }
+        }
+        match lunar_phases(data(1, 46.2), data(28, 46.2), ".", &heliocentric) {
+            Err(AstrologyError::InvalidArgument(_)) => {},
+            _ => panic!("InvalidArgument expected"),
+        }
+    }
+
+    #[test]
+    fn body_selection_list() {
+        assert_eq!(BodySelection::default().list(), CHART_BODIES.to_vec());
+        let selection: BodySelection =
+            "Sun, Moon, MeanNode, SouthNode, Pallas, AsteroidEros"
+                .parse()
+                .unwrap();
+        assert_eq!(selection.nodes, NodeKind::Mean);
+        assert_eq!(
+            selection.list(),
+            vec![
+                Bodies::Sun,
+                Bodies::Moon,
+                Bodies::MeanNode,
+                Bodies::Pallas,
+                Bodies::AsteroidEros,
+                Bodies::SouthNode,
+            ]
+        );
+        assert!("Sun, Earth".parse::<BodySelection>().is_err());
+        let json = serde_json::to_string(&selection).unwrap();
+        assert_eq!(
+            serde_json::from_str::<BodySelection>(&json).unwrap(),
+            selection
+        );
+        assert_eq!(
+            serde_json::from_str::<BodySelection>("{}").unwrap(),
+            BodySelection::default()
+        );
+    }
+
+    #[test]
+    fn body_selection_without_sun() {
+        let selection = "Moon,Mars".parse::<BodySelection>().unwrap();
+        let with_sun = ChartOptions {
+            bodies: "Sun, Moon, Mars".parse().unwrap(),
+            ..ChartOptions::default()
+        };
+        let without_sun = ChartOptions {
+            bodies: selection,
+            ..with_sun.clone()
+        };
+        let target = || DataChartNatal {
+            year: 2030,
+            ..data(1, 46.2)
+        };
+        // The solar return and the solar arc still use the natal Sun
+        let expected =
+            solar_return(data(1, 46.2), 2020, 40.7, -74.0, ".", &with_sun)
+                .unwrap();
+        let res =
+            solar_return(data(1, 46.2), 2020, 40.7, -74.0, ".", &without_sun)
+                .unwrap();
+        assert!(res.natal.bodie_longitude(Bodies::Sun).is_none());
+        assert!((res.julian_day - expected.julian_day).abs() < 1e-6);
+        let expected =
+            compute_directed(data(1, 46.2), target(), ".", &with_sun).unwrap();
+        let res = compute_directed(data(1, 46.2), target(), ".", &without_sun)
+            .unwrap();
+        assert!((res.arc - expected.arc).abs() < 1e-9);
+        assert!((res.arc - 30.0 * NAIBOD_ARC).abs() < 1.0);
+    }
+
+    #[test]
+    fn fixed_stars_catalog() {
+        let stars = FixedStarOptions::default();
+        assert_eq!(stars.stars.len(), FIXED_STARS.len());
+        assert!(!stars.ring);
+        // The planets without asteroids are computed without ephemeris files
+        let options = ChartOptions {
+            bodies: BodySelection {
+                asteroids: Vec::new(),
+                ..BodySelection::default()
+            },
+            ..ChartOptions::default()
+        };
+        match compute_fixed_stars(data(1, 46.2), ".", &options) {
+            Err(AstrologyError::MissingEphemerisFiles(_)) => {},
+            _ => panic!("MissingEphemerisFiles expected"),
+        }
+    }
+
+    #[test]
+    fn hermetic_lots_sect() {
+        // The bodies of the formulas are computed without being selected
+        let options = ChartOptions {
+            bodies: "Sun, Moon".parse().unwrap(),
+            ..ChartOptions::default()
+        };
+        let lot = |chart: &super::ChartData, name: &str| {
+            chart.lots.iter().find(|l| l.name == name).unwrap().clone()
+        };
+        // Noon: day chart
+        let day = compute_natal(data(1, 46.2), ".", &options).unwrap();
+        let asc = day.angle_longitude(Angle::Asc).unwrap();
+        let sun = day.bodie_longitude(Bodies::Sun).unwrap();
+        let moon = day.bodie_longitude(Bodies::Moon).unwrap();
+        let fortune = lot(&day, "Fortune");
+        assert!(!fortune.reversed);
+        assert!(separation(fortune.longitude, asc + moon - sun) < 1e-9);
+        assert!(
+            separation(
+                fortune.longitude + lot(&day, "Spirit").longitude,
+                asc * 2.0
+            ) < 1e-9
+        );
+        assert_eq!(day.lots.len(), 8);
+        // Midnight: night chart, Marriage isn't reversed
+        let night = compute_natal(
+            DataChartNatal {
+                hour: 0,
+                ..data(1, 46.2)
+            },
+            ".",
+            &options,
+        )
+        .unwrap();
+        let asc = night.angle_longitude(Angle::Asc).unwrap();
+        let sun = night.bodie_longitude(Bodies::Sun).unwrap();
+        let moon = night.bodie_longitude(Bodies::Moon).unwrap();
+        let fortune = lot(&night, "Fortune");
+        assert!(fortune.reversed);
+        assert!(separation(fortune.longitude, asc + sun - moon) < 1e-9);
+        assert!(!lot(&night, "Marriage").reversed);
+        // A lot can only use the lots before it
+        let options = ChartOptions {
+            lots: LotOptions {
+                formulas: vec![LotFormula::new(
+                    "Eros",
+                    LotPoint::Bodie(Bodies::Venus),
+                    LotPoint::Lot("Spirit".to_string()),
+                    true,
+                )],
+                draw: true,
+            },
+            ..options
+        };
+        match compute_natal(data(1, 46.2), ".", &options) {
+            Err(AstrologyError::InvalidArgument(_)) => {},
+            _ => panic!("InvalidArgument expected"),
+        }
+    }
+
+    #[test]
+    fn lot_marker_escaped() {
+        let options = ChartOptions {
+            bodies: "Sun, Moon".parse().unwrap(),
+            lots: LotOptions {
+                formulas: vec![LotFormula::new(
+                    "&<>Lot",
+                    LotPoint::Bodie(Bodies::Sun),
+                    LotPoint::Bodie(Bodies::Moon),
+                    false,
+                )],
+                draw: true,
+            },
+            ..ChartOptions::default()
+        };
+        let objects =
+            chart(1000.0, data(1, 46.2), ".", Language::English, &options)
+                .unwrap();
+        assert!(objects.iter().any(|o| o.svg.contains("&amp;&lt;&gt;")));
+        assert!(!objects.iter().any(|o| o.svg.contains("&<>")));
+    }
+
+    #[test]
+    fn essential_dignities() {
+        // Sun 19° Aries by day: exaltation, triplicity and face
+        let sun = bodie_dignities(Bodies::Sun, 19.0, true);
+        assert_eq!(
+            sun.dignities,
+            vec![Dignity::Exaltation, Dignity::Triplicity, Dignity::Face]
+        );
+        assert_eq!(sun.score, 8);
+        assert_eq!(sun.rulers.term, Bodies::Mercury);
+        // Venus 27° Pisces by night: exaltation, Mars rules the triplicity
+        let venus = bodie_dignities(Bodies::Venus, 357.0, false);
+        assert_eq!(venus.dignities, vec![Dignity::Exaltation]);
+        assert_eq!(venus.rulers.face, Bodies::Mars);
+        // Mars 5° Libra: detriment and peregrine
+        let mars = bodie_dignities(Bodies::Mars, 185.0, true);
+        assert_eq!(
+            mars.dignities,
+            vec![Dignity::Peregrine, Dignity::Detriment]
+        );
+        assert_eq!(mars.score, -10);
+        assert_eq!(mars.rulers.exaltation, Some(Bodies::Saturn));
+        // Venus in Aries and Mars in Taurus
+        let receptions = super::mutual_receptions(&[
+            bodie_dignities(Bodies::Venus, 10.0, true),
+            bodie_dignities(Bodies::Mars, 40.0, true),
+        ]);
+        assert_eq!(receptions.len(), 1);
+        assert_eq!(receptions[0].kind, ReceptionKind::Domicile);
+        // The classical planets are added to the selection
+        let options = ChartOptions {
+            bodies: "Sun, Moon".parse().unwrap(),
+            ..ChartOptions::default()
+        };
+        let data = compute_dignities(data(1, 46.2), ".", &options).unwrap();
+        assert!(data.diurnal);
+        assert_eq!(data.bodies.len(), 7);
+        let json = serde_json::to_string(&data.bodies).unwrap();
+        assert!(json.contains("\"exaltation\":null"));
+    }
+
+    #[test]
+    fn sect_planetary_hour() {
+        let options = ChartOptions {
+            bodies: "Sun, Moon".parse().unwrap(),
+            ..ChartOptions::default()
+        };
+        // Tuesday 1 February 2000 at noon in Geneva: 5th hour of the day of
+        // Mars (Mars, Sun, Venus, Mercury, Moon)
+        let chart = compute_natal(data(1, 46.2), ".", &options).unwrap();
+        assert!(chart.diurnal);
+        let hour = chart.planetary_hour.unwrap();
+        assert_eq!(hour.day_ruler, Bodies::Mars);
+        assert_eq!(hour.hour, 5);
+        assert_eq!(hour.hour_ruler, Bodies::Moon);
+        assert!(
+            hour.sunrise < chart.julian_day && chart.julian_day < hour.sunset
+        );
+        // Midnight: the planetary day of Monday isn't over
+        let night = compute_natal(
+            DataChartNatal {
+                hour: 0,
+                ..data(1, 46.2)
+            },
+            ".",
+            &options,
+        )
+        .unwrap();
+        assert!(!night.diurnal);
+        let hour = night.planetary_hour.unwrap();
+        assert_eq!(hour.day_ruler, Bodies::Moon);
+        assert_eq!((hour.hour, hour.hour_ruler), (18, Bodies::Mars));
+        let text = |t: &str| SectLabelPart::Text(t.to_string());
+        assert_eq!(
+            sect_label(false, Some(&hour), Language::French),
+            vec![
+                text("Thème nocturne, jour "),
+                SectLabelPart::Planet(Bodies::Moon),
+                text(", heure 18 "),
+                SectLabelPart::Planet(Bodies::Mars),
+            ]
+        );
+        assert_eq!(
+            sect_label(false, Some(&hour), Language::English),
+            vec![
+                text("Night chart, "),
+                SectLabelPart::Planet(Bodies::Moon),
+                text(" day, hour 18 "),
+                SectLabelPart::Planet(Bodies::Mars),
+            ]
+        );
+        assert_eq!(
+            sect_label(true, None, Language::English),
+            vec![text("Day chart")]
+        );
+        // Polar night: no sunrise
+        let polar = compute_natal(data(1, 80.0), ".", &options).unwrap();
+        assert!(polar.planetary_hour.is_none());
+    }
+
+    #[test]
+    fn time_lords() {
+        let options = ChartOptions {
+            bodies: "Sun, Moon".parse().unwrap(),
+            ..ChartOptions::default()
+        };
+        let natal = compute_natal(data(1, 46.2), ".", &options).unwrap();
+        let date = |year: i32| DataChartNatal {
+            year,
+            ..data(1, 46.2)
+        };
+        let sign_index = |sign: &Signs| {
+            Signs::iter().position(|s| s.to_string() == sign.to_string())
+        };
+        let asc = natal.angle_longitude(Angle::Asc).unwrap();
+        // Profections: a sign by year from the Asc, a sign by month
+        let years = profections(&natal, 0, 13, true).unwrap();
+        assert_eq!(years.len(), 14 * 13);
+        assert_eq!((years[0].house, years[0].month), (1, 0));
+        assert_eq!(years[13].house, 2);
+        let age_13 = &years[13 * 13];
+        assert_eq!((age_13.age, age_13.house), (13, 2));
+        assert_eq!(age_13.lord, rulers(asc + 30.0).domicile);
+        let at = profections_at(&natal, date(2013)).unwrap();
+        assert_eq!((at[0].age, at[0].house), (13, 2));
+        // The first month of the year has the sign of the year
+        assert_eq!((at[1].month, at[1].house), (1, 2));
+        // Zodiacal releasing: the sub periods are inside their period and a
+        // loosing of the bond jumps to the sign opposite to the period
+        let periods =
+            zodiacal_releasing(&natal, "Spirit", date(2000), date(2080), 2)
+                .unwrap();
+        let spirit = natal.lots.iter().find(|l| l.name == "Spirit").unwrap();
+        assert_eq!(periods[0].sign.to_string(), spirit.sign.to_string());
+        let mut parent = &periods[0];
+        let mut loosing = 0;
+        for p in periods.iter() {
+            if p.level == 1 {
+                parent = p;
+            } else {
+                assert!(p.start >= parent.start && p.end <= parent.end + 1e-9);
+                if p.loosing_of_bond {
+                    loosing += 1;
+                    let opposite = (sign_index(&parent.sign).unwrap() + 6) % 12;
+                    assert_eq!(sign_index(&p.sign), Some(opposite));
+                }
+            }
+        }
+        assert!(loosing > 0);
+        // Spirit in Cancer (25 years of 360 days) then Leo, the twelve signs
+        // of the level 2 last 211 months: loosing of the bond to Capricorn on
+        // 01.06.2017 11:00 UT
+        let is =
+            |sign: &Signs, other: Signs| sign.to_string() == other.to_string();
+        assert!(is(&spirit.sign, Signs::Cancer));
+        assert!(is(&periods[1].sign, Signs::Cancer));
+        let first = periods.iter().find(|p| p.loosing_of_bond).unwrap();
+        assert_eq!(first.level, 2);
+        assert!(is(&first.sign, Signs::Capricorn));
+        assert!((first.start - natal.julian_day - 211.0 * 30.0).abs() < 1e-6);
+        let loosing_date = julian_day(&DataChartNatal {
+            year: 2017,
+            month: 6,
+            hour: 11,
+            time_zone: 0.0,
+            ..data(1, 46.2)
+        })
+        .unwrap();
+        assert!((first.start - loosing_date).abs() < 1.0 / 1440.0);
+        let leo = periods
+            .iter()
+            .find(|p| p.level == 1 && is(&p.sign, Signs::Leo))
+            .unwrap();
+        assert!((leo.start - natal.julian_day - 25.0 * 360.0).abs() < 1e-6);
+        // Capricorn (27 years): the sequence returns twice to Capricorn, after
+        // 211 and 211 + 99 months (Cancer to Sagittarius)
+        let periods =
+            zodiacal_releasing(&natal, "Spirit", date(2097), date(2125), 2)
+                .unwrap();
+        let capricorn = periods
+            .iter()
+            .find(|p| p.level == 1 && is(&p.sign, Signs::Capricorn))
+            .unwrap();
+        let loosings: Vec<&super::ReleasingPeriod> =
+            periods.iter().filter(|p| p.loosing_of_bond).collect();
+        assert_eq!(loosings.len(), 2);
+        for (p, months) in loosings.iter().zip([211.0, 310.0].iter()) {
+            assert!(is(&p.sign, Signs::Cancer));
+            assert!(((p.start - capricorn.start) / 30.0 - months).abs() < 1e-6);
+        }
+        assert!(zodiacal_releasing(
+            &natal,
+            "Spirit",
+            date(2000),
+            date(2010),
+            5
+        )
+        .is_err());
+        // Firdaria of a day birth: Sun then Venus, sub periods of the Sun
+        let firdaria = firdaria(&natal, date(2000), date(2075)).unwrap();
+        assert_eq!(firdaria[0].lord, Bodies::Sun);
+        assert_eq!(firdaria[0].sub_lord, None);
+        assert_eq!(firdaria[2].sub_lord, Some(Bodies::Venus));
+        assert_eq!(firdaria[8].lord, Bodies::Venus);
+        assert!(firdaria
+            .iter()
+            .any(|f| f.lord == Bodies::SouthNode && f.sub_lord.is_none()));
+    }
 }
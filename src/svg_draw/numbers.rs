@@ -15,10 +15,11 @@
  * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
  */
 extern crate strum;
+use crate::svg_draw::chart_style::ChartStyle;
 use crate::svg_draw::svg_draw::{
     WorkingStorageDrawPolyMorphNatal, WorkingStorageDrawPolyMorphTransit,
 };
-use libswe_sys::sweconst::{Bodies, Theme};
+use libswe_sys::sweconst::Bodies;
 use svg::node::element::path::{Data, Number};
 use svg::node::element::{Group, Path};
 use svg::Document;
@@ -31,14 +32,14 @@ impl WorkingStorageDrawPolyMorphNatal {
         degre: i16,
         bodie_or_angle: Bodies,
     ) -> Document {
-        draw_degre(degre, bodie_or_angle, self.ws.theme)
+        draw_degre(degre, bodie_or_angle, &self.ws.style)
     }
     pub fn numbers_draw_minute(
         &self,
         degre: i16,
         bodie_or_angle: Bodies,
     ) -> Document {
-        draw_minute(degre, bodie_or_angle, self.ws.theme)
+        draw_minute(degre, bodie_or_angle, &self.ws.style)
     }
 }
 
@@ -48,26 +49,29 @@ impl WorkingStorageDrawPolyMorphTransit {
         degre: i16,
         bodie_or_angle: Bodies,
     ) -> Document {
-        draw_degre(degre, bodie_or_angle, self.ws.theme)
+        draw_degre(degre, bodie_or_angle, &self.ws.style)
     }
     pub fn numbers_draw_minute(
         &self,
         degre: i16,
         bodie_or_angle: Bodies,
     ) -> Document {
-        draw_minute(degre, bodie_or_angle, self.ws.theme)
+        draw_minute(degre, bodie_or_angle, &self.ws.style)
     }
 }
 
 /// Draw degre "°"
 /// The first parameter is the degre
 /// The second parameter is Bodies::EclNut for angle (because not used)
-/// The third parameter is the theme
-fn draw_degre(degre: i16, bodie_or_angle: Bodies, theme: Theme) -> Document {
+/// The third parameter is the style
+fn draw_degre(
+    degre: i16,
+    bodie_or_angle: Bodies,
+    style: &ChartStyle,
+) -> Document {
     let size: (Number, Number);
     let document: Document;
-    let color: String =
-        format!("#{:06X}", bodie_or_angle.object_color(theme) as i32);
+    let color: String = style.bodie_color(bodie_or_angle);
     match degre {
         0 => {
             size = (50.0, 50.0);
@@ -1975,12 +1979,15 @@ fn draw_degre(degre: i16, bodie_or_angle: Bodies, theme: Theme) -> Document {
 /// Draw minute "'"
 /// The first parameter is minute
 /// The second parameter is Bodies::EclNut for angle (because not used)
-/// The third parameter is the Theme
-fn draw_minute(minute: i16, bodie_or_angle: Bodies, theme: Theme) -> Document {
+/// The third parameter is the style
+fn draw_minute(
+    minute: i16,
+    bodie_or_angle: Bodies,
+    style: &ChartStyle,
+) -> Document {
     let size: (Number, Number);
     let document: Document;
-    let color: String =
-        format!("#{:06X}", bodie_or_angle.object_color(theme) as i32);
+    let color: String = style.bodie_color(bodie_or_angle);
     match minute {
         0 => {
             size = (50.0, 50.0);
@@ -0,0 +1,416 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+//! Secondary progressions: one day after the birth is one year of life
+//! Solar arc directions: every point of the natal chart is advanced by the arc
+//! of the progressed Sun (or the Naibod arc)
+use super::calc_options::Center;
+use super::chart_data::{
+    angle_data, aspect_record, bodie_data, calc_error, compute_chart,
+    compute_chart_houses, compute_natal, cusp_data, julian_day, natal_aspects,
+    signed_distance, transit_aspects, AngleData, AspectRecord, BodieData,
+    ChartData, CuspData, DateTimeUtc,
+};
+use super::compute_chart::{ChartOptions, DataChartNatal};
+use super::lots::{lot_data, LotData};
+use crate::error::AstrologyError;
+use libswe_sys::sweconst::{Angle, Bodies, OptionalFlag};
+use libswe_sys::swerust;
+use libswe_sys::swerust::handler_swe17;
+use serde::{Deserialize, Serialize};
+
+/// Tropical year in days
+pub const TROPICAL_YEAR: f64 = 365.242_189;
+
+/// Naibod arc: mean motion of the Sun in one day, in degree
+pub const NAIBOD_ARC: f64 = 0.985_647;
+
+// Mean motion of the Mc in one day (sidereal day), in degree
+const MC_SPEED: f64 = 360.985_647;
+const MC_ITERATIONS: usize = 10;
+const MC_PRECISION: f64 = 0.000_001;
+
+// Iterations and precision in degree for the date of a directed aspect
+const ARC_ITERATIONS: usize = 10;
+const ARC_PRECISION: f64 = 0.000_000_1;
+
+/// Angles (and houses) of a progressed chart
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum ProgressedAngles {
+    /// Houses of the progressed julian day (the Mc turns one time per year)
+    Quotidian,
+    /// Natal Mc + Naibod arc by year
+    #[default]
+    Naibod,
+    /// Natal Mc + arc of the progressed Sun
+    SolarArc,
+}
+
+/// Arc of the solar arc directions
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum DirectionArc {
+    /// Arc of the progressed Sun
+    #[default]
+    SolarArc,
+    /// Naibod arc by year (mean motion of the Sun)
+    Naibod,
+}
+
+/// Options of the solar arc directions
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DirectionOptions {
+    /// Arc of the directions (solar arc by default)
+    pub arc: DirectionArc,
+    /// Orb in degree of the directed aspects, the arc moves ~1° by year so
+    /// the orbs of the natal aspects would be years wide
+    pub orb: f64,
+}
+
+impl Default for DirectionOptions {
+    fn default() -> Self {
+        Self {
+            arc: DirectionArc::SolarArc,
+            orb: 1.0,
+        }
+    }
+}
+
+/// Progressed chart computed without svg
+/// The progressed chart is cast for the place of birth
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgressedData {
+    pub natal: ChartData,
+    pub progressed: ChartData,
+    /// Age in years at the target date
+    pub years: f64,
+    /// Aspects of the progressed bodies to the natal chart, body_a is the
+    /// natal point
+    pub aspects: Vec<AspectRecord>,
+}
+
+/// Compute the secondary progressions of a natal chart for a target date
+/// The location of target is not used
+pub fn compute_progressed(
+    data: DataChartNatal,
+    target: DataChartNatal,
+    path: &str,
+    options: &ChartOptions,
+) -> Result<ProgressedData, AstrologyError> {
+    let lat = data.lat as f64;
+    let lng = data.lng as f64;
+    let natal = compute_natal(data, path, options)?;
+    let years = (julian_day(&target)? - natal.julian_day) / TROPICAL_YEAR;
+    let julian_day = natal.julian_day + years;
+    let progressed = match options.progressed_angles {
+        ProgressedAngles::Quotidian => {
+            compute_chart(julian_day, lat, lng, options)?
+        },
+        ProgressedAngles::Naibod | ProgressedAngles::SolarArc => {
+            let arc = match options.progressed_angles {
+                ProgressedAngles::Naibod => years * NAIBOD_ARC,
+                _ => solar_arc(&natal, julian_day)?,
+            };
+            let mc = handler_swe17::degnorm(
+                natal.angle_longitude(Angle::Mc).unwrap_or(0.0) + arc,
+            );
+            let julian_day_houses =
+                julian_day_mc(julian_day, lat, lng, options, mc)?;
+            compute_chart_houses(
+                julian_day,
+                julian_day_houses,
+                lat,
+                lng,
+                options,
+            )?
+        },
+    };
+    let aspects = transit_aspects(&natal, &progressed.bodies, &options.aspects);
+    Ok(ProgressedData {
+        natal,
+        progressed,
+        years,
+        aspects,
+    })
+}
+
+/// Arc of the Sun between the birth and a julian day, in degree
+pub fn solar_arc(
+    natal: &ChartData,
+    julian_day: f64,
+) -> Result<f64, AstrologyError> {
+    direction_arc(natal, julian_day - natal.julian_day, DirectionArc::SolarArc)
+        .map(|(arc, _)| arc)
+}
+
+/// Aspect of a directed point to a natal point with its date of perfection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirectedAspect {
+    /// body_a is the natal point and body_b the directed point, exact_in_days
+    /// is in days of calendar from the target date
+    pub record: AspectRecord,
+    /// Julian day (UT) of the exact aspect
+    pub exact_julian_day: f64,
+    pub exact: Option<DateTimeUtc>,
+}
+
+/// Solar arc directed chart computed without svg
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirectedData {
+    pub natal: ChartData,
+    /// Natal chart with every bodie, cusp, angle and lot advanced by the arc,
+    /// its julian day is the target date, the sect is the natal one and there
+    /// is no planetary hour
+    pub directed: ChartData,
+    /// Age in years at the target date
+    pub years: f64,
+    /// Arc in degree at the target date
+    pub arc: f64,
+    /// Aspects of the directed points to the natal points within
+    /// ChartOptions::directions orb
+    pub aspects: Vec<DirectedAspect>,
+}
+
+/// Compute the solar arc directions of a natal chart for a target date
+/// The arc and the orb are options.directions, the location of target is
+/// not used
+pub fn compute_directed(
+    data: DataChartNatal,
+    target: DataChartNatal,
+    path: &str,
+    options: &ChartOptions,
+) -> Result<DirectedData, AstrologyError> {
+    let natal = compute_natal(data, path, options)?;
+    let julian_day_target = julian_day(&target)?;
+    let years = (julian_day_target - natal.julian_day) / TROPICAL_YEAR;
+    let (arc, rate) = direction_arc(&natal, years, options.directions.arc)?;
+    let directed = directed_chart(
+        &natal,
+        julian_day_target,
+        arc,
+        rate / TROPICAL_YEAR,
+        options,
+    );
+    let mut aspects: Vec<DirectedAspect> = Vec::new();
+    let directed_points = directed.points();
+    for (point_n, lng_n, _) in natal.points() {
+        for (point_d, lng_d, speed_d) in directed_points.iter().copied() {
+            if point_n == point_d {
+                continue;
+            }
+            if let Some(mut record) = aspect_record(
+                &options.aspects,
+                point_n,
+                lng_n,
+                0.0,
+                point_d,
+                lng_d,
+                speed_d,
+            )
+            .filter(|r| r.orb <= options.directions.orb)
+            {
+                let arc_exact =
+                    arc + record.exact_in_days.unwrap_or(0.0) * speed_d;
+                let years_exact = direction_years(
+                    &natal,
+                    arc_exact,
+                    years,
+                    options.directions.arc,
+                )?;
+                let exact_julian_day =
+                    natal.julian_day + years_exact * TROPICAL_YEAR;
+                record.exact_in_days =
+                    Some(exact_julian_day - julian_day_target);
+                aspects.push(DirectedAspect {
+                    record,
+                    exact_julian_day,
+                    exact: DateTimeUtc::from_julian_day(exact_julian_day),
+                });
+            }
+        }
+    }
+    Ok(DirectedData {
+        natal,
+        directed,
+        years,
+        arc,
+        aspects,
+    })
+}
+
+/// Arc in degree after years of life and its speed in degree by year
+/// The solar arc is negative before the birth, the natal and the progressed
+/// Sun are both geocentric (the Sun isn't always selected)
+fn direction_arc(
+    natal: &ChartData,
+    years: f64,
+    kind: DirectionArc,
+) -> Result<(f64, f64), AstrologyError> {
+    match kind {
+        DirectionArc::Naibod => Ok((years * NAIBOD_ARC, NAIBOD_ARC)),
+        DirectionArc::SolarArc => {
+            if natal.calc.center == Center::Heliocentric {
+                return Err(AstrologyError::InvalidArgument(
+                    "no solar arc in a heliocentric chart".to_string(),
+                ));
+            }
+            let (natal_sun, _) = sun_position(natal, natal.julian_day)?;
+            let (sun, speed) = sun_position(natal, natal.julian_day + years)?;
+            let arc = handler_swe17::degnorm(sun - natal_sun);
+            if years < 0.0 {
+                Ok((arc - 360.0, speed))
+            } else {
+                Ok((arc, speed))
+            }
+        },
+    }
+}
+
+/// Geocentric longitude in the zodiac of natal and speed of the Sun
+fn sun_position(
+    natal: &ChartData,
+    julian_day: f64,
+) -> Result<(f64, f64), AstrologyError> {
+    let calc = swerust::handler_swe03::calc_ut(
+        julian_day,
+        Bodies::Sun,
+        OptionalFlag::Speed as i32,
+    );
+    calc_error(Bodies::Sun, &calc)?;
+    Ok((
+        natal.zodiac.longitude(calc.longitude, julian_day)?,
+        calc.speed_longitude,
+    ))
+}
+
+/// Years of life for an arc, years is the first approximation
+fn direction_years(
+    natal: &ChartData,
+    arc: f64,
+    years: f64,
+    kind: DirectionArc,
+) -> Result<f64, AstrologyError> {
+    let mut y = years;
+    for _ in 0..ARC_ITERATIONS {
+        let (arc_y, rate) = direction_arc(natal, y, kind)?;
+        let distance = arc - arc_y;
+        if distance.abs() < ARC_PRECISION {
+            break;
+        }
+        y += distance / rate;
+    }
+    Ok(y)
+}
+
+/// Natal chart with every point advanced by arc at julian_day, speed is the
+/// speed of the directed points in degree by day
+fn directed_chart(
+    natal: &ChartData,
+    julian_day: f64,
+    arc: f64,
+    speed: f64,
+    options: &ChartOptions,
+) -> ChartData {
+    let angles: Vec<AngleData> = natal
+        .angles
+        .iter()
+        .map(|a| {
+            angle_data(
+                a.angle,
+                handler_swe17::degnorm(a.longitude + arc),
+                speed,
+            )
+        })
+        .collect();
+    let cusps: Vec<CuspData> = natal
+        .cusps
+        .iter()
+        .map(|c| {
+            cusp_data(
+                c.house,
+                handler_swe17::degnorm(c.longitude + arc),
+                &angles,
+            )
+        })
+        .collect();
+    let bodies: Vec<BodieData> = natal
+        .bodies
+        .iter()
+        .map(|b| {
+            bodie_data(
+                b.bodie,
+                handler_swe17::degnorm(b.longitude + arc),
+                b.latitude,
+                speed,
+                &cusps,
+            )
+        })
+        .collect();
+    let aspects = natal_aspects(&bodies, &angles, &options.aspects);
+    let lots: Vec<LotData> = natal
+        .lots
+        .iter()
+        .map(|l| {
+            lot_data(
+                &l.name,
+                handler_swe17::degnorm(l.longitude + arc),
+                l.reversed,
+                &cusps,
+            )
+        })
+        .collect();
+    ChartData {
+        julian_day,
+        house_system: natal.house_system,
+        zodiac: natal.zodiac,
+        ayanamsa: natal.ayanamsa,
+        calc: natal.calc,
+        bodies,
+        cusps,
+        angles,
+        aspects,
+        lots,
+        diurnal: natal.diurnal,
+        planetary_hour: None,
+    }
+}
+
+/// Julian day close to julian_day with the Mc on mc (in the zodiac of
+/// options) at lat/lng
+fn julian_day_mc(
+    julian_day: f64,
+    lat: f64,
+    lng: f64,
+    options: &ChartOptions,
+    mc: f64,
+) -> Result<f64, AstrologyError> {
+    let ayanamsa = options.zodiac.ayanamsa(julian_day)?;
+    let mut jd = julian_day;
+    for _ in 0..MC_ITERATIONS {
+        let houses = swerust::handler_swe14::houses(
+            jd,
+            lat,
+            lng,
+            options.house_system.code(),
+        );
+        let distance = signed_distance(houses.ascmc[1] - ayanamsa, mc);
+        if distance.abs() < MC_PRECISION {
+            break;
+        }
+        jd += distance / MC_SPEED;
+    }
+    Ok(jd)
+}
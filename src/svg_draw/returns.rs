@@ -0,0 +1,169 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+//! Solar and lunar returns: chart of the moment the Sun (or the Moon) returns
+//! to its natal longitude
+use super::calc_options::Center;
+use super::chart_data::{
+    calc_error, check_coordinates, compute_chart, compute_natal, julian_day,
+    signed_distance, ChartData, DateTimeUtc,
+};
+use super::compute_chart::{ChartOptions, DataChartNatal};
+use crate::error::AstrologyError;
+use libswe_sys::sweconst::Bodies;
+use libswe_sys::swerust;
+use libswe_sys::swerust::handler_swe17;
+use serde::{Deserialize, Serialize};
+
+// Mean motion in one day, in degree
+const SUN_SPEED: f64 = 0.985_647;
+const MOON_SPEED: f64 = 13.176_358;
+const RETURN_ITERATIONS: usize = 20;
+const RETURN_PRECISION: f64 = 0.000_000_1;
+
+/// Return chart computed without svg
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReturnData {
+    pub natal: ChartData,
+    /// Julian day (UT) of the exact return
+    pub julian_day: f64,
+    pub date: Option<DateTimeUtc>,
+    /// Chart of the return at the chosen location
+    pub chart: ChartData,
+}
+
+/// First return of the Sun to its natal longitude in the year (UT), the chart
+/// is cast at lat/lng
+pub fn solar_return(
+    data: DataChartNatal,
+    year: i32,
+    lat: f32,
+    lng: f32,
+    path: &str,
+    options: &ChartOptions,
+) -> Result<ReturnData, AstrologyError> {
+    compute_return(data, Bodies::Sun, (year, 1), (lat, lng), path, options)
+}
+
+/// First return of the Moon to its natal longitude in the month of the year
+/// (UT), the chart is cast at lat/lng
+pub fn lunar_return(
+    data: DataChartNatal,
+    year: i32,
+    month: u32,
+    lat: f32,
+    lng: f32,
+    path: &str,
+    options: &ChartOptions,
+) -> Result<ReturnData, AstrologyError> {
+    compute_return(data, Bodies::Moon, (year, month), (lat, lng), path, options)
+}
+
+/// Return of bodie after the first day of (year, month) at 0h UT
+/// The returns are geocentric or topocentric (the Sun of a heliocentric chart
+/// doesn't move)
+fn compute_return(
+    data: DataChartNatal,
+    bodie: Bodies,
+    (year, month): (i32, u32),
+    (lat, lng): (f32, f32),
+    path: &str,
+    options: &ChartOptions,
+) -> Result<ReturnData, AstrologyError> {
+    if options.calc.center == Center::Heliocentric {
+        return Err(AstrologyError::InvalidArgument(
+            "no return in a heliocentric chart".to_string(),
+        ));
+    }
+    check_coordinates(lat, lng)?;
+    let birth_place = (data.lat as f64, data.lng as f64);
+    let natal = compute_natal(data, path, options)?;
+    let julian_day_start = julian_day(&DataChartNatal {
+        year,
+        month,
+        day: 1,
+        hour: 0,
+        min: 0,
+        sec: 0.0,
+        lat,
+        lng,
+        time_zone: 0.0,
+    })?;
+    // The natal bodie isn't always selected, computed with the flags of the
+    // chart from the birth place (the observer is a global state of the swiss
+    // ephemeris)
+    options.calc.set_topo(birth_place.0, birth_place.1);
+    let calc = swerust::handler_swe03::calc_ut(
+        natal.julian_day,
+        bodie,
+        options.calc.flag(),
+    );
+    calc_error(bodie, &calc)?;
+    let longitude =
+        options.zodiac.longitude(calc.longitude, natal.julian_day)?;
+    let mean_speed = match bodie {
+        Bodies::Moon => MOON_SPEED,
+        _ => SUN_SPEED,
+    };
+    options.calc.set_topo(lat as f64, lng as f64);
+    let julian_day = return_julian_day(
+        bodie,
+        longitude,
+        julian_day_start,
+        mean_speed,
+        options,
+    )?;
+    let chart = compute_chart(julian_day, lat as f64, lng as f64, options)?;
+    Ok(ReturnData {
+        natal,
+        julian_day,
+        date: DateTimeUtc::from_julian_day(julian_day),
+        chart,
+    })
+}
+
+/// Julian day of the first passage of bodie on longitude (in the zodiac of
+/// options) after julian_day
+/// Newton's method from the mean motion, the Sun and the Moon are never
+/// retrograde
+fn return_julian_day(
+    bodie: Bodies,
+    longitude: f64,
+    julian_day: f64,
+    mean_speed: f64,
+    options: &ChartOptions,
+) -> Result<f64, AstrologyError> {
+    let zodiac = options.zodiac;
+    let calc =
+        swerust::handler_swe03::calc_ut(julian_day, bodie, options.calc.flag());
+    calc_error(bodie, &calc)?;
+    let mut jd = julian_day
+        + handler_swe17::degnorm(
+            longitude - zodiac.longitude(calc.longitude, julian_day)?,
+        ) / mean_speed;
+    for _ in 0..RETURN_ITERATIONS {
+        let calc =
+            swerust::handler_swe03::calc_ut(jd, bodie, options.calc.flag());
+        calc_error(bodie, &calc)?;
+        let distance =
+            signed_distance(zodiac.longitude(calc.longitude, jd)?, longitude);
+        if distance.abs() < RETURN_PRECISION {
+            break;
+        }
+        jd += distance / calc.speed_longitude;
+    }
+    Ok(jd)
+}
@@ -0,0 +1,183 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+//! Sect of a chart (Sun above or below the horizon), planetary day and
+//! planetary hour from the sunrise and the sunset of the swiss ephemeris
+use super::dignities::CHALDEAN_ORDER;
+use super::serde_sweconst;
+use super::swe_rise;
+use crate::error::AstrologyError;
+use libswe_sys::sweconst::{Bodies, Language, OptionalFlag};
+use libswe_sys::swerust::handler_swe17;
+use serde::{Deserialize, Serialize};
+
+// Ruler of the days of the week, Monday to Sunday
+const DAY_RULERS: [Bodies; 7] = [
+    Bodies::Moon,
+    Bodies::Mars,
+    Bodies::Mercury,
+    Bodies::Jupiter,
+    Bodies::Venus,
+    Bodies::Saturn,
+    Bodies::Sun,
+];
+
+/// Planetary day and hour of a moment at a location
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanetaryHour {
+    /// Ruler of the day of the week of the sunrise at the location
+    #[serde(with = "serde_sweconst::bodies")]
+    pub day_ruler: Bodies,
+    /// Unequal hour of the planetary day: 1 to 12 from the sunrise to the
+    /// sunset, 13 to 24 from the sunset to the next sunrise
+    pub hour: i32,
+    #[serde(with = "serde_sweconst::bodies")]
+    pub hour_ruler: Bodies,
+    /// Julian days (UT) of the sunrise, sunset and next sunrise of the
+    /// planetary day
+    pub sunrise: f64,
+    pub sunset: f64,
+    pub next_sunrise: f64,
+}
+
+/// Day chart: the Sun is above the horizon, between the Desc and the Asc in
+/// the order of the zodiac (houses 7 to 12 of a quadrant house system)
+pub fn is_diurnal(sun: f64, asc: f64) -> bool {
+    handler_swe17::degnorm(sun - asc) >= 180.0
+}
+
+/// Planetary day and hour at a julian day (UT) and a location, None if the
+/// Sun doesn't rise or set (polar day or night)
+/// The planetary day begins at the sunrise, its first hour is ruled by the
+/// ruler of the day and the next hours follow the Chaldean order
+pub fn planetary_hour(
+    julian_day: f64,
+    lat: f64,
+    lng: f64,
+) -> Result<Option<PlanetaryHour>, AstrologyError> {
+    // Last sunrise before the julian day
+    let sunrise =
+        match sun_event(julian_day - 1.0, lat, lng, swe_rise::CALC_RISE)? {
+            Some(rise) if rise <= julian_day => Some(rise),
+            Some(_) => {
+                sun_event(julian_day - 2.0, lat, lng, swe_rise::CALC_RISE)?
+            },
+            None => None,
+        };
+    let sunrise = match sunrise {
+        Some(rise) => rise,
+        None => return Ok(None),
+    };
+    let sunset = match sun_event(sunrise, lat, lng, swe_rise::CALC_SET)? {
+        Some(set) => set,
+        None => return Ok(None),
+    };
+    let next_sunrise = match sun_event(sunset, lat, lng, swe_rise::CALC_RISE)? {
+        Some(rise) => rise,
+        None => return Ok(None),
+    };
+    let (start, end, first) = if julian_day < sunset {
+        (sunrise, sunset, 1)
+    } else {
+        (sunset, next_sunrise, 13)
+    };
+    let hour = first + ((julian_day - start) / (end - start) * 12.0) as i32;
+    let hour = hour.min(first + 11);
+    let day_ruler = day_ruler(sunrise, lng);
+    let day_index = CHALDEAN_ORDER
+        .iter()
+        .position(|b| *b == day_ruler)
+        .unwrap_or_default();
+    Ok(Some(PlanetaryHour {
+        day_ruler,
+        hour,
+        hour_ruler: CHALDEAN_ORDER[(day_index + hour as usize - 1) % 7],
+        sunrise,
+        sunset,
+        next_sunrise,
+    }))
+}
+
+/// Ruler of the day of the week of a julian day (UT) in the local mean time
+/// of the longitude
+pub fn day_ruler(julian_day: f64, lng: f64) -> Bodies {
+    // The julian day 0.0 is a Monday at noon
+    let day = (julian_day + 0.5 + lng / 360.0).floor() as i64;
+    DAY_RULERS[day.rem_euclid(7) as usize]
+}
+
+/// Part of the sect label: a text or a planet drawn with its glyph
+#[derive(Debug, Clone, PartialEq)]
+pub enum SectLabelPart {
+    Text(String),
+    Planet(Bodies),
+}
+
+/// Label of the corner of the chart: sect, ruler of the day and planetary
+/// hour, the rulers are drawn with the glyphs of the planets
+pub fn sect_label(
+    diurnal: bool,
+    planetary_hour: Option<&PlanetaryHour>,
+    lang: Language,
+) -> Vec<SectLabelPart> {
+    let text = |t: &str| SectLabelPart::Text(t.to_string());
+    let sect = match (lang, diurnal) {
+        (Language::French, true) => "Thème diurne",
+        (Language::French, false) => "Thème nocturne",
+        (_, true) => "Day chart",
+        (_, false) => "Night chart",
+    };
+    match planetary_hour {
+        Some(h) => match lang {
+            Language::French => vec![
+                text(&format!("{}, jour ", sect)),
+                SectLabelPart::Planet(h.day_ruler),
+                text(&format!(", heure {} ", h.hour)),
+                SectLabelPart::Planet(h.hour_ruler),
+            ],
+            _ => vec![
+                text(&format!("{}, ", sect)),
+                SectLabelPart::Planet(h.day_ruler),
+                text(&format!(" day, hour {} ", h.hour)),
+                SectLabelPart::Planet(h.hour_ruler),
+            ],
+        },
+        None => vec![text(sect)],
+    }
+}
+
+/// Next rising or setting of the Sun after julian_day, None if the Sun
+/// doesn't rise or set
+fn sun_event(
+    julian_day: f64,
+    lat: f64,
+    lng: f64,
+    rsmi: i32,
+) -> Result<Option<f64>, AstrologyError> {
+    let res = swe_rise::rise_trans(
+        julian_day,
+        Bodies::Sun as i32,
+        OptionalFlag::SwissEph as i32,
+        rsmi,
+        lat,
+        lng,
+    );
+    match res.result {
+        -2 => Ok(None),
+        r if r < 0 => Err(AstrologyError::CalculationFailed(res.serr)),
+        _ => Ok(Some(res.julian_day)),
+    }
+}
@@ -0,0 +1,171 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+//! Serde for the enums of libswe_sys::sweconst without Serialize/Deserialize
+//! Use with #[serde(with = "...")], the value is the name of the variant
+
+/// Bodies (name of AsStaticRef: "Sun", "Moon", ...)
+pub mod bodies {
+    use libswe_sys::sweconst::Bodies;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use strum::{AsStaticRef, IntoEnumIterator};
+
+    pub fn serialize<S>(
+        bodie: &Bodies,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(bodie.as_static())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Bodies, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Bodies::iter()
+            .find(|b| b.as_static() == s)
+            .ok_or_else(|| D::Error::custom(format!("unknown bodie: {}", s)))
+    }
+}
+
+/// List of bodies (names of AsStaticRef)
+pub mod bodies_vec {
+    use libswe_sys::sweconst::Bodies;
+    use serde::de::Error;
+    use serde::ser::SerializeSeq;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use strum::{AsStaticRef, IntoEnumIterator};
+
+    pub fn serialize<S>(
+        bodies: &[Bodies],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(bodies.len()))?;
+        for bodie in bodies.iter() {
+            seq.serialize_element(bodie.as_static())?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<Bodies>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|s| {
+                Bodies::iter().find(|b| b.as_static() == s).ok_or_else(|| {
+                    D::Error::custom(format!("unknown bodie: {}", s))
+                })
+            })
+            .collect()
+    }
+}
+
+/// Optional bodie (name of AsStaticRef or null)
+pub mod bodies_option {
+    use libswe_sys::sweconst::Bodies;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use strum::{AsStaticRef, IntoEnumIterator};
+
+    pub fn serialize<S>(
+        bodie: &Option<Bodies>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match bodie {
+            Some(b) => serializer.serialize_some(b.as_static()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(
+        deserializer: D,
+    ) -> Result<Option<Bodies>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(s) => Bodies::iter()
+                .find(|b| b.as_static() == s)
+                .map(Some)
+                .ok_or_else(|| {
+                    D::Error::custom(format!("unknown bodie: {}", s))
+                }),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Signs (name of Display: "Aries", "Taurus", ...)
+pub mod signs {
+    use libswe_sys::sweconst::Signs;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use strum::IntoEnumIterator;
+
+    pub fn serialize<S>(sign: &Signs, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&sign.to_string())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Signs, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Signs::iter()
+            .find(|sign| sign.to_string() == s)
+            .ok_or_else(|| D::Error::custom(format!("unknown sign: {}", s)))
+    }
+}
+
+/// Angle (name of Debug: "Asc", "Mc", ...)
+pub mod angle {
+    use libswe_sys::sweconst::Angle;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use strum::IntoEnumIterator;
+
+    pub fn serialize<S>(angle: &Angle, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&format!("{:?}", angle))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Angle, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Angle::iter()
+            .find(|a| format!("{:?}", a) == s)
+            .ok_or_else(|| D::Error::custom(format!("unknown angle: {}", s)))
+    }
+}
@@ -0,0 +1,265 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+//! Tropical or sidereal zodiac
+//! The sidereal longitudes are the tropical longitudes minus the ayanamsa (with
+//! the nutation, like the flag SEFLG_SIDEREAL of swiss ephemeris)
+use super::house_system::HouseSystem;
+use super::swe_sidereal;
+use crate::error::AstrologyError;
+use libswe_sys::sweconst::OptionalFlag;
+use libswe_sys::swerust::handler_swe14::HousesResult;
+use libswe_sys::swerust::handler_swe17;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use strum_macros::EnumIter;
+
+/// Ayanamsa of the sidereal zodiac
+/// The code of each variant is the SE_SIDM_* of swiss ephemeris
+#[derive(
+    Debug, Clone, Copy, PartialEq, Serialize, Deserialize, EnumIter, Default,
+)]
+pub enum Ayanamsa {
+    FaganBradley,
+    #[default]
+    Lahiri,
+    DeLuce,
+    Raman,
+    Ushashashi,
+    Krishnamurti,
+    DjwhalKhul,
+    Yukteshwar,
+    JnBhasin,
+    BabylonianHuber,
+    Aldebaran15Tau,
+    Hipparchos,
+    Sassanian,
+    GalacticCenter0Sag,
+    TrueCitra,
+    TrueRevati,
+    TruePushya,
+}
+
+impl Ayanamsa {
+    /// Code sid_mode for swiss ephemeris
+    pub fn code(self) -> i32 {
+        match self {
+            Ayanamsa::FaganBradley => 0,
+            Ayanamsa::Lahiri => 1,
+            Ayanamsa::DeLuce => 2,
+            Ayanamsa::Raman => 3,
+            Ayanamsa::Ushashashi => 4,
+            Ayanamsa::Krishnamurti => 5,
+            Ayanamsa::DjwhalKhul => 6,
+            Ayanamsa::Yukteshwar => 7,
+            Ayanamsa::JnBhasin => 8,
+            Ayanamsa::BabylonianHuber => 12,
+            Ayanamsa::Aldebaran15Tau => 14,
+            Ayanamsa::Hipparchos => 15,
+            Ayanamsa::Sassanian => 16,
+            Ayanamsa::GalacticCenter0Sag => 17,
+            Ayanamsa::TrueCitra => 27,
+            Ayanamsa::TrueRevati => 28,
+            Ayanamsa::TruePushya => 29,
+        }
+    }
+
+    /// Name of the ayanamsa (same in english and french)
+    pub fn text(self) -> String {
+        match self {
+            Ayanamsa::FaganBradley => "Fagan-Bradley",
+            Ayanamsa::Lahiri => "Lahiri",
+            Ayanamsa::DeLuce => "De Luce",
+            Ayanamsa::Raman => "Raman",
+            Ayanamsa::Ushashashi => "Ushashashi",
+            Ayanamsa::Krishnamurti => "Krishnamurti",
+            Ayanamsa::DjwhalKhul => "Djwhal Khul",
+            Ayanamsa::Yukteshwar => "Yukteshwar",
+            Ayanamsa::JnBhasin => "J.N. Bhasin",
+            Ayanamsa::BabylonianHuber => "Babylonian (Huber)",
+            Ayanamsa::Aldebaran15Tau => "Aldebaran 15 Tau",
+            Ayanamsa::Hipparchos => "Hipparchos",
+            Ayanamsa::Sassanian => "Sassanian",
+            Ayanamsa::GalacticCenter0Sag => "Galactic center 0 Sag",
+            Ayanamsa::TrueCitra => "True Citra",
+            Ayanamsa::TrueRevati => "True Revati",
+            Ayanamsa::TruePushya => "True Pushya",
+        }
+        .to_string()
+    }
+}
+
+/// Parse the english name of an ayanamsa (lahiri, fagan_bradley, ...)
+impl FromStr for Ayanamsa {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s
+            .trim()
+            .to_lowercase()
+            .replace(['-', ' ', '.'], "_")
+            .as_str()
+        {
+            "fagan_bradley" | "fagan" => Ok(Ayanamsa::FaganBradley),
+            "lahiri" | "chitrapaksha" => Ok(Ayanamsa::Lahiri),
+            "de_luce" | "deluce" => Ok(Ayanamsa::DeLuce),
+            "raman" => Ok(Ayanamsa::Raman),
+            "ushashashi" => Ok(Ayanamsa::Ushashashi),
+            "krishnamurti" | "kp" => Ok(Ayanamsa::Krishnamurti),
+            "djwhal_khul" => Ok(Ayanamsa::DjwhalKhul),
+            "yukteshwar" => Ok(Ayanamsa::Yukteshwar),
+            "jn_bhasin" | "j_n__bhasin" => Ok(Ayanamsa::JnBhasin),
+            "babylonian_huber" | "huber" => Ok(Ayanamsa::BabylonianHuber),
+            "aldebaran_15tau" | "aldebaran_15_tau" => {
+                Ok(Ayanamsa::Aldebaran15Tau)
+            },
+            "hipparchos" => Ok(Ayanamsa::Hipparchos),
+            "sassanian" => Ok(Ayanamsa::Sassanian),
+            "galactic_center_0sag" | "galactic_center_0_sag" => {
+                Ok(Ayanamsa::GalacticCenter0Sag)
+            },
+            "true_citra" | "true_chitra" => Ok(Ayanamsa::TrueCitra),
+            "true_revati" => Ok(Ayanamsa::TrueRevati),
+            "true_pushya" => Ok(Ayanamsa::TruePushya),
+            _ => Err(format!("{} isn't a known ayanamsa", s)),
+        }
+    }
+}
+
+/// Zodiac of the longitudes
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum Zodiac {
+    #[default]
+    Tropical,
+    Sidereal(Ayanamsa),
+}
+
+impl Zodiac {
+    /// Ayanamsa in degree at a julian day (UT), 0 for the tropical zodiac
+    pub fn ayanamsa(self, julian_day: f64) -> Result<f64, AstrologyError> {
+        match self {
+            Zodiac::Tropical => Ok(0.0),
+            Zodiac::Sidereal(ayanamsa) => {
+                swe_sidereal::set_sid_mode(ayanamsa.code());
+                let res = swe_sidereal::get_ayanamsa_ex_ut(
+                    julian_day,
+                    OptionalFlag::SwissEph as i32,
+                );
+                if res.result < 0 {
+                    Err(AstrologyError::CalculationFailed(res.serr))
+                } else {
+                    Ok(res.ayanamsa)
+                }
+            },
+        }
+    }
+
+    /// Longitude in this zodiac of a tropical longitude at a julian day (UT)
+    pub fn longitude(
+        self,
+        tropical: f64,
+        julian_day: f64,
+    ) -> Result<f64, AstrologyError> {
+        Ok(handler_swe17::degnorm(
+            tropical - self.ayanamsa(julian_day)?,
+        ))
+    }
+
+    /// Text of the zodiac with the value of the ayanamsa for the svg, None
+    /// for the tropical zodiac
+    pub fn label(self, ayanamsa: f64) -> Option<String> {
+        match self {
+            Zodiac::Tropical => None,
+            Zodiac::Sidereal(a) => {
+                let minutes = (ayanamsa * 60.0).round() as i64;
+                Some(format!(
+                    "Ayanamsa {} {}°{:02}'",
+                    a.text(),
+                    minutes / 60,
+                    minutes % 60
+                ))
+            },
+        }
+    }
+}
+
+/// Parse tropical or the name of an ayanamsa (sidereal zodiac)
+impl FromStr for Zodiac {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "tropical" => Ok(Zodiac::Tropical),
+            _ => Ayanamsa::from_str(s).map(Zodiac::Sidereal).map_err(|_| {
+                format!("{} isn't tropical or a known ayanamsa", s)
+            }),
+        }
+    }
+}
+
+/// Houses of swiss ephemeris (tropical) in the zodiac of ayanamsa
+/// The whole sign houses begin at 0° of the sign of the sidereal Asc
+pub(crate) fn zodiac_houses(
+    house: HousesResult,
+    ayanamsa: f64,
+    house_system: HouseSystem,
+) -> HousesResult {
+    if ayanamsa == 0.0 {
+        return house;
+    }
+    // ascmc: Asc, Mc, Armc (not a longitude), Vertex, equatorial Asc,
+    // co-Asc Koch, co-Asc Munkasey, polar Asc
+    let mut ascmc = house.ascmc;
+    for (i, a) in ascmc.iter_mut().enumerate().take(8) {
+        if i != 2 {
+            *a = handler_swe17::degnorm(*a - ayanamsa);
+        }
+    }
+    let cusps: Vec<f64> = match house_system {
+        HouseSystem::WholeSign => {
+            let sign = (ascmc[0] / 30.0).floor() * 30.0;
+            house
+                .cusps
+                .iter()
+                .enumerate()
+                .map(|(i, c)| {
+                    if (1..=12).contains(&i) {
+                        handler_swe17::degnorm(sign + (i - 1) as f64 * 30.0)
+                    } else {
+                        *c
+                    }
+                })
+                .collect()
+        },
+        _ => house
+            .cusps
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                if (1..=12).contains(&i) {
+                    handler_swe17::degnorm(c - ayanamsa)
+                } else {
+                    *c
+                }
+            })
+            .collect(),
+    };
+    HousesResult {
+        cusps,
+        ascmc,
+        result: house.result,
+    }
+}
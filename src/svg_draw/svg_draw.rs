@@ -17,19 +17,22 @@
 extern crate libswe_sys;
 extern crate strum;
 //use strum::AsStaticRef;
-use crate::svg_draw::angles::ANGLE_SIZE;
-use crate::svg_draw::bodies::BODIE_SIZE;
+use crate::svg_draw::angles::{draw_angle, ANGLE_SIZE};
+use crate::svg_draw::aspect_config::ChartAspect;
+use crate::svg_draw::bodies::{draw_bodie, BODIE_SIZE};
+use crate::svg_draw::chart_style::ChartStyle;
 use crate::svg_draw::houses::HOUSE_SIZE;
 use crate::svg_draw::numbers::{DEG_SIZE, MIN_SIZE};
+use crate::svg_draw::sect::SectLabelPart;
 use crate::svg_draw::zodiacs::ZODIAC_SIZE;
 use libswe_sys::sweconst::{
-    Angle, Aspects, Bodies, House, Language, Object, ObjectPos, Signs, Theme,
+    Angle, Bodies, House, Language, Object, ObjectPos, Signs,
 };
-use libswe_sys::swerust::handler_swe14::HousesResult;
 use std::f32;
 use strum::IntoEnumIterator;
 use svg::node::element::path::{Data, Number};
-use svg::node::element::{Circle, Group, Line, Path};
+use svg::node::element::{Circle, Group, Line, Path, Rectangle, Text};
+use svg::node::Text as TextNode;
 use svg::Document;
 
 // Working Storage - CONST
@@ -57,7 +60,7 @@ const CIRCLE_SIZE_NATAL: [(Number, bool); 9] = [
 
 /// Transit circle
 /// (Position, Bool visible)
-const CIRCLE_SIZE_TRANSIT: [(Number, bool); 12] = [
+const CIRCLE_SIZE_TRANSIT: [(Number, bool); 13] = [
     (45.0, true),  // 0 CIRCLE ASPECT
     (59.0, true),  // 1 CIRCLE TRANSIT
     (75.0, true),  // 2 CIRCLE ZODIAC END
@@ -71,6 +74,7 @@ const CIRCLE_SIZE_TRANSIT: [(Number, bool); 12] = [
     (49.0, false), // 9 Planet pos transit
     (57.5, false), // 10 - 7 transit
     (54.5, false), // 11 - 8 transit
+    (47.0, false), // 12 Angle pos transit houses
 ];
 
 // For draw min/sec (color for angle) / Bodies::EclNut = -1 not used
@@ -79,7 +83,23 @@ const ANGLE_BODIE: Bodies = Bodies::EclNut;
 const BODIE_DISTANCE_NATAL: Number = 8.0;
 const BODIE_DISTANCE_OFFSET_NATAL: Number = 0.5;
 const BODIE_DISTANCE_TRANSIT: Number = 13.5;
+
+// Size of the text of a label in % of max_size
+const LABEL_SIZE: Number = 3.0;
+// Marker on the circle of the aspects: half width in degree, height and size
+// of the text in % of the radius of this circle
+const MARKER_POINTER: Number = 2.0;
+const MARKER_HEIGHT: Number = 6.0;
+const MARKER_TEXT: Number = 5.0;
 const BODIE_DISTANCE_OFFSET_TRANSIT: Number = 0.5;
+// Ring of the fixed stars of the natal chart outside the glyphs of the
+// bodies, in % of the radius total: circle, half length of the ticks, begin
+// and end of the names and size of the text
+const STAR_RING: Number = 88.0;
+const STAR_TICK: Number = 1.0;
+const STAR_TEXT_BEGIN: Number = 90.0;
+const STAR_TEXT_END: Number = 99.0;
+const STAR_TEXT: Number = 2.2;
 
 // Working Storage - Enums
 // #[derive(Debug, Clone, PartialEq, Display)]
@@ -93,9 +113,10 @@ pub enum LargerDrawLine {
 #[derive(Debug, Clone)]
 pub struct WorkingStoragePolyMorphNatal {
     pub max_size: Number,
-    pub theme: Theme,
+    pub style: ChartStyle,
     pub lang: Language,
     pub house: Vec<House>,
+    pub angle: Vec<House>,
     pub object: Vec<Object>,
     pub temp_position_bodies: Vec<TempPositionBodies>,
 }
@@ -105,13 +126,18 @@ pub struct WorkingStoragePolyMorphNatal {
 #[derive(Debug, Clone)]
 pub struct WorkingStoragePolyMorphTransit {
     pub max_size: Number,
-    pub theme: Theme,
+    pub style: ChartStyle,
     pub lang: Language,
     pub house: Vec<House>,
+    pub angle: Vec<House>,
     pub object_natal: Vec<Object>,
     pub object_transit: Vec<Object>,
     pub temp_position_bodies_natal: Vec<TempPositionBodies>,
     pub temp_position_bodies_transit: Vec<TempPositionBodies>,
+    /// Houses of the transit location, empty if not drawn
+    pub house_transit: Vec<House>,
+    /// Angles of the transit location, empty if not drawn
+    pub angle_transit: Vec<House>,
 }
 
 /// Working storage for natal chart
@@ -128,6 +154,15 @@ pub struct WorkingStorageDrawPolyMorphTransit {
     pub ws: WorkingStoragePolyMorphTransit,
 }
 
+/// Corner of a label on the chart
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LabelCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
 /// Offset position x and y
 /// Type "Number" for working with "svg" crate
 #[derive(Debug, Clone)]
@@ -199,12 +234,13 @@ pub trait Draw {
         &self,
         lng_1: Number,
         lng_2: Number,
-        aspect: Aspects,
+        aspect: ChartAspect,
     ) -> SvgObject;
 }
 
 pub trait CalcDraw {
     fn get_radius_total(&self) -> Number;
+    fn get_asc_longitude(&self) -> Number;
     fn get_radius_circle(&self, occurs: usize) -> (Number, bool);
     fn get_radius_rules_inside_circle(
         &self,
@@ -256,34 +292,18 @@ pub trait CalcDraw {
 impl WorkingStoragePolyMorphNatal {
     pub fn new(
         max_size: Number,
-        theme: Theme,
+        style: ChartStyle,
         lang: Language,
-        house: HousesResult,
+        house: Vec<House>,
+        angle: Vec<House>,
         object: Vec<Object>,
     ) -> WorkingStoragePolyMorphNatal {
-        let mut h: Vec<House> = Vec::new();
-        for (i, res) in house.clone().cusps.iter().enumerate() {
-            if i > 0 {
-                // angle compatible Placidus
-                let angle;
-                angle = match i - 1 {
-                    0 => Angle::Asc,
-                    3 => Angle::Fc,
-                    6 => Angle::Desc,
-                    9 => Angle::Mc,
-                    _ => Angle::Nothing,
-                };
-                h.push(House::new(i as i32, res.clone(), angle));
-                if i + 1 > 12 {
-                    break;
-                }
-            }
-        }
         WorkingStoragePolyMorphNatal {
             max_size: max_size,
-            theme: theme,
+            style,
             lang: lang,
-            house: h,
+            house,
+            angle,
             object: object,
             temp_position_bodies: Vec::new(),
         }
@@ -293,41 +313,229 @@ impl WorkingStoragePolyMorphNatal {
 impl WorkingStoragePolyMorphTransit {
     pub fn new(
         max_size: Number,
-        theme: Theme,
+        style: ChartStyle,
         lang: Language,
-        house: HousesResult,
+        house: Vec<House>,
+        angle: Vec<House>,
         object_natal: Vec<Object>,
         object_transit: Vec<Object>,
     ) -> WorkingStoragePolyMorphTransit {
-        let mut h: Vec<House> = Vec::new();
-        for (i, res) in house.clone().cusps.iter().enumerate() {
-            if i > 0 {
-                // angle compatible Placidus
-                let angle;
-                angle = match i - 1 {
-                    0 => Angle::Asc,
-                    3 => Angle::Fc,
-                    6 => Angle::Desc,
-                    9 => Angle::Mc,
-                    _ => Angle::Nothing,
-                };
-                h.push(House::new(i as i32, res.clone(), angle));
-                if i + 1 > 12 {
-                    break;
-                }
-            }
-        }
         WorkingStoragePolyMorphTransit {
             max_size: max_size,
-            theme: theme,
+            style,
             lang: lang,
-            house: h,
+            house,
+            angle,
             object_natal: object_natal,
             object_transit: object_transit,
             temp_position_bodies_natal: Vec::new(),
             temp_position_bodies_transit: Vec::new(),
+            house_transit: Vec::new(),
+            angle_transit: Vec::new(),
         }
     }
+
+    /// Draw the houses and angles of the transit location in the transit
+    /// circle (relocated transit wheel)
+    pub fn set_transit_houses(&mut self, house: Vec<House>, angle: Vec<House>) {
+        self.house_transit = house;
+        self.angle_transit = angle;
+    }
+
+    /// Bodie computed in the natal (or transit) ring
+    pub fn get_bodie_is_on_ring(
+        &self,
+        bodie: Bodies,
+        sw_transit: bool,
+    ) -> bool {
+        let objects = if sw_transit {
+            &self.object_transit
+        } else {
+            &self.object_natal
+        };
+        objects.iter().any(|o| o.object_enum == bodie)
+    }
+}
+
+/// Background of the chart, nothing if the color is "none"
+pub(crate) fn draw_background(
+    document: Document,
+    style: &ChartStyle,
+    width: Number,
+    height: Number,
+) -> Document {
+    if style.background == "none" {
+        document
+    } else {
+        document.add(
+            Rectangle::new()
+                .set("x", 0)
+                .set("y", 0)
+                .set("width", width)
+                .set("height", height)
+                .set("fill", style.background.as_str()),
+        )
+    }
+}
+
+/// Label (name of a person, day ruler...) in a corner of the chart
+pub(crate) fn draw_label(
+    text: &str,
+    style: &ChartStyle,
+    max_size: Number,
+    corner: LabelCorner,
+) -> SvgObject {
+    let font_size = max_size * LABEL_SIZE / 100.0;
+    let size_x = max_size / 2.0;
+    let size_y = font_size * 1.5;
+    let (x, anchor, pos_x) = match corner {
+        LabelCorner::TopLeft | LabelCorner::BottomLeft => (0.0, "start", 0.0),
+        LabelCorner::TopRight | LabelCorner::BottomRight => {
+            (size_x, "end", max_size - size_x)
+        },
+    };
+    let document = Document::new().set("viewBox", (0, 0, size_x, size_y)).add(
+        Text::new()
+            .set("x", x)
+            .set("y", font_size)
+            .set("font-size", font_size)
+            .set("font-family", "sans-serif")
+            .set("text-anchor", anchor)
+            .set("fill", style.text.as_str())
+            .add(TextNode::new(escape_text(text))),
+    );
+    SvgObject {
+        svg: document.to_string(),
+        size_x,
+        size_y,
+        pos_x,
+        pos_y: match corner {
+            LabelCorner::TopLeft | LabelCorner::TopRight => 0.0,
+            LabelCorner::BottomLeft | LabelCorner::BottomRight => {
+                max_size - size_y
+            },
+        },
+    }
+}
+
+/// Text of the svg with & < > escaped (names of the lots and of the stars)
+fn escape_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Sect label in a corner of the chart, the texts are followed by the glyphs
+/// of the planets (bodies.rs)
+/// The width of a text is estimated at 0.6 font size by char
+pub(crate) fn draw_sect_label(
+    parts: &[SectLabelPart],
+    style: &ChartStyle,
+    max_size: Number,
+    corner: LabelCorner,
+) -> SvgObject {
+    let font_size = max_size * LABEL_SIZE / 100.0;
+    let size_x = max_size / 2.0;
+    let size_y = font_size * 1.5;
+    // The texts have the spaces around the glyphs
+    let glyph_size = font_size * 1.2;
+    let width = |part: &SectLabelPart| match part {
+        SectLabelPart::Text(t) => 0.6 * font_size * t.chars().count() as Number,
+        SectLabelPart::Planet(_) => glyph_size,
+    };
+    let total: Number = parts.iter().map(width).sum();
+    let (mut x, pos_x) = match corner {
+        LabelCorner::TopLeft | LabelCorner::BottomLeft => (0.0, 0.0),
+        LabelCorner::TopRight | LabelCorner::BottomRight => {
+            ((size_x - total).max(0.0), max_size - size_x)
+        },
+    };
+    let mut document = Document::new().set("viewBox", (0, 0, size_x, size_y));
+    for part in parts.iter() {
+        document = match part {
+            SectLabelPart::Text(t) => document.add(
+                Text::new()
+                    .set("x", x)
+                    .set("y", font_size)
+                    .set("font-size", font_size)
+                    .set("font-family", "sans-serif")
+                    .set("fill", style.text.as_str())
+                    .add(TextNode::new(escape_text(t))),
+            ),
+            SectLabelPart::Planet(b) => document.add(
+                draw_bodie(*b, false, style)
+                    .set("x", x)
+                    .set("y", 0.0)
+                    .set("width", glyph_size)
+                    .set("height", glyph_size),
+            ),
+        };
+        x += width(part);
+    }
+    SvgObject {
+        svg: document.to_string(),
+        size_x,
+        size_y,
+        pos_x,
+        pos_y: match corner {
+            LabelCorner::TopLeft | LabelCorner::TopRight => 0.0,
+            LabelCorner::BottomLeft | LabelCorner::BottomRight => {
+                max_size - size_y
+            },
+        },
+    }
+}
+
+/// Marker of a point of the zodiac: a triangle pointing to the longitude on
+/// the circle of the aspects (circle 0) and a text
+fn draw_marker<T: CalcDraw>(
+    ws: &T,
+    max_size: Number,
+    style: &ChartStyle,
+    longitude: f64,
+    text: &str,
+) -> SvgObject {
+    let pos =
+        ws.get_fix_pos(360.0 - ws.get_asc_longitude() + longitude as Number);
+    let radius = ws.get_radius_circle(0).0;
+    let height = radius * MARKER_HEIGHT / 100.0;
+    let tria =
+        ws.get_triangle_path(pos, MARKER_POINTER, radius - height, radius);
+    let pos_text = ws.get_pos_trigo(pos, radius - height * 2.0);
+    let font_size = radius * MARKER_TEXT / 100.0;
+    let document = Document::new()
+        .set("viewBox", (0, 0, max_size as i32, max_size as i32))
+        .add(
+            Path::new()
+                .set("fill", style.angle.as_str())
+                .set("stroke", style.angle.as_str())
+                .set("stroke-width", 1)
+                .set(
+                    "d",
+                    Data::new()
+                        .move_to((tria[2].x, tria[2].y))
+                        .line_to((tria[0].x, tria[0].y))
+                        .line_to((tria[1].x, tria[1].y))
+                        .close(),
+                ),
+        )
+        .add(
+            Text::new()
+                .set("x", pos_text.x)
+                .set("y", pos_text.y + font_size / 3.0)
+                .set("font-size", font_size)
+                .set("font-family", "sans-serif")
+                .set("text-anchor", "middle")
+                .set("fill", style.angle.as_str())
+                .add(TextNode::new(escape_text(text))),
+        );
+    SvgObject {
+        svg: document.to_string(),
+        size_x: max_size,
+        size_y: max_size,
+        pos_x: 0.0,
+        pos_y: 0.0,
+    }
 }
 
 impl WorkingStorageDrawPolyMorphNatal {
@@ -336,6 +544,92 @@ impl WorkingStorageDrawPolyMorphNatal {
     ) -> WorkingStorageDrawPolyMorphNatal {
         WorkingStorageDrawPolyMorphNatal { ws: ws }
     }
+
+    /// Marker of a point of the zodiac (lot): a triangle pointing to the
+    /// longitude on the circle of the aspects and a text
+    pub fn draw_marker(&self, longitude: f64, text: &str) -> SvgObject {
+        draw_marker(&self.ws, self.ws.max_size, &self.ws.style, longitude, text)
+    }
+
+    /// Fixed stars on an outer ring: a circle, a tick on the longitude of
+    /// each star and its name along the radius
+    pub fn draw_stars(&self, stars: &[(f64, &str)]) -> SvgObject {
+        let radius = self.ws.get_radius_total();
+        let center = self.ws.get_center();
+        let mut document = Document::new()
+            .set(
+                "viewBox",
+                (0, 0, self.ws.max_size as i32, self.ws.max_size as i32),
+            )
+            .add(
+                Circle::new()
+                    .set("cx", center.x)
+                    .set("cy", center.y)
+                    .set("r", radius * STAR_RING / 100.0)
+                    .set("fill", "none")
+                    .set("stroke", self.ws.style.circle.as_str())
+                    .set("stroke-width", 0.5),
+            );
+        for (longitude, name) in stars.iter() {
+            let pos = self.ws.get_fix_pos(
+                360.0 - self.ws.get_asc_longitude() + *longitude as Number,
+            );
+            let tick = self.ws.get_line_trigo(
+                pos,
+                radius * (STAR_RING - STAR_TICK) / 100.0,
+                radius * (STAR_RING + STAR_TICK) / 100.0,
+            );
+            let begin =
+                self.ws.get_pos_trigo(pos, radius * STAR_TEXT_BEGIN / 100.0);
+            // Direction of the radius on the screen, the names on the left
+            // half are turned to be read from left to right
+            let mut rotate = 180.0 - pos;
+            let mut anchor = "start";
+            if (rotate / CIRCLE * 2.0 * f32::consts::PI).cos() < 0.0 {
+                rotate += 180.0;
+                anchor = "end";
+            }
+            let length = radius * (STAR_TEXT_END - STAR_TEXT_BEGIN) / 100.0;
+            let font_size = (radius * STAR_TEXT / 100.0)
+                .min(length / (0.6 * name.chars().count().max(1) as Number));
+            let text = escape_text(name);
+            document = document
+                .add(
+                    Line::new()
+                        .set("x1", tick[0].x)
+                        .set("y1", tick[0].y)
+                        .set("x2", tick[1].x)
+                        .set("y2", tick[1].y)
+                        .set("stroke", self.ws.style.text.as_str())
+                        .set("stroke-width", 1),
+                )
+                .add(
+                    Text::new()
+                        .set("x", begin.x)
+                        .set("y", begin.y)
+                        .set("font-size", font_size)
+                        .set("font-family", "sans-serif")
+                        .set("text-anchor", anchor)
+                        .set("dominant-baseline", "middle")
+                        .set(
+                            "transform",
+                            format!(
+                                "rotate({},{},{})",
+                                rotate, begin.x, begin.y
+                            ),
+                        )
+                        .set("fill", self.ws.style.text.as_str())
+                        .add(TextNode::new(text)),
+                );
+        }
+        SvgObject {
+            svg: document.to_string(),
+            size_x: self.ws.max_size,
+            size_y: self.ws.max_size,
+            pos_x: 0.0,
+            pos_y: 0.0,
+        }
+    }
 }
 
 impl WorkingStorageDrawPolyMorphTransit {
@@ -344,6 +638,12 @@ impl WorkingStorageDrawPolyMorphTransit {
     ) -> WorkingStorageDrawPolyMorphTransit {
         WorkingStorageDrawPolyMorphTransit { ws: ws }
     }
+
+    /// Marker of a point of the zodiac (prenatal syzygy, eclipse): a triangle
+    /// pointing to the longitude on the circle of the aspects and a text
+    pub fn draw_marker(&self, longitude: f64, text: &str) -> SvgObject {
+        draw_marker(&self.ws, self.ws.max_size, &self.ws.style, longitude, text)
+    }
 }
 
 impl Draw for WorkingStorageDrawPolyMorphNatal {
@@ -365,7 +665,7 @@ impl Draw for WorkingStorageDrawPolyMorphNatal {
                         .set("cx", center.0)
                         .set("cy", center.1)
                         .set("r", calc_draw.get_radius_circle(i).0)
-                        .set("stroke", "black")
+                        .set("stroke", self.ws.style.circle.as_str())
                         .set("stroke-width", 1),
                 );
             }
@@ -384,7 +684,7 @@ impl Draw for WorkingStorageDrawPolyMorphNatal {
             let sign = i as i32;
             // 0°
             // temporary Aries 0°0'0"
-            let off_pos_asc: f32 = 360.0 - self.ws.house[0].longitude as f32;
+            let off_pos_asc: f32 = 360.0 - self.ws.get_asc_longitude();
             let mut pos = sign as f32 * 30.0 + &off_pos_asc;
             pos = self.ws.get_fix_pos(pos);
             let a_xy: [Offset; 2] = self.ws.get_line_trigo(
@@ -398,7 +698,7 @@ impl Draw for WorkingStorageDrawPolyMorphNatal {
                     .set("y1", a_xy[0].y)
                     .set("x2", a_xy[1].x)
                     .set("y2", a_xy[1].y)
-                    .set("stroke", "black")
+                    .set("stroke", self.ws.style.circle.as_str())
                     .set("stroke-width", 1),
             );
             // 1° to 29°
@@ -421,7 +721,7 @@ impl Draw for WorkingStorageDrawPolyMorphNatal {
                         .set("y1", a_xy_line[0].y)
                         .set("x2", a_xy_line[1].x)
                         .set("y2", a_xy_line[1].y)
-                        .set("stroke", "black")
+                        .set("stroke", self.ws.style.circle.as_str())
                         .set("stroke-width", 1),
                 );
             }
@@ -442,7 +742,7 @@ impl Draw for WorkingStorageDrawPolyMorphNatal {
         let mut triangle_house = Vec::new();
         // For all 12 house delimiter
         for i in 0..12 {
-            let offset_house: f32 = 360.0 - self.ws.house[0].longitude as f32;
+            let offset_house: f32 = 360.0 - self.ws.get_asc_longitude();
             let mut house_pos: f32 =
                 offset_house + self.ws.house[i].longitude as f32;
             house_pos = self.ws.get_fix_pos(house_pos);
@@ -471,7 +771,7 @@ impl Draw for WorkingStorageDrawPolyMorphNatal {
                         .set("y1", a_xy_line[0].y)
                         .set("x2", a_xy_line[1].x)
                         .set("y2", a_xy_line[1].y)
-                        .set("stroke", "black")
+                        .set("stroke", self.ws.style.circle.as_str())
                         .set("stroke-width", 1),
                 );
             } else {
@@ -484,8 +784,8 @@ impl Draw for WorkingStorageDrawPolyMorphNatal {
             }
             triangle_house.push(
                 Path::new()
-                    .set("fill", "black")
-                    .set("stroke", "black")
+                    .set("fill", self.ws.style.circle.as_str())
+                    .set("stroke", self.ws.style.circle.as_str())
                     .set("stroke-width", 1)
                     .set(
                         "d",
@@ -506,19 +806,24 @@ impl Draw for WorkingStorageDrawPolyMorphNatal {
             group_house = group_house.add(triangle_house[i].clone());
         }
 
-        let document = Document::new()
-            //.set("baseProfile", "full")
-            //.set("version", "1.1")
-            //.set("xmlns:xlink", "http://www.w3.org/1999/xlink")
-            .set(
-                "viewBox",
-                (0, 0, self.ws.max_size as i32, self.ws.max_size as i32),
-            )
-            .add(circle[0].clone())
-            .add(circle[1].clone())
-            .add(circle[2].clone())
-            .add(group_degre)
-            .add(group_house);
+        let document = draw_background(
+            Document::new(),
+            &self.ws.style,
+            self.ws.max_size,
+            self.ws.max_size,
+        )
+        //.set("baseProfile", "full")
+        //.set("version", "1.1")
+        //.set("xmlns:xlink", "http://www.w3.org/1999/xlink")
+        .set(
+            "viewBox",
+            (0, 0, self.ws.max_size as i32, self.ws.max_size as i32),
+        )
+        .add(circle[0].clone())
+        .add(circle[1].clone())
+        .add(circle[2].clone())
+        .add(group_degre)
+        .add(group_house);
         document
     }
 
@@ -529,7 +834,7 @@ impl Draw for WorkingStorageDrawPolyMorphNatal {
         let zodiac_size =
             (((ZODIAC_SIZE * zodiac_ratio) / 100.0) * self.ws.max_size) / 100.0;
 
-        let off_pos_asc: f32 = 360.0 - self.ws.house[0].longitude as f32;
+        let off_pos_asc: f32 = 360.0 - self.ws.get_asc_longitude();
         let mut pos =
             ((sign.clone() as u64 - 1) as f32 * 30.0) + 15.0 + &off_pos_asc;
         pos = self.ws.get_fix_pos(pos);
@@ -555,7 +860,7 @@ impl Draw for WorkingStorageDrawPolyMorphNatal {
         let house_ratio: Number = 5.0; // To do a const
         let house_size =
             (((HOUSE_SIZE * house_ratio) / 100.0) * self.ws.max_size) / 100.0;
-        let off_pos_asc: f32 = 360.0 - self.ws.house[0].longitude as f32;
+        let off_pos_asc: f32 = 360.0 - self.ws.get_asc_longitude();
         let pos_next: Number;
         if numero > 11 {
             pos_next = self.ws.house[0].longitude as f32 + &off_pos_asc;
@@ -613,7 +918,7 @@ impl Draw for WorkingStorageDrawPolyMorphNatal {
         let pos: Number = self.ws.get_angle_longitude(angle.clone());
         let pos_fix: Number = self.ws.get_angle_fix_longitude(angle.clone());
 
-        for h in self.ws.house.clone() {
+        for h in self.ws.angle.clone() {
             if h.angle.clone() == angle {
                 svg_deg =
                     self.numbers_draw_degre(h.split.deg as i16, ANGLE_BODIE);
@@ -640,8 +945,7 @@ impl Draw for WorkingStorageDrawPolyMorphNatal {
         );
 
         // Trait
-        let color: String =
-            format!("#{:06X}", ANGLE_BODIE.object_color(self.ws.theme) as i32);
+        let color: String = self.ws.style.bodie_color(ANGLE_BODIE);
         let t_xy_begin: [Offset; 2] = self.ws.get_line_trigo(
             pos,
             self.ws.get_radius_circle(2).0,
@@ -755,8 +1059,7 @@ impl Draw for WorkingStorageDrawPolyMorphNatal {
         );
 
         // Trait
-        let color: String =
-            format!("#{:06X}", bodie.object_color(self.ws.theme) as i32);
+        let color: String = self.ws.style.bodie_color(bodie);
         let t_xy_begin: [Offset; 2] = self.ws.get_line_trigo(
             pos,
             self.ws.get_radius_circle(2).0,
@@ -818,92 +1121,104 @@ impl Draw for WorkingStorageDrawPolyMorphNatal {
         &self,
         lng_1: Number,
         lng_2: Number,
-        aspect: Aspects,
+        aspect: ChartAspect,
     ) -> SvgObject {
         let pos1 = self.ws.get_pos_trigo(lng_1, self.ws.get_radius_circle(0).0);
         let pos2 = self.ws.get_pos_trigo(lng_2, self.ws.get_radius_circle(0).0);
+        let color: String = self.ws.style.aspect_color(aspect);
         let line;
         match aspect {
-            Aspects::Conjunction => {
+            ChartAspect::Conjunction => {
                 line = Line::new()
                     .set("x1", pos1.x)
                     .set("y1", pos1.y)
                     .set("x2", pos2.x)
                     .set("y2", pos2.y)
-                    .set("stroke", "red")
+                    .set("stroke", color.as_str())
                     .set("stroke-width", 2);
             },
-            Aspects::Opposition => {
+            ChartAspect::Opposition => {
                 line = Line::new()
                     .set("x1", pos1.x)
                     .set("y1", pos1.y)
                     .set("x2", pos2.x)
                     .set("y2", pos2.y)
                     .set("stroke-dasharray", "4, 1, 2")
-                    .set("stroke", "red")
+                    .set("stroke", color.as_str())
                     .set("stroke-width", 1);
             },
-            Aspects::Trine => {
+            ChartAspect::Trine => {
                 line = Line::new()
                     .set("x1", pos1.x)
                     .set("y1", pos1.y)
                     .set("x2", pos2.x)
                     .set("y2", pos2.y)
-                    .set("stroke", "blue")
+                    .set("stroke", color.as_str())
                     .set("stroke-width", 1);
             },
-            Aspects::Square => {
+            ChartAspect::Square => {
                 line = Line::new()
                     .set("x1", pos1.x)
                     .set("y1", pos1.y)
                     .set("x2", pos2.x)
                     .set("y2", pos2.y)
-                    .set("stroke", "red")
+                    .set("stroke", color.as_str())
                     .set("stroke-width", 1);
             },
-            Aspects::Sextile => {
+            ChartAspect::Sextile => {
                 line = Line::new()
                     .set("x1", pos1.x)
                     .set("y1", pos1.y)
                     .set("x2", pos2.x)
                     .set("y2", pos2.y)
-                    .set("stroke", "green")
+                    .set("stroke", color.as_str())
                     .set("stroke-width", 1);
             },
-            Aspects::Inconjunction => {
+            ChartAspect::Inconjunction => {
                 line = Line::new()
                     .set("x1", pos1.x)
                     .set("y1", pos1.y)
                     .set("x2", pos2.x)
                     .set("y2", pos2.y)
-                    .set("stroke", "violet")
+                    .set("stroke", color.as_str())
                     .set("stroke-width", 1);
             },
-            Aspects::Sesquisquare => {
+            ChartAspect::Sesquisquare => {
                 line = Line::new()
                     .set("x1", pos1.x)
                     .set("y1", pos1.y)
                     .set("x2", pos2.x)
                     .set("y2", pos2.y)
-                    .set("stroke", "brown")
+                    .set("stroke", color.as_str())
                     .set("stroke-width", 1);
             },
-            Aspects::Semisquare => {
+            ChartAspect::Semisquare => {
                 line = Line::new()
                     .set("x1", pos1.x)
                     .set("y1", pos1.y)
                     .set("x2", pos2.x)
                     .set("y2", pos2.y)
-                    .set("stroke", "brown")
+                    .set("stroke", color.as_str())
                     .set("stroke-width", 1);
             },
-            Aspects::Semisextile => {
+            ChartAspect::Semisextile => {
                 line = Line::new()
                     .set("x1", pos1.x)
                     .set("y1", pos1.y)
                     .set("x2", pos2.x)
                     .set("y2", pos2.y)
-                    .set("stroke", "violet")
+                    .set("stroke", color.as_str())
+                    .set("stroke-width", 1);
+            },
+            _ => {
+                // Harmonic aspects (quintile, septile, novile)
+                line = Line::new()
+                    .set("x1", pos1.x)
+                    .set("y1", pos1.y)
+                    .set("x2", pos2.x)
+                    .set("y2", pos2.y)
+                    .set("stroke-dasharray", "1, 2")
+                    .set("stroke", color.as_str())
                     .set("stroke-width", 1);
             },
         }
@@ -943,7 +1258,7 @@ impl Draw for WorkingStorageDrawPolyMorphTransit {
                         .set("cx", center.0)
                         .set("cy", center.1)
                         .set("r", calc_draw.get_radius_circle(i).0)
-                        .set("stroke", "black")
+                        .set("stroke", self.ws.style.circle.as_str())
                         .set("stroke-width", 1),
                 );
             }
@@ -962,7 +1277,7 @@ impl Draw for WorkingStorageDrawPolyMorphTransit {
             let sign = i as i32;
             // 0°
             // temporary Aries 0°0'0"
-            let off_pos_asc: f32 = 360.0 - self.ws.house[0].longitude as f32;
+            let off_pos_asc: f32 = 360.0 - self.ws.get_asc_longitude();
             let mut pos = sign as f32 * 30.0 + &off_pos_asc;
             pos = self.ws.get_fix_pos(pos);
             let a_xy: [Offset; 2] = self.ws.get_line_trigo(
@@ -976,7 +1291,7 @@ impl Draw for WorkingStorageDrawPolyMorphTransit {
                     .set("y1", a_xy[0].y)
                     .set("x2", a_xy[1].x)
                     .set("y2", a_xy[1].y)
-                    .set("stroke", "black")
+                    .set("stroke", self.ws.style.circle.as_str())
                     .set("stroke-width", 1),
             );
             // 1° to 29°
@@ -999,7 +1314,7 @@ impl Draw for WorkingStorageDrawPolyMorphTransit {
                         .set("y1", a_xy_line[0].y)
                         .set("x2", a_xy_line[1].x)
                         .set("y2", a_xy_line[1].y)
-                        .set("stroke", "black")
+                        .set("stroke", self.ws.style.circle.as_str())
                         .set("stroke-width", 1),
                 );
             }
@@ -1020,7 +1335,7 @@ impl Draw for WorkingStorageDrawPolyMorphTransit {
         let mut triangle_house = Vec::new();
         // For all 12 house delimiter
         for i in 0..12 {
-            let offset_house: f32 = 360.0 - self.ws.house[0].longitude as f32;
+            let offset_house: f32 = 360.0 - self.ws.get_asc_longitude();
             let mut house_pos: f32 =
                 offset_house + self.ws.house[i].longitude as f32;
             house_pos = self.ws.get_fix_pos(house_pos);
@@ -1049,7 +1364,7 @@ impl Draw for WorkingStorageDrawPolyMorphTransit {
                         .set("y1", a_xy_line[0].y)
                         .set("x2", a_xy_line[1].x)
                         .set("y2", a_xy_line[1].y)
-                        .set("stroke", "black")
+                        .set("stroke", self.ws.style.circle.as_str())
                         .set("stroke-width", 1),
                 );
             } else {
@@ -1062,8 +1377,8 @@ impl Draw for WorkingStorageDrawPolyMorphTransit {
             }
             triangle_house.push(
                 Path::new()
-                    .set("fill", "black")
-                    .set("stroke", "black")
+                    .set("fill", self.ws.style.circle.as_str())
+                    .set("stroke", self.ws.style.circle.as_str())
                     .set("stroke-width", 1)
                     .set(
                         "d",
@@ -1084,20 +1399,77 @@ impl Draw for WorkingStorageDrawPolyMorphTransit {
             group_house = group_house.add(triangle_house[i].clone());
         }
 
-        let document = Document::new()
-            //.set("baseProfile", "full")
-            //.set("version", "1.1")
-            //.set("xmlns:xlink", "http://www.w3.org/1999/xlink")
-            .set(
-                "viewBox",
-                (0, 0, self.ws.max_size as i32, self.ws.max_size as i32),
-            )
-            .add(circle[0].clone())
-            .add(circle[1].clone())
-            .add(circle[2].clone())
-            .add(circle[3].clone())
-            .add(group_degre)
-            .add(group_house);
+        // Houses of the transit location in the transit circle, dashed for
+        // the cusps and in the color of the angles for the angles
+        let mut group_house_transit: Group = Group::new();
+        let offset_house: f32 = 360.0 - self.ws.get_asc_longitude();
+        for h in self.ws.house_transit.iter() {
+            let house_pos =
+                self.ws.get_fix_pos(offset_house + h.longitude as f32);
+            let a_xy_line: [Offset; 2] = self.ws.get_line_trigo(
+                house_pos,
+                self.ws.get_radius_circle(1).0,
+                self.ws.get_radius_circle(0).0,
+            );
+            let line = Line::new()
+                .set("x1", a_xy_line[0].x)
+                .set("y1", a_xy_line[0].y)
+                .set("x2", a_xy_line[1].x)
+                .set("y2", a_xy_line[1].y);
+            group_house_transit = if h.angle == Angle::Nothing {
+                group_house_transit.add(
+                    line.set("stroke", self.ws.style.circle.as_str())
+                        .set("stroke-width", 1)
+                        .set("stroke-dasharray", "3, 3"),
+                )
+            } else {
+                group_house_transit.add(
+                    line.set("stroke", self.ws.style.angle.as_str())
+                        .set("stroke-width", 2),
+                )
+            };
+        }
+        let angle_size = (ANGLE_SIZE * 3.0 / 100.0) * self.ws.max_size / 100.0;
+        for a in self.ws.angle_transit.iter() {
+            if !self.ws.get_angle_is_on_chart(a.angle) {
+                continue;
+            }
+            let angle_pos =
+                self.ws.get_fix_pos(offset_house + a.longitude as f32);
+            let offset: Offset = self.ws.get_center_item(
+                angle_size,
+                self.ws
+                    .get_pos_trigo(angle_pos, self.ws.get_radius_circle(12).0),
+            );
+            group_house_transit = group_house_transit.add(
+                draw_angle(a.angle, &self.ws.style)
+                    .set("x", offset.x)
+                    .set("y", offset.y)
+                    .set("width", angle_size)
+                    .set("height", angle_size),
+            );
+        }
+
+        let document = draw_background(
+            Document::new(),
+            &self.ws.style,
+            self.ws.max_size,
+            self.ws.max_size,
+        )
+        //.set("baseProfile", "full")
+        //.set("version", "1.1")
+        //.set("xmlns:xlink", "http://www.w3.org/1999/xlink")
+        .set(
+            "viewBox",
+            (0, 0, self.ws.max_size as i32, self.ws.max_size as i32),
+        )
+        .add(circle[0].clone())
+        .add(circle[1].clone())
+        .add(circle[2].clone())
+        .add(circle[3].clone())
+        .add(group_degre)
+        .add(group_house)
+        .add(group_house_transit);
         document
     }
 
@@ -1108,7 +1480,7 @@ impl Draw for WorkingStorageDrawPolyMorphTransit {
         let zodiac_size =
             (((ZODIAC_SIZE * zodiac_ratio) / 100.0) * self.ws.max_size) / 100.0;
 
-        let off_pos_asc: f32 = 360.0 - self.ws.house[0].longitude as f32;
+        let off_pos_asc: f32 = 360.0 - self.ws.get_asc_longitude();
         let mut pos =
             ((sign.clone() as u64 - 1) as f32 * 30.0) + 15.0 + &off_pos_asc;
         pos = self.ws.get_fix_pos(pos);
@@ -1134,7 +1506,7 @@ impl Draw for WorkingStorageDrawPolyMorphTransit {
         let house_ratio: Number = 5.0; // To do a const
         let house_size =
             (((HOUSE_SIZE * house_ratio) / 100.0) * self.ws.max_size) / 100.0;
-        let off_pos_asc: f32 = 360.0 - self.ws.house[0].longitude as f32;
+        let off_pos_asc: f32 = 360.0 - self.ws.get_asc_longitude();
         let pos_next: Number;
         if numero > 11 {
             pos_next = self.ws.house[0].longitude as f32 + &off_pos_asc;
@@ -1192,7 +1564,7 @@ impl Draw for WorkingStorageDrawPolyMorphTransit {
         let pos: Number = self.ws.get_angle_longitude(angle.clone());
         let pos_fix: Number = self.ws.get_angle_fix_longitude(angle.clone());
 
-        for h in self.ws.house.clone() {
+        for h in self.ws.angle.clone() {
             if h.angle.clone() == angle {
                 svg_deg =
                     self.numbers_draw_degre(h.split.deg as i16, ANGLE_BODIE);
@@ -1219,8 +1591,7 @@ impl Draw for WorkingStorageDrawPolyMorphTransit {
         );
 
         // Trait
-        let color: String =
-            format!("#{:06X}", ANGLE_BODIE.object_color(self.ws.theme) as i32);
+        let color: String = self.ws.style.bodie_color(ANGLE_BODIE);
         let t_xy_begin: [Offset; 2] = self.ws.get_line_trigo(
             pos,
             self.ws.get_radius_circle(3).0,
@@ -1368,8 +1739,7 @@ impl Draw for WorkingStorageDrawPolyMorphTransit {
         );
 
         // Trait
-        let color: String =
-            format!("#{:06X}", bodie.object_color(self.ws.theme) as i32);
+        let color: String = self.ws.style.bodie_color(bodie);
         let t_xy_begin: [Offset; 2] = if sw_transit {
             self.ws.get_line_trigo(
                 pos,
@@ -1447,92 +1817,104 @@ impl Draw for WorkingStorageDrawPolyMorphTransit {
         &self,
         lng_1: Number,
         lng_2: Number,
-        aspect: Aspects,
+        aspect: ChartAspect,
     ) -> SvgObject {
         let pos1 = self.ws.get_pos_trigo(lng_1, self.ws.get_radius_circle(0).0);
         let pos2 = self.ws.get_pos_trigo(lng_2, self.ws.get_radius_circle(0).0);
+        let color: String = self.ws.style.aspect_color(aspect);
         let line;
         match aspect {
-            Aspects::Conjunction => {
+            ChartAspect::Conjunction => {
                 line = Line::new()
                     .set("x1", pos1.x)
                     .set("y1", pos1.y)
                     .set("x2", pos2.x)
                     .set("y2", pos2.y)
-                    .set("stroke", "red")
+                    .set("stroke", color.as_str())
                     .set("stroke-width", 2);
             },
-            Aspects::Opposition => {
+            ChartAspect::Opposition => {
                 line = Line::new()
                     .set("x1", pos1.x)
                     .set("y1", pos1.y)
                     .set("x2", pos2.x)
                     .set("y2", pos2.y)
                     .set("stroke-dasharray", "4, 1, 2")
-                    .set("stroke", "red")
+                    .set("stroke", color.as_str())
+                    .set("stroke-width", 1);
+            },
+            ChartAspect::Trine => {
+                line = Line::new()
+                    .set("x1", pos1.x)
+                    .set("y1", pos1.y)
+                    .set("x2", pos2.x)
+                    .set("y2", pos2.y)
+                    .set("stroke", color.as_str())
                     .set("stroke-width", 1);
             },
-            Aspects::Trine => {
+            ChartAspect::Square => {
                 line = Line::new()
                     .set("x1", pos1.x)
                     .set("y1", pos1.y)
                     .set("x2", pos2.x)
                     .set("y2", pos2.y)
-                    .set("stroke", "blue")
+                    .set("stroke", color.as_str())
                     .set("stroke-width", 1);
             },
-            Aspects::Square => {
+            ChartAspect::Sextile => {
                 line = Line::new()
                     .set("x1", pos1.x)
                     .set("y1", pos1.y)
                     .set("x2", pos2.x)
                     .set("y2", pos2.y)
-                    .set("stroke", "red")
+                    .set("stroke", color.as_str())
                     .set("stroke-width", 1);
             },
-            Aspects::Sextile => {
+            ChartAspect::Inconjunction => {
                 line = Line::new()
                     .set("x1", pos1.x)
                     .set("y1", pos1.y)
                     .set("x2", pos2.x)
                     .set("y2", pos2.y)
-                    .set("stroke", "green")
+                    .set("stroke", color.as_str())
                     .set("stroke-width", 1);
             },
-            Aspects::Inconjunction => {
+            ChartAspect::Sesquisquare => {
                 line = Line::new()
                     .set("x1", pos1.x)
                     .set("y1", pos1.y)
                     .set("x2", pos2.x)
                     .set("y2", pos2.y)
-                    .set("stroke", "violet")
+                    .set("stroke", color.as_str())
                     .set("stroke-width", 1);
             },
-            Aspects::Sesquisquare => {
+            ChartAspect::Semisquare => {
                 line = Line::new()
                     .set("x1", pos1.x)
                     .set("y1", pos1.y)
                     .set("x2", pos2.x)
                     .set("y2", pos2.y)
-                    .set("stroke", "brown")
+                    .set("stroke", color.as_str())
                     .set("stroke-width", 1);
             },
-            Aspects::Semisquare => {
+            ChartAspect::Semisextile => {
                 line = Line::new()
                     .set("x1", pos1.x)
                     .set("y1", pos1.y)
                     .set("x2", pos2.x)
                     .set("y2", pos2.y)
-                    .set("stroke", "brown")
+                    .set("stroke", color.as_str())
                     .set("stroke-width", 1);
             },
-            Aspects::Semisextile => {
+            _ => {
+                // Harmonic aspects (quintile, septile, novile)
                 line = Line::new()
                     .set("x1", pos1.x)
                     .set("y1", pos1.y)
                     .set("x2", pos2.x)
                     .set("y2", pos2.y)
-                    .set("stroke", "violet")
+                    .set("stroke-dasharray", "1, 2")
+                    .set("stroke", color.as_str())
                     .set("stroke-width", 1);
             },
         }
@@ -1558,14 +1940,26 @@ impl CalcDraw for WorkingStoragePolyMorphNatal {
         self.max_size / 2.0
     }
 
+    /// Longitude of the Ascendant, the chart is rotated on this value
+    fn get_asc_longitude(&self) -> Number {
+        let mut pos: Number = 0.0;
+        for a in self.angle.iter() {
+            if a.angle == Angle::Asc {
+                pos = a.longitude as Number;
+                break;
+            }
+        }
+        pos
+    }
+
+    /// Out of range: the radius total not visible
     fn get_radius_circle(&self, occurs: usize) -> (Number, bool) {
-        if occurs > CIRCLE_SIZE_NATAL.len() {
-            panic!("Out of range in circle occurs: {}", occurs);
+        match CIRCLE_SIZE_NATAL.get(occurs) {
+            Some(circle) => {
+                ((self.get_radius_total() * circle.0) / 100.0, circle.1)
+            },
+            None => (self.get_radius_total(), false),
         }
-        (
-            (self.get_radius_total() * CIRCLE_SIZE_NATAL[occurs].0) / 100.0,
-            CIRCLE_SIZE_NATAL[occurs].1,
-        )
     }
 
     fn get_radius_rules_inside_circle(
@@ -1759,37 +2153,16 @@ impl CalcDraw for WorkingStoragePolyMorphNatal {
             false
         }
     }
+    /// The bodies of the chart are the computed objects (BodySelection)
     fn get_bodie_is_on_chart(&self, bodie: Bodies) -> bool {
-        if bodie == Bodies::Sun
-            || bodie == Bodies::Moon
-            || bodie == Bodies::Mars
-            || bodie == Bodies::Mercury
-            || bodie == Bodies::Venus
-            || bodie == Bodies::Jupiter
-            || bodie == Bodies::Saturn
-            || bodie == Bodies::Uranus
-            || bodie == Bodies::Neptune
-            || bodie == Bodies::Pluto
-            || bodie == Bodies::TrueNode // North Node true
-            || bodie == Bodies::Chiron
-       //     || bodie == Bodies::MeanApog // AsteroidLilith != Dark moon mean
-            || bodie == Bodies::OscuApog // AsteroidLilith != Dark moon true
-            || bodie == Bodies::Ceres
-            || bodie == Bodies::SouthNode
-            || bodie == Bodies::FortunaPart
-        {
-            true
-        } else {
-            false
-        }
+        self.object.iter().any(|o| o.object_enum == bodie)
     }
 
     fn get_angle_longitude(&self, angle: Angle) -> Number {
         let mut pos: Number = 0.0;
-        for h in self.house.clone() {
+        for h in self.angle.clone() {
             if h.angle.clone() == angle {
-                pos =
-                    360.0 - self.house[0].longitude as f32 + h.longitude as f32;
+                pos = 360.0 - self.get_asc_longitude() + h.longitude as f32;
                 break;
             }
         }
@@ -1804,8 +2177,7 @@ impl CalcDraw for WorkingStoragePolyMorphNatal {
         let mut pos: Number = 0.0;
         for b in self.object.clone() {
             if b.object_enum.clone() == bodie {
-                pos =
-                    360.0 - self.house[0].longitude as f32 + b.longitude as f32;
+                pos = 360.0 - self.get_asc_longitude() + b.longitude as f32;
                 break;
             }
         }
@@ -2198,14 +2570,26 @@ impl CalcDraw for WorkingStoragePolyMorphTransit {
         self.max_size / 2.0
     }
 
+    /// Longitude of the Ascendant, the chart is rotated on this value
+    fn get_asc_longitude(&self) -> Number {
+        let mut pos: Number = 0.0;
+        for a in self.angle.iter() {
+            if a.angle == Angle::Asc {
+                pos = a.longitude as Number;
+                break;
+            }
+        }
+        pos
+    }
+
+    /// Out of range: the radius total not visible
     fn get_radius_circle(&self, occurs: usize) -> (Number, bool) {
-        if occurs > CIRCLE_SIZE_TRANSIT.len() {
-            panic!("Out of range in circle occurs: {}", occurs);
+        match CIRCLE_SIZE_TRANSIT.get(occurs) {
+            Some(circle) => {
+                ((self.get_radius_total() * circle.0) / 100.0, circle.1)
+            },
+            None => (self.get_radius_total(), false),
         }
-        (
-            (self.get_radius_total() * CIRCLE_SIZE_TRANSIT[occurs].0) / 100.0,
-            CIRCLE_SIZE_TRANSIT[occurs].1,
-        )
     }
 
     fn get_radius_rules_inside_circle(
@@ -2399,37 +2783,18 @@ impl CalcDraw for WorkingStoragePolyMorphTransit {
             false
         }
     }
+    /// The bodies of the chart are the computed objects (BodySelection) of
+    /// the natal or of the transit ring
     fn get_bodie_is_on_chart(&self, bodie: Bodies) -> bool {
-        if bodie == Bodies::Sun
-            || bodie == Bodies::Moon
-            || bodie == Bodies::Mars
-            || bodie == Bodies::Mercury
-            || bodie == Bodies::Venus
-            || bodie == Bodies::Jupiter
-            || bodie == Bodies::Saturn
-            || bodie == Bodies::Uranus
-            || bodie == Bodies::Neptune
-            || bodie == Bodies::Pluto
-            || bodie == Bodies::TrueNode // North Node true
-            || bodie == Bodies::Chiron
-       //     || bodie == Bodies::MeanApog // AsteroidLilith != Dark moon mean
-            || bodie == Bodies::OscuApog // AsteroidLilith != Dark moon true
-            || bodie == Bodies::Ceres
-            || bodie == Bodies::SouthNode
-            || bodie == Bodies::FortunaPart
-        {
-            true
-        } else {
-            false
-        }
+        self.get_bodie_is_on_ring(bodie, false)
+            || self.get_bodie_is_on_ring(bodie, true)
     }
 
     fn get_angle_longitude(&self, angle: Angle) -> Number {
         let mut pos: Number = 0.0;
-        for h in self.house.clone() {
+        for h in self.angle.clone() {
             if h.angle.clone() == angle {
-                pos =
-                    360.0 - self.house[0].longitude as f32 + h.longitude as f32;
+                pos = 360.0 - self.get_asc_longitude() + h.longitude as f32;
                 break;
             }
         }
@@ -2442,16 +2807,14 @@ impl CalcDraw for WorkingStoragePolyMorphTransit {
         if sw_transit {
             for b in self.object_transit.clone() {
                 if b.object_enum.clone() == bodie {
-                    pos = 360.0 - self.house[0].longitude as f32
-                        + b.longitude as f32;
+                    pos = 360.0 - self.get_asc_longitude() + b.longitude as f32;
                     break;
                 }
             }
         } else {
             for b in self.object_natal.clone() {
                 if b.object_enum.clone() == bodie {
-                    pos = 360.0 - self.house[0].longitude as f32
-                        + b.longitude as f32;
+                    pos = 360.0 - self.get_asc_longitude() + b.longitude as f32;
                     break;
                 }
             }
@@ -2524,7 +2887,7 @@ impl CalcDraw for WorkingStoragePolyMorphTransit {
         }
         //println!("B");
         for b in Bodies::iter() {
-            if self.get_bodie_is_on_chart(b) {
+            if self.get_bodie_is_on_ring(b, sw_transit) {
                 i = i + 1;
                 let longitude = self.get_bodie_longitude(b, sw_transit);
                 temp_no_order.push(TempPositionBodies {
@@ -0,0 +1,108 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+//! Eclipse functions of the swiss ephemeris
+//! They are compiled in libswe-sys (swecl.c) but not wrapped in swerust
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_double, c_int};
+
+extern "C" {
+    fn swe_sol_eclipse_when_glob(
+        tjd_start: c_double,
+        ifl: c_int,
+        ifltype: c_int,
+        tret: *mut c_double,
+        backward: c_int,
+        serr: *mut c_char,
+    ) -> c_int;
+    fn swe_lun_eclipse_when(
+        tjd_start: c_double,
+        ifl: c_int,
+        ifltype: c_int,
+        tret: *mut c_double,
+        backward: c_int,
+        serr: *mut c_char,
+    ) -> c_int;
+}
+
+/// Type of eclipse in result (SE_ECL_*)
+pub const ECL_TOTAL: i32 = 4;
+pub const ECL_ANNULAR: i32 = 8;
+pub const ECL_PARTIAL: i32 = 16;
+pub const ECL_ANNULAR_TOTAL: i32 = 32;
+
+/// result: type of eclipse or -1 (error in serr)
+/// tret[0]: julian day (UT) of the maximum
+#[derive(Debug)]
+pub struct EclipseWhenResult {
+    pub result: i32,
+    pub tret: [f64; 10],
+    pub serr: String,
+}
+
+/// Next (or previous if backward) solar eclipse on the earth
+pub fn sol_eclipse_when_glob(
+    tjd_start: f64,
+    iflag: i32,
+    backward: bool,
+) -> EclipseWhenResult {
+    let mut tret: [f64; 10] = [0.0; 10];
+    let mut serr: [c_char; 256] = [0; 256];
+    let result = unsafe {
+        swe_sol_eclipse_when_glob(
+            tjd_start,
+            iflag,
+            0,
+            tret.as_mut_ptr(),
+            backward as c_int,
+            serr.as_mut_ptr(),
+        )
+    };
+    EclipseWhenResult {
+        result,
+        tret,
+        serr: unsafe { CStr::from_ptr(serr.as_ptr()) }
+            .to_string_lossy()
+            .into_owned(),
+    }
+}
+
+/// Next (or previous if backward) lunar eclipse
+pub fn lun_eclipse_when(
+    tjd_start: f64,
+    iflag: i32,
+    backward: bool,
+) -> EclipseWhenResult {
+    let mut tret: [f64; 10] = [0.0; 10];
+    let mut serr: [c_char; 256] = [0; 256];
+    let result = unsafe {
+        swe_lun_eclipse_when(
+            tjd_start,
+            iflag,
+            0,
+            tret.as_mut_ptr(),
+            backward as c_int,
+            serr.as_mut_ptr(),
+        )
+    };
+    EclipseWhenResult {
+        result,
+        tret,
+        serr: unsafe { CStr::from_ptr(serr.as_ptr()) }
+            .to_string_lossy()
+            .into_owned(),
+    }
+}
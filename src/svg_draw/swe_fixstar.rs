@@ -0,0 +1,103 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+//! Fixed star functions of the swiss ephemeris (sweph.c), the stars are read
+//! in sefstars.txt of the path of the ephemeris files
+//! They are compiled in libswe-sys but not wrapped in swerust
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_double, c_int};
+
+// Size of the name of the star (in and out), 2 * SE_MAX_STNAME
+const STAR_SIZE: usize = 512;
+
+extern "C" {
+    fn swe_fixstar2_ut(
+        star: *mut c_char,
+        tjd_ut: c_double,
+        iflag: c_int,
+        xx: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+    fn swe_fixstar2_mag(
+        star: *mut c_char,
+        mag: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+}
+
+/// result: flag or -1 (error in serr)
+/// star: "traditional name,nomenclature name" of the catalog
+/// longitude/latitude: ecliptic or equatorial (right ascension/declination)
+/// with the flag EquatorialPosition
+#[derive(Debug)]
+pub struct FixstarResult {
+    pub result: i32,
+    pub star: String,
+    pub longitude: f64,
+    pub latitude: f64,
+    pub serr: String,
+}
+
+/// Name of the star in a buffer for swe_fixstar2_*
+fn star_buffer(star: &str) -> [c_char; STAR_SIZE] {
+    let mut buffer: [c_char; STAR_SIZE] = [0; STAR_SIZE];
+    for (b, c) in buffer.iter_mut().zip(star.bytes().take(STAR_SIZE - 1)) {
+        *b = c as c_char;
+    }
+    buffer
+}
+
+/// String of a C buffer
+fn to_string(buffer: &[c_char]) -> String {
+    unsafe { CStr::from_ptr(buffer.as_ptr()) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Position of a fixed star at a julian day (UT), star is a traditional name
+/// ("Regulus"), a nomenclature name (",alLeo") or a number in the catalog
+pub fn fixstar2_ut(star: &str, tjd_ut: f64, iflag: i32) -> FixstarResult {
+    let mut star = star_buffer(star);
+    let mut xx: [c_double; 6] = [0.0; 6];
+    let mut serr: [c_char; 256] = [0; 256];
+    let result = unsafe {
+        swe_fixstar2_ut(
+            star.as_mut_ptr(),
+            tjd_ut,
+            iflag,
+            xx.as_mut_ptr(),
+            serr.as_mut_ptr(),
+        )
+    };
+    FixstarResult {
+        result,
+        star: to_string(&star),
+        longitude: xx[0],
+        latitude: xx[1],
+        serr: to_string(&serr),
+    }
+}
+
+/// Visual magnitude of a fixed star, (result, magnitude, serr)
+pub fn fixstar2_mag(star: &str) -> (i32, f64, String) {
+    let mut star = star_buffer(star);
+    let mut mag: c_double = 0.0;
+    let mut serr: [c_char; 256] = [0; 256];
+    let result = unsafe {
+        swe_fixstar2_mag(star.as_mut_ptr(), &mut mag, serr.as_mut_ptr())
+    };
+    (result, mag, to_string(&serr))
+}
@@ -0,0 +1,86 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+//! Rising and setting of the swiss ephemeris
+//! It is compiled in libswe-sys (swecl.c) but not wrapped in swerust
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_double, c_int};
+use std::ptr;
+
+extern "C" {
+    fn swe_rise_trans(
+        tjd_ut: c_double,
+        ipl: c_int,
+        starname: *mut c_char,
+        epheflag: c_int,
+        rsmi: c_int,
+        geopos: *mut c_double,
+        atpress: c_double,
+        attemp: c_double,
+        tret: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+}
+
+/// Event of rise_trans (SE_CALC_*)
+pub const CALC_RISE: i32 = 1;
+pub const CALC_SET: i32 = 2;
+
+/// result: 0, -2 if the bodie doesn't rise or set (circumpolar) or -1 (error
+/// in serr)
+/// julian_day: julian day (UT) of the event
+#[derive(Debug)]
+pub struct RiseTransResult {
+    pub result: i32,
+    pub julian_day: f64,
+    pub serr: String,
+}
+
+/// Next rising or setting (rsmi) of a planet after tjd_ut at a location,
+/// upper limb of the disc with the refraction of the standard atmosphere
+pub fn rise_trans(
+    tjd_ut: f64,
+    ipl: i32,
+    epheflag: i32,
+    rsmi: i32,
+    lat: f64,
+    lng: f64,
+) -> RiseTransResult {
+    let mut geopos: [f64; 3] = [lng, lat, 0.0];
+    let mut tret: f64 = 0.0;
+    let mut serr: [c_char; 256] = [0; 256];
+    let result = unsafe {
+        swe_rise_trans(
+            tjd_ut,
+            ipl,
+            ptr::null_mut(),
+            epheflag,
+            rsmi,
+            geopos.as_mut_ptr(),
+            0.0,
+            0.0,
+            &mut tret,
+            serr.as_mut_ptr(),
+        )
+    };
+    RiseTransResult {
+        result,
+        julian_day: tret,
+        serr: unsafe { CStr::from_ptr(serr.as_ptr()) }
+            .to_string_lossy()
+            .into_owned(),
+    }
+}
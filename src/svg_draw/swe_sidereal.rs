@@ -0,0 +1,60 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+//! Sidereal functions of the swiss ephemeris (swephlib.c)
+//! They are compiled in libswe-sys but not wrapped in swerust
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_double, c_int};
+
+extern "C" {
+    fn swe_set_sid_mode(sid_mode: c_int, t0: c_double, ayan_t0: c_double);
+    fn swe_get_ayanamsa_ex_ut(
+        tjd_ut: c_double,
+        ephe_flag: c_int,
+        daya: *mut c_double,
+        serr: *mut c_char,
+    ) -> c_int;
+}
+
+/// result: flag or -1 (error in serr)
+/// ayanamsa: in degree, with the nutation
+#[derive(Debug)]
+pub struct AyanamsaResult {
+    pub result: i32,
+    pub ayanamsa: f64,
+    pub serr: String,
+}
+
+/// Set the ayanamsa (SE_SIDM_*) of the sidereal computations
+pub fn set_sid_mode(sid_mode: i32) {
+    unsafe { swe_set_sid_mode(sid_mode, 0.0, 0.0) }
+}
+
+/// Ayanamsa of the sid mode at a julian day (UT)
+pub fn get_ayanamsa_ex_ut(tjd_ut: f64, iflag: i32) -> AyanamsaResult {
+    let mut ayanamsa: f64 = 0.0;
+    let mut serr: [c_char; 256] = [0; 256];
+    let result = unsafe {
+        swe_get_ayanamsa_ex_ut(tjd_ut, iflag, &mut ayanamsa, serr.as_mut_ptr())
+    };
+    AyanamsaResult {
+        result,
+        ayanamsa,
+        serr: unsafe { CStr::from_ptr(serr.as_ptr()) }
+            .to_string_lossy()
+            .into_owned(),
+    }
+}
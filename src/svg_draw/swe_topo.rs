@@ -0,0 +1,29 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+//! Topocentric function of the swiss ephemeris (sweph.c)
+//! It is compiled in libswe-sys but not wrapped in swerust
+use std::os::raw::c_double;
+
+extern "C" {
+    fn swe_set_topo(geolon: c_double, geolat: c_double, geoalt: c_double);
+}
+
+/// Set the location of the observer for the topocentric positions (flag
+/// TopocentricPosition), altitude in meter above sea level
+pub fn set_topo(geolon: f64, geolat: f64, geoalt: f64) {
+    unsafe { swe_set_topo(geolon, geolat, geoalt) }
+}
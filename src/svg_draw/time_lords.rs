@@ -0,0 +1,389 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+//! Time lords of a natal chart: annual and monthly profections, zodiacal
+//! releasing from a lot and firdaria, timelines of periods with their start
+//! and end
+use super::chart_data::{julian_day, ChartData, DateTimeUtc};
+use super::compute_chart::DataChartNatal;
+use super::dignities::{rulers, CHALDEAN_ORDER};
+use super::progression::TROPICAL_YEAR;
+use super::serde_sweconst;
+use crate::error::AstrologyError;
+use libswe_sys::sweconst::{Angle, Bodies, Signs};
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+// Zodiacal releasing: days of one minor year of a planet for each level
+// (Valens: year of 360 days, month of 30 days, 2.5 days and 5 hours)
+const RELEASING_UNITS: [f64; 4] = [360.0, 30.0, 2.5, 5.0 / 24.0];
+
+// Minor years of the ruler of each sign (Aries to Pisces), Aquarius has the
+// 30 years of Saturn instead of 27
+const MINOR_YEARS: [f64; 12] = [
+    15.0, 8.0, 20.0, 25.0, 19.0, 20.0, 8.0, 15.0, 12.0, 27.0, 30.0, 12.0,
+];
+
+// Firdaria of a day birth and of a night birth: lord and years, the cycle of
+// 75 years begins again after the South Node
+const FIRDARIA_DAY: [(Bodies, f64); 9] = [
+    (Bodies::Sun, 10.0),
+    (Bodies::Venus, 8.0),
+    (Bodies::Mercury, 13.0),
+    (Bodies::Moon, 9.0),
+    (Bodies::Saturn, 11.0),
+    (Bodies::Jupiter, 12.0),
+    (Bodies::Mars, 7.0),
+    (Bodies::TrueNode, 3.0),
+    (Bodies::SouthNode, 2.0),
+];
+const FIRDARIA_NIGHT: [(Bodies, f64); 9] = [
+    (Bodies::Moon, 9.0),
+    (Bodies::Saturn, 11.0),
+    (Bodies::Jupiter, 12.0),
+    (Bodies::Mars, 7.0),
+    (Bodies::Sun, 10.0),
+    (Bodies::Venus, 8.0),
+    (Bodies::Mercury, 13.0),
+    (Bodies::TrueNode, 3.0),
+    (Bodies::SouthNode, 2.0),
+];
+
+/// Annual or monthly profection of the natal Asc, the years begin at the
+/// birth and last a tropical year, the months are twelfths of the year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profection {
+    /// Age at the start of the year of the profection
+    pub age: i32,
+    /// Month of the year of the profection (1 to 12), 0 for the annual
+    /// profection
+    pub month: i32,
+    #[serde(with = "serde_sweconst::signs")]
+    pub sign: Signs,
+    /// Whole sign house from the natal Asc
+    pub house: i32,
+    /// Lord of the year (month): ruler of the domicile of the sign
+    #[serde(with = "serde_sweconst::bodies")]
+    pub lord: Bodies,
+    /// Julian days (UT) of the start and the end
+    pub start: f64,
+    pub end: f64,
+    pub start_date: Option<DateTimeUtc>,
+    pub end_date: Option<DateTimeUtc>,
+}
+
+/// Period of the zodiacal releasing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleasingPeriod {
+    /// Level 1 to 4 (periods of years, months, days and hours)
+    pub level: u32,
+    #[serde(with = "serde_sweconst::signs")]
+    pub sign: Signs,
+    #[serde(with = "serde_sweconst::bodies")]
+    pub lord: Bodies,
+    /// Loosing of the bond: when the sequence returns to the first sign of
+    /// the level, the period jumps to the sign opposite to this first sign
+    pub loosing_of_bond: bool,
+    /// Julian days (UT) of the start and the end
+    pub start: f64,
+    pub end: f64,
+    pub start_date: Option<DateTimeUtc>,
+    pub end_date: Option<DateTimeUtc>,
+}
+
+/// Period (sub_lord None) or sub period of the firdaria
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FirdarPeriod {
+    #[serde(with = "serde_sweconst::bodies")]
+    pub lord: Bodies,
+    /// Lord of the sub period, the seven sub periods follow the Chaldean
+    /// order from the lord (the nodes have no sub period)
+    #[serde(with = "serde_sweconst::bodies_option")]
+    pub sub_lord: Option<Bodies>,
+    /// Julian days (UT) of the start and the end
+    pub start: f64,
+    pub end: f64,
+    pub start_date: Option<DateTimeUtc>,
+    pub end_date: Option<DateTimeUtc>,
+}
+
+/// Annual profections from age_start to age_end, each followed by its
+/// monthly profections if monthly
+pub fn profections(
+    natal: &ChartData,
+    age_start: i32,
+    age_end: i32,
+    monthly: bool,
+) -> Result<Vec<Profection>, AstrologyError> {
+    if age_start < 0 || age_end < age_start {
+        return Err(AstrologyError::InvalidArgument(format!(
+            "invalid range of ages: {} to {}",
+            age_start, age_end
+        )));
+    }
+    let asc = asc_sign(natal)?;
+    let mut res: Vec<Profection> = Vec::new();
+    for age in age_start..=age_end {
+        res.push(profection(natal, asc, age, 0));
+        if monthly {
+            for month in 1..=12 {
+                res.push(profection(natal, asc, age, month));
+            }
+        }
+    }
+    Ok(res)
+}
+
+/// Annual and monthly profection at a date
+pub fn profections_at(
+    natal: &ChartData,
+    date: DataChartNatal,
+) -> Result<Vec<Profection>, AstrologyError> {
+    let julian_day = julian_day(&date)?;
+    if julian_day < natal.julian_day {
+        return Err(AstrologyError::InvalidDateTime(format!(
+            "the date {}.{}.{} is before the birth",
+            date.day, date.month, date.year
+        )));
+    }
+    let asc = asc_sign(natal)?;
+    let years = (julian_day - natal.julian_day) / TROPICAL_YEAR;
+    let age = years.floor() as i32;
+    let month = ((years - age as f64) * 12.0).floor() as i32 + 1;
+    Ok(vec![
+        profection(natal, asc, age, 0),
+        profection(natal, asc, age, month.min(12)),
+    ])
+}
+
+/// Zodiacal releasing from a lot of natal.lots (Spirit or Fortune of the
+/// hermetic lots), periods of the levels 1 to levels between start and end
+/// The sub periods of a period begin with its sign and are cut at its end
+pub fn zodiacal_releasing(
+    natal: &ChartData,
+    lot: &str,
+    start: DataChartNatal,
+    end: DataChartNatal,
+    levels: u32,
+) -> Result<Vec<ReleasingPeriod>, AstrologyError> {
+    if levels < 1 || levels as usize > RELEASING_UNITS.len() {
+        return Err(AstrologyError::InvalidArgument(format!(
+            "levels of the zodiacal releasing: {} (1 to {})",
+            levels,
+            RELEASING_UNITS.len()
+        )));
+    }
+    let range = date_range(start, end)?;
+    let longitude = natal
+        .lots
+        .iter()
+        .find(|l| l.name == lot)
+        .map(|l| l.longitude)
+        .ok_or_else(|| {
+            AstrologyError::InvalidArgument(format!(
+                "the lot {} isn't in the chart (ChartOptions::lots)",
+                lot
+            ))
+        })?;
+    let mut res: Vec<ReleasingPeriod> = Vec::new();
+    release(
+        1,
+        levels,
+        (longitude / 30.0) as usize % 12,
+        (natal.julian_day, f64::INFINITY),
+        range,
+        &mut res,
+    );
+    Ok(res)
+}
+
+/// Firdaria of a day or night birth (natal.diurnal) between start and end,
+/// each period followed by its sub periods
+pub fn firdaria(
+    natal: &ChartData,
+    start: DataChartNatal,
+    end: DataChartNatal,
+) -> Result<Vec<FirdarPeriod>, AstrologyError> {
+    let range = date_range(start, end)?;
+    let table = if natal.diurnal {
+        FIRDARIA_DAY
+    } else {
+        FIRDARIA_NIGHT
+    };
+    let mut res: Vec<FirdarPeriod> = Vec::new();
+    let mut julian_day = natal.julian_day;
+    for (lord, years) in table.iter().cycle() {
+        if julian_day > range.1 {
+            break;
+        }
+        let period_end = julian_day + years * TROPICAL_YEAR;
+        if period_end > range.0 {
+            res.push(firdar_period(*lord, None, julian_day, period_end));
+            if let Some(first) = CHALDEAN_ORDER.iter().position(|b| b == lord) {
+                let length = (period_end - julian_day) / 7.0;
+                for i in 0..7 {
+                    let sub_start = julian_day + length * i as f64;
+                    let sub_end = sub_start + length;
+                    if sub_end > range.0 && sub_start <= range.1 {
+                        res.push(firdar_period(
+                            *lord,
+                            Some(CHALDEAN_ORDER[(first + i) % 7]),
+                            sub_start,
+                            sub_end,
+                        ));
+                    }
+                }
+            }
+        }
+        julian_day = period_end;
+    }
+    Ok(res)
+}
+
+/// Periods of a level inside the period (start, end) of the level above,
+/// beginning with sign, and their sub periods down to levels
+/// The twelve signs last 211 units, each return of the sequence to sign is a
+/// loosing of the bond (twice at the level 2 of the periods of Capricorn and
+/// Aquarius, of 324 and 360 months)
+fn release(
+    level: u32,
+    levels: u32,
+    sign: usize,
+    period: (f64, f64),
+    range: (f64, f64),
+    res: &mut Vec<ReleasingPeriod>,
+) {
+    let unit = RELEASING_UNITS[level as usize - 1];
+    let mut current = sign;
+    let mut julian_day = period.0;
+    while julian_day < period.1 && julian_day <= range.1 {
+        let loosing_of_bond = current == sign && julian_day > period.0;
+        if loosing_of_bond {
+            current = (sign + 6) % 12;
+        }
+        let end = (julian_day + MINOR_YEARS[current] * unit).min(period.1);
+        if end > range.0 {
+            res.push(ReleasingPeriod {
+                level,
+                sign: sign_of(current),
+                lord: lord_of(current),
+                loosing_of_bond,
+                start: julian_day,
+                end,
+                start_date: DateTimeUtc::from_julian_day(julian_day),
+                end_date: DateTimeUtc::from_julian_day(end),
+            });
+            if level < levels {
+                release(
+                    level + 1,
+                    levels,
+                    current,
+                    (julian_day, end),
+                    range,
+                    res,
+                );
+            }
+        }
+        julian_day = end;
+        current = (current + 1) % 12;
+    }
+}
+
+/// Profection of the year at age (month 0) or of a month of this year
+fn profection(
+    natal: &ChartData,
+    asc: usize,
+    age: i32,
+    month: i32,
+) -> Profection {
+    let offset = age as usize + (month.max(1) - 1) as usize;
+    let sign = (asc + offset) % 12;
+    let (start, length) = if month == 0 {
+        (natal.julian_day + age as f64 * TROPICAL_YEAR, TROPICAL_YEAR)
+    } else {
+        (
+            natal.julian_day
+                + age as f64 * TROPICAL_YEAR
+                + (month - 1) as f64 * TROPICAL_YEAR / 12.0,
+            TROPICAL_YEAR / 12.0,
+        )
+    };
+    let end = start + length;
+    Profection {
+        age,
+        month,
+        sign: sign_of(sign),
+        house: (offset % 12) as i32 + 1,
+        lord: lord_of(sign),
+        start,
+        end,
+        start_date: DateTimeUtc::from_julian_day(start),
+        end_date: DateTimeUtc::from_julian_day(end),
+    }
+}
+
+/// Period of the firdaria
+fn firdar_period(
+    lord: Bodies,
+    sub_lord: Option<Bodies>,
+    start: f64,
+    end: f64,
+) -> FirdarPeriod {
+    FirdarPeriod {
+        lord,
+        sub_lord,
+        start,
+        end,
+        start_date: DateTimeUtc::from_julian_day(start),
+        end_date: DateTimeUtc::from_julian_day(end),
+    }
+}
+
+/// Sign of the natal Asc (0 = Aries)
+fn asc_sign(natal: &ChartData) -> Result<usize, AstrologyError> {
+    natal
+        .angle_longitude(Angle::Asc)
+        .map(|asc| (asc / 30.0) as usize % 12)
+        .ok_or_else(|| {
+            AstrologyError::InvalidArgument("the chart has no Asc".to_string())
+        })
+}
+
+/// Julian days of start and end, end can't be before start
+fn date_range(
+    start: DataChartNatal,
+    end: DataChartNatal,
+) -> Result<(f64, f64), AstrologyError> {
+    let julian_day_start = julian_day(&start)?;
+    let julian_day_end = julian_day(&end)?;
+    if julian_day_end < julian_day_start {
+        return Err(AstrologyError::InvalidDateTime(format!(
+            "the end of the period ({}.{}.{}) is before the start ({}.{}.{})",
+            end.day, end.month, end.year, start.day, start.month, start.year
+        )));
+    }
+    Ok((julian_day_start, julian_day_end))
+}
+
+/// Sign of an index (0 = Aries)
+fn sign_of(index: usize) -> Signs {
+    Signs::iter().nth(index % 12).unwrap_or(Signs::Aries)
+}
+
+/// Ruler of the domicile of a sign (0 = Aries)
+fn lord_of(index: usize) -> Bodies {
+    rulers((index % 12) as f64 * 30.0).domicile
+}
@@ -0,0 +1,278 @@
+/*
+ * Traditional astrology for rust
+ * ==============================
+ *
+ * Rust library by Stéphane (https://github.com/stephaneworkspace)
+ *
+ * Using swissephem c library by Astrodienst AG
+ * by Dieter Koch and Alois Treindl (https://www.astro.com/ftp/swisseph/)
+ *
+ * The source code is released under an CC License, which allows it to be used
+ * also on commercial projects. This software uses the swiss ephemeris which is
+ * licensed GPL.
+ *
+ * Therefore, if you want to this source in your commercial projects, you must
+ * adhere to the GPL license or buy a Swiss Ephemeris commercial license.
+ */
+//! Search of the transits to a natal chart over a date range
+//! The aspects are the ones of the transit chart (AspectConfig of
+//! ChartOptions), each transit bodie is followed step by step and the events
+//! (ingress into the orb, exact, leaving the orb) are refined by bisection
+use super::aspect_config::ChartAspect;
+use super::chart_data::{
+    calc_bodie, calc_error, compute_natal, julian_day, signed_distance,
+    ChartPoint, DateTimeUtc,
+};
+use super::compute_chart::{ChartOptions, DataChartNatal};
+use super::serde_sweconst;
+use crate::error::AstrologyError;
+use libswe_sys::sweconst::Bodies;
+use libswe_sys::swerust::handler_swe17;
+use serde::{Deserialize, Serialize};
+
+// Step of the search in days, the Moon moves ~1° in 2 hours
+const STEP: f64 = 0.5;
+const MOON_STEP: f64 = 1.0 / 12.0;
+// ~0.05 second for a step of 0.5 day
+const BISECTION_ITERATIONS: usize = 20;
+
+/// Kind of a transit event
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TransitEventKind {
+    /// The transit bodie enters the orb of the aspect
+    Enter,
+    /// The aspect is exact (several times with a retrograde bodie)
+    Exact,
+    /// The transit bodie leaves the orb of the aspect
+    Leave,
+}
+
+/// Event of a transit bodie to a natal point
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitEvent {
+    pub kind: TransitEventKind,
+    pub aspect: ChartAspect,
+    /// Natal bodie or Asc/Mc
+    pub natal: ChartPoint,
+    #[serde(with = "serde_sweconst::bodies")]
+    pub transit: Bodies,
+    /// Julian day (UT) of the event
+    pub julian_day: f64,
+    pub date: Option<DateTimeUtc>,
+    /// Longitude of the transit bodie at the event
+    pub longitude: f64,
+    pub retrograde: bool,
+}
+
+/// Transit events to the natal bodies and Asc/Mc between start and end,
+/// sorted by date
+/// An aspect already in orb at start has no Enter event (and no Leave event
+/// if still in orb at end), the location of start is the observer of a
+/// topocentric chart and the transit bodies are the ones of options.bodies
+/// without the Part of Fortune (moving with the Asc)
+pub fn transit_search(
+    data: DataChartNatal,
+    start: DataChartNatal,
+    end: DataChartNatal,
+    path: &str,
+    options: &ChartOptions,
+) -> Result<Vec<TransitEvent>, AstrologyError> {
+    let julian_day_start = julian_day(&start)?;
+    let julian_day_end = julian_day(&end)?;
+    if julian_day_end < julian_day_start {
+        return Err(AstrologyError::InvalidDateTime(format!(
+            "the end of the search ({}.{}.{}) is before the start ({}.{}.{})",
+            end.day, end.month, end.year, start.day, start.month, start.year
+        )));
+    }
+    let natal = compute_natal(data, path, options)?;
+    options.calc.set_topo(start.lat as f64, start.lng as f64);
+    let natal_points = natal.points();
+    let mut events: Vec<TransitEvent> = Vec::new();
+    let bodies = options.bodies.list();
+    for bodie in bodies.iter().filter(|b| **b != Bodies::FortunaPart) {
+        let step = if *bodie == Bodies::Moon {
+            MOON_STEP
+        } else {
+            STEP
+        };
+        let mut jd = julian_day_start;
+        let mut lng = zodiac_position(*bodie, jd, options)?.0;
+        while jd < julian_day_end {
+            let jd_next = (jd + step).min(julian_day_end);
+            let lng_next = zodiac_position(*bodie, jd_next, options)?.0;
+            for (point, lng_natal, _) in natal_points.iter().copied() {
+                search_step(
+                    &mut events,
+                    options,
+                    *bodie,
+                    (point, lng_natal),
+                    (jd, lng),
+                    (jd_next, lng_next),
+                )?;
+            }
+            jd = jd_next;
+            lng = lng_next;
+        }
+    }
+    events.sort_by(|a, b| {
+        a.julian_day
+            .partial_cmp(&b.julian_day)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    Ok(events)
+}
+
+/// Events of one transit bodie to one natal point between two steps
+fn search_step(
+    events: &mut Vec<TransitEvent>,
+    options: &ChartOptions,
+    bodie: Bodies,
+    (point, lng_natal): (ChartPoint, f64),
+    (jd_1, lng_1): (f64, f64),
+    (jd_2, lng_2): (f64, f64),
+) -> Result<(), AstrologyError> {
+    let transit = ChartPoint::Bodie(bodie);
+    let aspect_at = |lng: f64| {
+        options
+            .aspects
+            .find_aspect(point, lng_natal, transit, lng)
+            .map(|(aspect, _)| aspect)
+    };
+    let aspect_1 = aspect_at(lng_1);
+    let aspect_2 = aspect_at(lng_2);
+    if aspect_1 != aspect_2 {
+        // Border of the orb
+        let jd = bisection(jd_1, jd_2, |jd| {
+            Ok(aspect_at(zodiac_position(bodie, jd, options)?.0) == aspect_1)
+        })?;
+        if let Some(aspect) = aspect_1 {
+            events.push(event(
+                TransitEventKind::Leave,
+                aspect,
+                point,
+                bodie,
+                jd,
+                options,
+            )?);
+        }
+        if let Some(aspect) = aspect_2 {
+            events.push(event(
+                TransitEventKind::Enter,
+                aspect,
+                point,
+                bodie,
+                jd,
+                options,
+            )?);
+        }
+    }
+    let mut aspects: Vec<ChartAspect> =
+        aspect_1.into_iter().chain(aspect_2).collect();
+    aspects.dedup();
+    for aspect in aspects {
+        for exact in exact_longitudes(lng_natal, aspect.angle()) {
+            let distance_1 = signed_distance(exact, lng_1);
+            let distance_2 = signed_distance(exact, lng_2);
+            // Crossing of the exact longitude (not of the opposite point)
+            if distance_1.signum() != distance_2.signum()
+                && (distance_1 - distance_2).abs() < 180.0
+            {
+                let jd = bisection(jd_1, jd_2, |jd| {
+                    Ok(signed_distance(
+                        exact,
+                        zodiac_position(bodie, jd, options)?.0,
+                    )
+                    .signum()
+                        == distance_1.signum())
+                })?;
+                events.push(event(
+                    TransitEventKind::Exact,
+                    aspect,
+                    point,
+                    bodie,
+                    jd,
+                    options,
+                )?);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Longitudes of the exact aspect to a natal longitude
+fn exact_longitudes(lng_natal: f64, angle: f64) -> Vec<f64> {
+    if angle == 0.0 || angle == 180.0 {
+        vec![handler_swe17::degnorm(lng_natal + angle)]
+    } else {
+        vec![
+            handler_swe17::degnorm(lng_natal + angle),
+            handler_swe17::degnorm(lng_natal - angle),
+        ]
+    }
+}
+
+/// Last julian day between jd_1 and jd_2 where before is true, before(jd_1)
+/// is true and before(jd_2) is false
+pub(crate) fn bisection<F>(
+    jd_1: f64,
+    jd_2: f64,
+    before: F,
+) -> Result<f64, AstrologyError>
+where
+    F: Fn(f64) -> Result<bool, AstrologyError>,
+{
+    let mut a = jd_1;
+    let mut b = jd_2;
+    for _ in 0..BISECTION_ITERATIONS {
+        let m = (a + b) / 2.0;
+        if before(m)? {
+            a = m;
+        } else {
+            b = m;
+        }
+    }
+    Ok((a + b) / 2.0)
+}
+
+/// Event at a julian day
+fn event(
+    kind: TransitEventKind,
+    aspect: ChartAspect,
+    natal: ChartPoint,
+    bodie: Bodies,
+    julian_day: f64,
+    options: &ChartOptions,
+) -> Result<TransitEvent, AstrologyError> {
+    let (longitude, speed) = zodiac_position(bodie, julian_day, options)?;
+    Ok(TransitEvent {
+        kind,
+        aspect,
+        natal,
+        transit: bodie,
+        julian_day,
+        date: DateTimeUtc::from_julian_day(julian_day),
+        longitude,
+        retrograde: speed < 0.0,
+    })
+}
+
+/// Longitude in the zodiac and from the center of options and speed of a
+/// transit bodie (South Node of the nodes of options)
+pub(crate) fn zodiac_position(
+    bodie: Bodies,
+    julian_day: f64,
+    options: &ChartOptions,
+) -> Result<(f64, f64), AstrologyError> {
+    let calc = calc_bodie(
+        julian_day,
+        bodie,
+        options.calc.flag(),
+        options.bodies.nodes,
+    );
+    calc_error(bodie, &calc)?;
+    Ok((
+        options.zodiac.longitude(calc.longitude, julian_day)?,
+        calc.speed_longitude,
+    ))
+}